@@ -0,0 +1,565 @@
+//! Rough alignment: estimated timing for a transcript that is already known
+//! word for word (a stenographer's record, an official transcript), so the
+//! captions carry its exact wording instead of what recognition heard.
+//!
+//! This is a heuristic, not forced alignment: no acoustic model scores the
+//! words against the sound, so boundaries inside a run of speech are
+//! estimates. Real alignment waits on a model to run the known tokens
+//! through.
+//!
+//! The audio is cut into 20 ms frames and marked as speech or pause, and a
+//! dynamic program lays the words over the stretches of speech in order:
+//! each stretch should hold as many syllables as its length allows at the
+//! speaker's rate, and the pauses between stretches should fall where the
+//! text has punctuation. Within a stretch the words are spread by syllable
+//! count, and each boundary between them moves to a nearby dip in loudness
+//! when there is one. Stretches inside applause, cheering, laughter or booing (found as
+//! for the reaction cues) are free to leave without words and costly to fill.
+//!
+//! Each word gets its own confidence: how well the time it ends up with
+//! fits its syllables at the speaker's rate, whether a pause or a dip bears
+//! out its edges, and how little of it is a reaction. Text that was not
+//! said, or speech missing from the text, overfills or starves the time
+//! around it and leaves boundaries where the audio has none, so a run of
+//! low-confidence words marks where the record and the recording disagree.
+
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+use crate::audio::AudioSignal;
+use crate::emphasis::{percentile, rms_db};
+use crate::import::split_speaker_prefix;
+use crate::reactions::{self, ReactionKind};
+use crate::sentences::sentence_pieces;
+use crate::{BasicSegment, WordTiming};
+
+/// Below this a word is reported as a likely mismatch
+pub const LOW_CONFIDENCE: f32 = 0.5;
+
+/// Speech/pause frame length
+const FRAME_SECONDS: f64 = 0.020;
+/// Quieter gaps shorter than this are inside a phrase (stops, breaths)
+const MIN_PAUSE_SECONDS: f64 = 0.15;
+/// Speech shorter than this is a click or a bump, not a word
+const MIN_SPEECH_SECONDS: f64 = 0.08;
+/// Share of a stretch inside an audience reaction for it to be one
+const REACTION_OVERLAP: f64 = 0.5;
+/// Speech threshold, as a share of the way from the noise floor to the
+/// speech level, and at least this many dB above the floor
+const THRESHOLD_FRACTION: f32 = 0.3;
+const MIN_THRESHOLD_DB: f32 = 6.0;
+/// How far (in words) a stretch may start from where the speaking time
+/// alone would put it; bounds the search on long recordings
+const SEARCH_BAND_WORDS: usize = 250;
+/// Most words one placement can hold
+const MAX_PLACEMENT_WORDS: usize = 60;
+/// Stretches one placement may run across, for a pause the text has no
+/// break for
+const MAX_MERGED_STRETCHES: usize = 3;
+/// Alignment costs: misfit is the squared log ratio of a placement's speech
+/// time to what its syllables need, per second they need
+const FIT_WEIGHT: f64 = 3.0;
+/// Running a placement across a pause, plus per second of pause
+const MERGE_COST: f64 = 1.5;
+const MERGE_COST_PER_SECOND: f64 = 6.0;
+/// A stretch holding no words (a cough, crosstalk, speech missing from the
+/// text); nothing for a reaction
+const EMPTY_COST: f64 = 2.5;
+/// Words over a reaction, per second of it
+const REACTION_COST_PER_SECOND: f64 = 2.0;
+/// Likelihood of a pause after a word, by its punctuation
+const SENTENCE_PAUSE: f64 = 0.7;
+const CLAUSE_PAUSE: f64 = 0.4;
+const WORD_PAUSE: f64 = 0.08;
+/// How far a boundary between two words of a stretch may move to a dip
+const BOUNDARY_REACH_SECONDS: f64 = 0.15;
+/// A dip at least this far below the loudness on both sides of it is a gap
+/// between words
+const MIN_DIP_DB: f32 = 3.0;
+/// Shortest word a boundary move may leave
+const MIN_WORD_SECONDS: f64 = 0.06;
+/// Misfit (log ratio) at which confidence falls to about 0.6
+const CONFIDENCE_SPREAD: f64 = 0.5;
+/// Confidence lost for each edge of a word that neither a pause nor a dip
+/// bears out
+const UNHEARD_EDGE_PENALTY: f64 = 0.2;
+/// Syllables a digit takes to say ("2024" is five)
+const DIGIT_SYLLABLES: f64 = 1.5;
+/// Weight of a token with no letters or digits ("--")
+const SYMBOL_WEIGHT: f64 = 0.2;
+/// Longest output segment; longer sentences are cut at a comma if possible
+const MAX_SEGMENT_WORDS: usize = 24;
+
+/// A piece of the known text that becomes one output segment
+pub struct TextSegment {
+    pub speaker: Option<String>,
+    pub words: Vec<String>,
+}
+
+/// The known text: paragraphs split into sentences, with `SPEAKER:` labels
+/// (as in court and hearing records) carried until the next label
+pub async fn load_text(path: &Path) -> Result<Vec<TextSegment>> {
+    let content = tokio::fs::read_to_string(path).await
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    let segments = parse_text(content.trim_start_matches('\u{feff}'));
+    if segments.is_empty() {
+        return Err(anyhow!("{:?} has no text to align", path));
+    }
+    Ok(segments)
+}
+
+fn parse_text(content: &str) -> Vec<TextSegment> {
+    let mut paragraphs: Vec<(Option<String>, String)> = Vec::new();
+    let mut speaker: Option<String> = None;
+    let mut current = String::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        let (label, text) = split_speaker_prefix(line);
+        if (line.is_empty() || label.is_some()) && !current.is_empty() {
+            paragraphs.push((speaker.clone(), std::mem::take(&mut current)));
+        }
+        if label.is_some() {
+            speaker = label;
+        }
+        if !text.is_empty() {
+            current.push(' ');
+            current.push_str(&text);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push((speaker, current));
+    }
+
+    let mut segments = Vec::new();
+    for (speaker, paragraph) in paragraphs {
+        let chars: Vec<char> = paragraph.chars().collect();
+        for (start, end, _) in sentence_pieces(&paragraph) {
+            let sentence: String = chars[start..end].iter().collect();
+            let words: Vec<String> = sentence.split_whitespace().map(str::to_string).collect();
+            if words.is_empty() {
+                continue;
+            }
+            for words in split_long(words) {
+                segments.push(TextSegment {
+                    speaker: speaker.clone(),
+                    words,
+                });
+            }
+        }
+    }
+    segments
+}
+
+/// Caption-sized pieces of even length, cut after a comma where there is one
+fn split_long(mut words: Vec<String>) -> Vec<Vec<String>> {
+    let mut pieces = Vec::new();
+    while words.len() > MAX_SEGMENT_WORDS {
+        let target = words.len().div_ceil(words.len().div_ceil(MAX_SEGMENT_WORDS));
+        let cut = (target * 2 / 3..=target)
+            .rev()
+            .find(|&i| words[i - 1].ends_with([',', ';', ':']))
+            .unwrap_or(target);
+        let rest = words.split_off(cut);
+        pieces.push(std::mem::replace(&mut words, rest));
+    }
+    pieces.push(words);
+    pieces
+}
+
+/// A word of the known text, flattened across segments
+struct TextWord {
+    segment: usize,
+    text: String,
+    /// Roughly its syllables, for how long it takes to say
+    weight: f64,
+    /// Likelihood the speaker pauses after it
+    pause_after: f64,
+}
+
+/// Sound between two pauses
+struct Stretch {
+    start: f64,
+    end: f64,
+    /// Mostly inside applause, cheering, laughter or booing
+    reaction: bool,
+}
+
+impl Stretch {
+    fn length(&self) -> f64 {
+        self.end - self.start
+    }
+}
+
+/// Frame loudness of the whole recording
+struct Envelope {
+    levels: Vec<f32>,
+    /// Seconds per frame
+    frame: f64,
+}
+
+impl Envelope {
+    /// The quietest frame within `reach` of `time` and inside `low..high`,
+    /// and how far it sits below the louder frames on both sides of it
+    fn dip(&self, time: f64, reach: f64, low: f64, high: f64) -> Option<(f64, f32)> {
+        let frame = |t: f64| ((t / self.frame) as usize).min(self.levels.len().saturating_sub(1));
+        let (first, last) = (frame((time - reach).max(low)), frame((time + reach).min(high)));
+        if last < first + 2 {
+            return None;
+        }
+        let window = &self.levels[first..=last];
+        let (i, floor) = window.iter().copied().enumerate().min_by(|a, b| a.1.total_cmp(&b.1))?;
+        let left = window[..=i].iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let right = window[i..].iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        Some(((first + i) as f64 * self.frame + self.frame / 2.0, left.min(right) - floor))
+    }
+}
+
+/// Words from `stretches[first..last]` onto `words[from..to]`; no words for
+/// a stretch left empty
+struct Placement {
+    first: usize,
+    last: usize,
+    from: usize,
+    to: usize,
+}
+
+pub fn align(audio: &AudioSignal, text: &[TextSegment]) -> Result<Vec<BasicSegment>> {
+    let (stretches, envelope) = speech_stretches(audio);
+    if stretches.is_empty() {
+        return Err(anyhow!("No speech found in the audio"));
+    }
+
+    let mut words: Vec<TextWord> = text
+        .iter()
+        .enumerate()
+        .flat_map(|(segment, s)| {
+            s.words.iter().map(move |word| TextWord {
+                segment,
+                text: word.clone(),
+                weight: syllables(word),
+                pause_after: pause_likelihood(word),
+            })
+        })
+        .collect();
+    if let Some(last) = words.last_mut() {
+        last.pause_after = 1.0;
+    }
+
+    // The rate is measured again over the stretches that got words, so
+    // applause and crosstalk don't slow it down
+    let total_weight: f64 = words.iter().map(|w| w.weight).sum();
+    let mut seconds_per_weight = speech_time(&stretches) / total_weight;
+    let mut placements = place_words(&stretches, &words, seconds_per_weight)?;
+    let spoken: f64 = placements
+        .iter()
+        .filter(|p| p.to > p.from)
+        .map(|p| speech_time(&stretches[p.first..p.last]))
+        .sum();
+    if spoken > 0.0 {
+        seconds_per_weight = spoken / total_weight;
+        placements = place_words(&stretches, &words, seconds_per_weight)?;
+    }
+
+    let mut timings: Vec<WordTiming> = Vec::with_capacity(words.len());
+    for placement in placements.iter().filter(|p| p.to > p.from) {
+        let span = &stretches[placement.first..placement.last];
+        let placed = &words[placement.from..placement.to];
+        let mut bounds = spread(span, placed);
+        let heard = snap_to_dips(&mut bounds, &envelope);
+
+        for (i, (&(start, end), word)) in bounds.iter().zip(placed).enumerate() {
+            let speech = speech_between(span, start, end);
+            let misfit = (speech / (word.weight * seconds_per_weight)).ln();
+            let fit = if speech > 0.0 { (-misfit.powi(2) / (2.0 * CONFIDENCE_SPREAD.powi(2))).exp() } else { 0.0 };
+            let unheard = [heard[i], heard[i + 1]].iter().filter(|h| !**h).count();
+            let edges = 1.0 - UNHEARD_EDGE_PENALTY * unheard as f64;
+            let reaction = speech_between(span.iter().filter(|s| s.reaction), start, end);
+            let speech_share = if speech > 0.0 { 1.0 - reaction / speech } else { 0.0 };
+            timings.push(WordTiming {
+                text: word.text.clone(),
+                start,
+                end,
+                confidence: (fit * edges * speech_share) as f32,
+            });
+        }
+    }
+
+    let mut segments: Vec<BasicSegment> = Vec::with_capacity(text.len());
+    let mut timings = timings.into_iter().zip(&words).peekable();
+    for (index, piece) in text.iter().enumerate() {
+        let mut segment_words = Vec::with_capacity(piece.words.len());
+        while let Some((timing, _)) = timings.next_if(|(_, word)| word.segment == index) {
+            segment_words.push(timing);
+        }
+        let (Some(first), Some(last)) = (segment_words.first(), segment_words.last()) else {
+            continue;
+        };
+        segments.push(BasicSegment {
+            id: segments.len() + 1,
+            start: first.start,
+            end: last.end,
+            text: piece.words.join(" "),
+            confidence: segment_words.iter().map(|w| w.confidence).sum::<f32>() / segment_words.len() as f32,
+            speaker: piece.speaker.clone(),
+            words: segment_words,
+        });
+    }
+    Ok(segments)
+}
+
+/// Stretches of sound between pauses, and the loudness they were found from
+fn speech_stretches(audio: &AudioSignal) -> (Vec<Stretch>, Envelope) {
+    let hop = ((FRAME_SECONDS * audio.sample_rate as f64) as usize).max(1);
+    let levels: Vec<f32> = audio.samples.chunks(hop).map(rms_db).collect();
+    let envelope = Envelope {
+        levels: levels.clone(),
+        frame: hop as f64 / audio.sample_rate as f64,
+    };
+    if levels.is_empty() {
+        return (Vec::new(), envelope);
+    }
+
+    let mut sorted = levels.clone();
+    sorted.sort_by(f32::total_cmp);
+    let (floor, level) = (percentile(&sorted, 0.1), percentile(&sorted, 0.95));
+    let threshold = floor + (THRESHOLD_FRACTION * (level - floor)).max(MIN_THRESHOLD_DB);
+
+    let to_seconds = |frame: usize| (frame * hop) as f64 / audio.sample_rate as f64;
+    let duration = audio.samples.len() as f64 / audio.sample_rate as f64;
+    let mut stretches: Vec<Stretch> = Vec::new();
+    let mut run_start = None;
+    for (i, &db) in levels.iter().chain(std::iter::once(&f32::NEG_INFINITY)).enumerate() {
+        match (db >= threshold, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(first)) => {
+                let (start, end) = (to_seconds(first), to_seconds(i).min(duration));
+                match stretches.last_mut() {
+                    Some(last) if start - last.end < MIN_PAUSE_SECONDS => last.end = end,
+                    _ => stretches.push(Stretch {
+                        start,
+                        end,
+                        reaction: false,
+                    }),
+                }
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    stretches.retain(|s| s.length() >= MIN_SPEECH_SECONDS);
+
+    // Speech over a music bed is still speech
+    let reactions: Vec<_> = reactions::detect(audio).into_iter().filter(|r| r.kind != ReactionKind::Music).collect();
+    for stretch in &mut stretches {
+        let inside: f64 = reactions
+            .iter()
+            .map(|r| (stretch.end.min(r.end) - stretch.start.max(r.start)).max(0.0))
+            .sum();
+        stretch.reaction = inside >= REACTION_OVERLAP * stretch.length();
+    }
+    (stretches, envelope)
+}
+
+/// Best placement of every word, in order, over the stretches
+fn place_words(stretches: &[Stretch], words: &[TextWord], seconds_per_weight: f64) -> Result<Vec<Placement>> {
+    let (k_count, n_count) = (stretches.len(), words.len());
+
+    // Word index each stretch boundary is expected near, by speaking time
+    let total_speech = speech_time(stretches.iter().filter(|s| !s.reaction)).max(f64::EPSILON);
+    let mut expected = Vec::with_capacity(k_count + 1);
+    let mut before = 0.0;
+    expected.push(0);
+    for stretch in stretches {
+        if !stretch.reaction {
+            before += stretch.length();
+        }
+        expected.push(((before / total_speech) * n_count as f64).round() as usize);
+    }
+    let low = |k: usize| expected[k].saturating_sub(SEARCH_BAND_WORDS);
+    let high = |k: usize| if k == k_count { n_count } else { (expected[k] + SEARCH_BAND_WORDS).min(n_count) };
+
+    // cost[k][n - low(k)]: best cost with stretches ..k holding words ..n
+    let mut cost: Vec<Vec<f64>> = (0..=k_count).map(|k| vec![f64::INFINITY; high(k) + 1 - low(k)]).collect();
+    let mut back: Vec<Vec<(u32, u32)>> = (0..=k_count).map(|k| vec![(0, 0); high(k) + 1 - low(k)]).collect();
+    cost[0][0] = 0.0;
+
+    let relax = |cost: &mut Vec<Vec<f64>>, back: &mut Vec<Vec<(u32, u32)>>, k: usize, n: usize, value: f64, from: (usize, usize)| {
+        if n < low(k) || n > high(k) {
+            return;
+        }
+        let cell = &mut cost[k][n - low(k)];
+        if value < *cell {
+            *cell = value;
+            back[k][n - low(k)] = (from.0 as u32, from.1 as u32);
+        }
+    };
+
+    let mut prefix = Vec::with_capacity(n_count + 1);
+    prefix.push(0.0);
+    for word in words {
+        prefix.push(prefix.last().copied().unwrap_or_default() + word.weight);
+    }
+
+    for k in 0..k_count {
+        for n in low(k)..=high(k) {
+            let here = cost[k][n - low(k)];
+            if !here.is_finite() {
+                continue;
+            }
+
+            let empty = if stretches[k].reaction { 0.0 } else { EMPTY_COST };
+            relax(&mut cost, &mut back, k + 1, n, here + empty, (k, n));
+
+            let (mut speech, mut penalty) = (0.0, 0.0);
+            for j in k + 1..=(k + MAX_MERGED_STRETCHES).min(k_count) {
+                speech += stretches[j - 1].length();
+                if stretches[j - 1].reaction {
+                    penalty += REACTION_COST_PER_SECOND * stretches[j - 1].length();
+                }
+                if j > k + 1 {
+                    penalty += MERGE_COST + MERGE_COST_PER_SECOND * (stretches[j - 1].start - stretches[j - 2].end);
+                }
+                for m in n + 1..=(n + MAX_PLACEMENT_WORDS).min(high(j)) {
+                    if m < low(j) {
+                        continue;
+                    }
+                    let needed = (prefix[m] - prefix[n]) * seconds_per_weight;
+                    let fit = FIT_WEIGHT * needed * (speech / needed).ln().powi(2);
+                    let value = here + fit - words[m - 1].pause_after.ln() + penalty;
+                    relax(&mut cost, &mut back, j, m, value, (k, n));
+                }
+            }
+        }
+    }
+
+    if !cost[k_count][n_count - low(k_count)].is_finite() {
+        return Err(anyhow!(
+            "Could not fit {} words to {:.0}s of speech; is this the right recording?",
+            n_count,
+            total_speech
+        ));
+    }
+
+    let mut placements = Vec::new();
+    let (mut k, mut n) = (k_count, n_count);
+    while k > 0 {
+        let (from_k, from_n) = back[k][n - low(k)];
+        let (from_k, from_n) = (from_k as usize, from_n as usize);
+        placements.push(Placement {
+            first: from_k,
+            last: k,
+            from: from_n,
+            to: n,
+        });
+        (k, n) = (from_k, from_n);
+    }
+    placements.reverse();
+    Ok(placements)
+}
+
+fn speech_time<'a>(stretches: impl IntoIterator<Item = &'a Stretch>) -> f64 {
+    stretches.into_iter().fold(0.0, |total, s| total + s.length())
+}
+
+/// Speech time of `stretches` between `start` and `end`
+fn speech_between<'a>(stretches: impl IntoIterator<Item = &'a Stretch>, start: f64, end: f64) -> f64 {
+    stretches.into_iter().map(|s| (s.end.min(end) - s.start.max(start)).max(0.0)).sum()
+}
+
+/// Start and end of each word, spreading them over the speech (not the
+/// pauses) by weight
+fn spread(stretches: &[Stretch], words: &[TextWord]) -> Vec<(f64, f64)> {
+    let speech = speech_time(stretches);
+    let total: f64 = words.iter().map(|w| w.weight).sum();
+
+    // A word starting right at the end of a stretch starts the next one
+    let clock = |mut offset: f64, starting: bool| {
+        for stretch in stretches {
+            let length = stretch.length();
+            if offset < length || (!starting && offset <= length) {
+                return stretch.start + offset;
+            }
+            offset -= length;
+        }
+        stretches.last().map(|s| s.end).unwrap_or_default()
+    };
+
+    let mut cursor = 0.0;
+    words
+        .iter()
+        .map(|word| {
+            let start = clock(cursor, true);
+            cursor += word.weight / total * speech;
+            (start, clock(cursor, false).max(start))
+        })
+        .collect()
+}
+
+/// Move each boundary between two words that run on into each other to the
+/// deepest nearby dip in loudness. Returns, for every edge (before the first
+/// word, between words, after the last), whether a pause or a dip bears it
+/// out
+fn snap_to_dips(bounds: &mut [(f64, f64)], envelope: &Envelope) -> Vec<bool> {
+    let mut heard = vec![true; bounds.len() + 1];
+    for i in 0..bounds.len().saturating_sub(1) {
+        let (before, after) = (bounds[i], bounds[i + 1]);
+        // The words are on either side of a pause already
+        if before.1 < after.0 {
+            continue;
+        }
+        let (low, high) = (before.0 + MIN_WORD_SECONDS, after.1 - MIN_WORD_SECONDS);
+        match envelope.dip(before.1, BOUNDARY_REACH_SECONDS, low, high) {
+            Some((time, depth)) if depth >= MIN_DIP_DB => {
+                bounds[i].1 = time;
+                bounds[i + 1].0 = time;
+            }
+            _ => heard[i + 1] = false,
+        }
+    }
+    heard
+}
+
+/// Rough syllable count: vowel groups, letters of an acronym, and digits
+/// read out
+fn syllables(word: &str) -> f64 {
+    let letters: String = word.chars().filter(|c| c.is_alphabetic()).collect();
+    let digits = word.chars().filter(char::is_ascii_digit).count();
+    if letters.is_empty() && digits == 0 {
+        return SYMBOL_WEIGHT;
+    }
+
+    let acronym = letters.chars().count() >= 2 && letters.chars().all(char::is_uppercase);
+    let vowel_groups = if acronym {
+        letters.chars().count()
+    } else {
+        let lower = letters.to_lowercase();
+        let mut groups = 0;
+        let mut in_vowel = false;
+        for c in lower.chars() {
+            let vowel = "aeiouyáéíóúàèìòùäëïöüâêîôûãõ".contains(c);
+            if vowel && !in_vowel {
+                groups += 1;
+            }
+            in_vowel = vowel;
+        }
+        // Silent final e ("make"), but not "table"
+        if groups > 1 && lower.ends_with('e') && !lower.ends_with("le") {
+            groups -= 1;
+        }
+        groups
+    };
+    (vowel_groups as f64 + digits as f64 * DIGIT_SYLLABLES).max(1.0)
+}
+
+fn pause_likelihood(word: &str) -> f64 {
+    let word = word.trim_end_matches(['"', '”', '’', '\'', ')', ']']);
+    if word.ends_with(['.', '!', '?']) {
+        SENTENCE_PAUSE
+    } else if word.ends_with([',', ';', ':', '—']) || word == "--" {
+        CLAUSE_PAUSE
+    } else {
+        WORD_PAUSE
+    }
+}
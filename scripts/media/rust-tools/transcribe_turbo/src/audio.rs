@@ -0,0 +1,104 @@
+//! Decoded mono audio for the acoustic analysis stages.
+//!
+//! WAV files are read directly; anything else (video containers, MP3, ...)
+//! is decoded to 16 kHz mono through `ffmpeg`.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use anyhow::{anyhow, Context, Result};
+use tokio::process::Command;
+
+/// Sample rate requested from ffmpeg; plenty for loudness and pitch
+const DECODE_SAMPLE_RATE: u32 = 16_000;
+
+pub struct AudioSignal {
+    /// Mono samples in -1.0..=1.0
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl AudioSignal {
+    pub async fn load(path: &Path) -> Result<Self> {
+        let is_wav = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+
+        if is_wav {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || read_wav(&path)).await?
+        } else {
+            decode_with_ffmpeg(path).await
+        }
+    }
+
+    /// Samples between two timestamps, clamped to the signal
+    pub fn slice(&self, start: f64, end: f64) -> &[f32] {
+        let to_index = |t: f64| ((t.max(0.0) * self.sample_rate as f64) as usize).min(self.samples.len());
+        let (start, end) = (to_index(start), to_index(end));
+        &self.samples[start..end.max(start)]
+    }
+}
+
+fn read_wav(path: &Path) -> Result<AudioSignal> {
+    let mut reader = hound::WavReader::open(path)
+        .with_context(|| format!("Failed to open WAV file {:?}", path))?;
+    let spec = reader.spec();
+    let channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = (1_i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    let samples = interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect();
+
+    Ok(AudioSignal {
+        samples,
+        sample_rate: spec.sample_rate,
+    })
+}
+
+async fn decode_with_ffmpeg(path: &Path) -> Result<AudioSignal> {
+    let output = Command::new("ffmpeg")
+        .arg("-v").arg("error")
+        .arg("-i").arg(path)
+        .arg("-vn")
+        .arg("-ac").arg("1")
+        .arg("-ar").arg(DECODE_SAMPLE_RATE.to_string())
+        .arg("-f").arg("f32le")
+        .arg("-")
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("Failed to run ffmpeg (is it installed?)")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffmpeg could not decode {:?}: {}",
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let samples = output
+        .stdout
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .collect();
+
+    Ok(AudioSignal {
+        samples,
+        sample_rate: DECODE_SAMPLE_RATE,
+    })
+}
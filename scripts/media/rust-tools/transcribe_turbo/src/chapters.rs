@@ -0,0 +1,272 @@
+//! Topic segmentation into chapters, TextTiling-style (Hearst, 1997).
+//!
+//! At every gap between sentences we compare the few sentences before it
+//! with the few after: cosine similarity of their content words, blended with
+//! the similarity of their issue-category weights so a move from "jobs" to
+//! "Medicare" registers even when no words are shared. Gaps that sit in a
+//! deep valley of that similarity curve are topic boundaries; the deepest are
+//! kept as long as chapters stay long enough to be worth navigating to.
+//! Neighbouring chapters about the same issue are merged, and each chapter is
+//! titled after its dominant issue.
+
+use std::collections::{BTreeMap, HashMap};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::sentences::Sentence;
+use crate::taxonomy::Taxonomy;
+use crate::text::content_terms;
+
+/// Sentences compared on each side of a gap
+const BLOCK_SENTENCES: usize = 4;
+/// Share of the gap similarity that comes from issue categories
+const CATEGORY_WEIGHT: f32 = 0.4;
+/// Chapters shorter than this aren't worth a navigation entry
+const MIN_CHAPTER_SECONDS: f64 = 45.0;
+const MAX_TERMS: usize = 5;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct Chapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+    /// Taxonomy category carrying the most keyword weight in the chapter
+    pub dominant_issue: Option<String>,
+    /// Issue terms mentioned in the chapter, most weight first
+    #[serde(default)]
+    pub terms: Vec<String>,
+}
+
+/// Chapters covering the whole speech, the first starting at 0
+pub fn detect(sentences: &[Sentence], taxonomy: &Taxonomy) -> Vec<Chapter> {
+    let Some(last) = sentences.last() else {
+        return Vec::new();
+    };
+
+    let gaps = gap_similarities(sentences);
+    let depths = depth_scores(&gaps);
+
+    // Hearst's cutoff: valleys deeper than the mean minus half a deviation
+    let valleys: Vec<f32> = depths.iter().copied().filter(|d| *d > 0.0).collect();
+    let cutoff = if valleys.is_empty() {
+        f32::INFINITY
+    } else {
+        let mean = valleys.iter().sum::<f32>() / valleys.len() as f32;
+        let variance = valleys.iter().map(|d| (d - mean).powi(2)).sum::<f32>() / valleys.len() as f32;
+        mean - variance.sqrt() / 2.0
+    };
+
+    let mut candidates: Vec<usize> = (0..depths.len()).filter(|&g| depths[g] > 0.0 && depths[g] >= cutoff).collect();
+    candidates.sort_by(|&a, &b| depths[b].total_cmp(&depths[a]));
+
+    // Deepest first, as long as every chapter stays long enough
+    let mut boundaries: Vec<f64> = Vec::new();
+    for gap in candidates {
+        let at = sentences[gap + 1].start;
+        let clear = at >= MIN_CHAPTER_SECONDS
+            && last.end - at >= MIN_CHAPTER_SECONDS
+            && boundaries.iter().all(|b| (b - at).abs() >= MIN_CHAPTER_SECONDS);
+        if clear {
+            boundaries.push(at);
+        }
+    }
+    boundaries.sort_by(f64::total_cmp);
+
+    let mut starts = vec![0.0];
+    starts.extend(boundaries);
+    let mut chapters: Vec<Chapter> = Vec::new();
+    for (index, &start) in starts.iter().enumerate() {
+        let end = starts.get(index + 1).copied().unwrap_or(last.end);
+        let inside: Vec<&Sentence> = sentences.iter().filter(|s| s.start >= start && s.start < end).collect();
+        let (dominant_issue, terms) = issues(&inside);
+
+        match chapters.last_mut() {
+            Some(previous) if dominant_issue.is_some() && previous.dominant_issue == dominant_issue => {
+                previous.end = end;
+                let inside: Vec<&Sentence> = sentences.iter().filter(|s| s.start >= previous.start && s.start < end).collect();
+                previous.terms = issues(&inside).1;
+            }
+            _ => chapters.push(Chapter {
+                start,
+                end,
+                title: String::new(),
+                dominant_issue,
+                terms,
+            }),
+        }
+    }
+
+    let titles = titles(&chapters, sentences, taxonomy);
+    for (chapter, title) in chapters.iter_mut().zip(titles) {
+        chapter.title = title;
+    }
+    chapters
+}
+
+/// Similarity across every gap between consecutive sentences, smoothed
+fn gap_similarities(sentences: &[Sentence]) -> Vec<f32> {
+    let words: Vec<HashMap<String, f32>> = sentences
+        .iter()
+        .map(|s| {
+            let mut counts = HashMap::new();
+            for term in content_terms(&s.text) {
+                *counts.entry(term).or_insert(0.0) += 1.0;
+            }
+            counts
+        })
+        .collect();
+    let categories: Vec<HashMap<String, f32>> = sentences
+        .iter()
+        .map(|s| {
+            let mut weights = HashMap::new();
+            for hit in &s.keywords {
+                *weights.entry(hit.category.clone()).or_insert(0.0) += hit.weight * hit.spans.len() as f32;
+            }
+            weights
+        })
+        .collect();
+
+    let raw: Vec<f32> = (0..sentences.len().saturating_sub(1))
+        .map(|gap| {
+            let left = gap.saturating_sub(BLOCK_SENTENCES - 1)..gap + 1;
+            let right = gap + 1..(gap + 1 + BLOCK_SENTENCES).min(sentences.len());
+
+            let lexical = cosine(&merge(&words[left.clone()]), &merge(&words[right.clone()]));
+            let (left_issues, right_issues) = (merge(&categories[left]), merge(&categories[right]));
+            if left_issues.is_empty() || right_issues.is_empty() {
+                lexical
+            } else {
+                (1.0 - CATEGORY_WEIGHT) * lexical + CATEGORY_WEIGHT * cosine(&left_issues, &right_issues)
+            }
+        })
+        .collect();
+
+    // Three-gap moving average takes the edge off single odd sentences
+    (0..raw.len())
+        .map(|i| {
+            let window = &raw[i.saturating_sub(1)..(i + 2).min(raw.len())];
+            window.iter().sum::<f32>() / window.len() as f32
+        })
+        .collect()
+}
+
+/// How far each gap's similarity sits below the peaks on either side
+fn depth_scores(gaps: &[f32]) -> Vec<f32> {
+    (0..gaps.len())
+        .map(|i| {
+            let mut left = i;
+            while left > 0 && gaps[left - 1] >= gaps[left] {
+                left -= 1;
+            }
+            let mut right = i;
+            while right + 1 < gaps.len() && gaps[right + 1] >= gaps[right] {
+                right += 1;
+            }
+            (gaps[left] - gaps[i]) + (gaps[right] - gaps[i])
+        })
+        .collect()
+}
+
+/// Dominant category and the chapter's issue terms, heaviest first
+fn issues(sentences: &[&Sentence]) -> (Option<String>, Vec<String>) {
+    let mut categories: BTreeMap<&str, f32> = BTreeMap::new();
+    let mut terms: BTreeMap<&str, f32> = BTreeMap::new();
+    for hit in sentences.iter().flat_map(|s| &s.keywords) {
+        let weight = hit.weight * hit.spans.len() as f32;
+        *categories.entry(&hit.category).or_default() += weight;
+        *terms.entry(&hit.term).or_default() += weight;
+    }
+
+    let dominant = categories
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(category, _)| category.to_string());
+    let mut terms: Vec<(&str, f32)> = terms.into_iter().collect();
+    terms.sort_by(|a, b| b.1.total_cmp(&a.1));
+    (dominant, terms.into_iter().take(MAX_TERMS).map(|(t, _)| t.to_string()).collect())
+}
+
+/// Issue label, told apart by a term when the issue comes back later;
+/// chapters without an issue are named by position or their commonest words
+fn titles(chapters: &[Chapter], sentences: &[Sentence], taxonomy: &Taxonomy) -> Vec<String> {
+    let mut titles: Vec<String> = Vec::new();
+    let any_issues = chapters.iter().any(|c| c.dominant_issue.is_some());
+
+    for (index, chapter) in chapters.iter().enumerate() {
+        let title = match &chapter.dominant_issue {
+            Some(category) => {
+                let label = taxonomy
+                    .category(category)
+                    .map(|c| c.label.clone())
+                    .unwrap_or_else(|| title_case(category));
+                let earlier: Vec<&Chapter> = chapters[..index].iter().filter(|c| c.dominant_issue == chapter.dominant_issue).collect();
+                if earlier.is_empty() {
+                    label
+                } else {
+                    match chapter.terms.iter().find(|t| !earlier.iter().any(|c| c.terms.contains(t))) {
+                        Some(term) => format!("{}: {}", label, title_case(term)),
+                        None => format!("{} (continued)", label),
+                    }
+                }
+            }
+            // Between issue chapters, an issue-free start or end is the
+            // welcome or the sign-off
+            None if index == 0 && any_issues => "Opening".to_string(),
+            None if index + 1 == chapters.len() && any_issues => "Closing".to_string(),
+            None => {
+                let mut counts: HashMap<String, usize> = HashMap::new();
+                for sentence in sentences.iter().filter(|s| s.start >= chapter.start && s.start < chapter.end) {
+                    for term in content_terms(&sentence.text) {
+                        *counts.entry(term).or_default() += 1;
+                    }
+                }
+                let mut counts: Vec<(String, usize)> = counts.into_iter().filter(|(term, _)| term.len() > 3).collect();
+                counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+                match counts.as_slice() {
+                    [] => format!("Part {}", index + 1),
+                    [(only, _)] => title_case(only),
+                    [(first, _), (second, _), ..] => format!("{} & {}", title_case(first), title_case(second)),
+                }
+            }
+        };
+        titles.push(title);
+    }
+
+    titles
+}
+
+fn merge(maps: &[HashMap<String, f32>]) -> HashMap<String, f32> {
+    let mut merged = HashMap::new();
+    for map in maps {
+        for (key, value) in map {
+            *merged.entry(key.clone()).or_insert(0.0) += value;
+        }
+    }
+    merged
+}
+
+fn cosine(a: &HashMap<String, f32>, b: &HashMap<String, f32>) -> f32 {
+    let dot: f32 = a.iter().filter_map(|(k, v)| b.get(k).map(|w| v * w)).sum();
+    let norm = |m: &HashMap<String, f32>| m.values().map(|v| v * v).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator > 0.0 {
+        dot / denominator
+    } else {
+        0.0
+    }
+}
+
+fn title_case(text: &str) -> String {
+    text.split([' ', '_'])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,366 @@
+//! Checkable claims for fact-checking.
+//!
+//! A claim is a sentence that states something a fact-checker can look up:
+//! a figure ("$1.2 trillion", "8 percent", "3 million jobs"), a comparison
+//! ("the highest since 1980"), a trend ("wages doubled") or a finding put in
+//! someone else's mouth ("according to the CBO"). Everything is rule-based so
+//! the same speech always yields the same claims, offline.
+//!
+//! Quantities are normalized to a number and a unit: dollars to USD, "8%" and
+//! "8 percent" to percent, and plain counts to the noun they count. Years,
+//! dates and bill numbers aren't quantities. Questions and promises ("we will create a million
+//! jobs") aren't checkable and are skipped unless they cite a source.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::entities;
+use crate::sentences::Sentence;
+use crate::text::{is_stopword, TextSpan};
+
+/// Number words understood in front of a scale ("two million") or "percent"
+const NUMBER_WORDS: &[(&str, f64)] = &[
+    ("a", 1.0), ("one", 1.0), ("two", 2.0), ("three", 3.0), ("four", 4.0), ("five", 5.0), ("six", 6.0),
+    ("seven", 7.0), ("eight", 8.0), ("nine", 9.0), ("ten", 10.0), ("eleven", 11.0), ("twelve", 12.0),
+    ("fifteen", 15.0), ("twenty", 20.0), ("thirty", 30.0), ("forty", 40.0), ("fifty", 50.0), ("sixty", 60.0),
+    ("seventy", 70.0), ("eighty", 80.0), ("ninety", 90.0), ("hundred", 100.0), ("a hundred", 100.0),
+    ("half a", 0.5),
+];
+/// Multiplier verbs, read as "N times" the earlier value
+const MULTIPLIERS: &[(&str, f64)] = &[
+    ("doubled", 2.0), ("tripled", 3.0), ("quadrupled", 4.0), ("halved", 0.5), ("cut in half", 0.5),
+];
+/// Words between a number and the noun it counts ("3 million new jobs")
+const COUNT_FILLERS: &[&str] = &[
+    "new", "more", "additional", "extra", "other", "of", "our", "the", "their", "american", "good", "paying",
+    "good-paying", "full-time", "hardworking", "working",
+];
+const MONTHS: &[&str] = &[
+    "january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november",
+    "december",
+];
+
+static QUANTITY: LazyLock<Regex> = LazyLock::new(|| {
+    let words = NUMBER_WORDS.iter().map(|(w, _)| *w).collect::<Vec<_>>().join("|");
+    Regex::new(&format!(
+        r"(?i)(?P<dollar>\$\s?)?(?P<num>\d{{1,3}}(?:,\d{{3}})+(?:\.\d+)?|\d*\.\d+|\d+|\b(?:{0})(?:[- ](?:{0}))?)(?:(?P<abbr>(?-i:[kMBT]|[mbt]n))\b|\b)(?:\s+(?P<scale>thousand|million|billion|trillion))?(?:\s*(?P<unit>%|percent(?:age\s+points?)?|per\s+cent|dollars|bucks))?",
+        words
+    ))
+    .unwrap()
+});
+static COMPARISON: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:more|less|fewer|higher|lower|bigger|larger|smaller|faster|slower|better|worse|greater|cheaper)\s+than\b|\b(?:the\s+)?(?:highest|lowest|largest|biggest|smallest|fastest|slowest|best|worst|most|fewest|least)\b[^.;!?]{0,40}?\b(?:since|in\s+(?:\w+\s+)?history|ever|on\s+record|in\s+(?:a\s+)?(?:decade|generation|century))\b|\brecord[- ](?:high|low|levels?|numbers?|breaking)\b|\b(?:twice|half|three\s+times)\s+as\b|\b\w+\s+times\s+(?:more|less|as|higher|lower)\b|\bcompared\s+(?:to|with)\b|\bthan\s+(?:ever|any\s+\w+)\b",
+    )
+    .unwrap()
+});
+static TREND: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:doubled|tripled|quadrupled|halved|cut\s+in\s+half|rose|risen|rising|increased|increasing|decreased|decreasing|fell|fallen|falling|dropped|declined|declining|grew|grown|growing|soared|skyrocketed|plummeted|surged|climbed|shrunk|shrank|(?:went|gone|going)\s+(?:up|down))\b",
+    )
+    .unwrap()
+});
+static ATTRIBUTION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\baccording\s+to\b|\b(?:reports?|study|studies|survey|poll|data|statistics|numbers|analysis|estimates?)\s+(?:shows?|showed|shown|found|finds|says|said|confirms?|confirmed)\b|\b(?:economists|experts|scientists|analysts|doctors)\s+(?:say|said|agree|agreed|estimate|estimated|project|projected)\b|\b(?:CBO|BLS|Census\s+Bureau|Congressional\s+Budget\s+Office|Bureau\s+of\s+Labor\s+Statistics|the\s+Fed|Federal\s+Reserve|FBI|CDC|GAO)\s+(?:says|said|reports?|reported|estimates?|estimated|found|finds|projects?|projected)\b",
+    )
+    .unwrap()
+});
+/// Promises and hypotheticals about the future
+static PLEDGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:I|we)\s+(?:will|would|could|can|plan\s+to|promise\s+to|are\s+going\s+to)\b|\b(?:I|we)['’](?:ll|re\s+going\s+to|m\s+going\s+to)\b|\bif\s+(?:I|we)\b",
+    )
+    .unwrap()
+});
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaimKind {
+    /// A figure stated as fact: "unemployment is 3.9 percent"
+    Statistic,
+    /// Measured against something else: "the highest since 1980"
+    Comparison,
+    /// A change over time: "wages doubled", "crime fell 10 percent"
+    Trend,
+    /// Credited to a source: "according to the CBO"
+    Attribution,
+}
+
+impl ClaimKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClaimKind::Statistic => "statistic",
+            ClaimKind::Comparison => "comparison",
+            ClaimKind::Trend => "trend",
+            ClaimKind::Attribution => "attribution",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct Claim {
+    pub text: String,
+    pub kind: ClaimKind,
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<String>,
+    pub quantities: Vec<Quantity>,
+    /// Comparison, trend and attribution phrases in the claim
+    #[serde(default)]
+    pub cues: Vec<String>,
+    /// The sentences either side, for context when checking
+    #[serde(default)]
+    pub context_before: String,
+    #[serde(default)]
+    pub context_after: String,
+}
+
+/// A normalized figure: "$1.2 trillion" is 1.2e12 USD
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct Quantity {
+    /// As spoken
+    pub text: String,
+    pub value: f64,
+    /// "USD", "percent", "percentage points", "times", or the noun counted ("jobs")
+    pub unit: String,
+    /// Where the figure occurs in the claim text
+    pub span: TextSpan,
+}
+
+impl Quantity {
+    /// "1.2e12 USD"
+    pub fn display(&self) -> String {
+        let value = if self.value.abs() >= 1e6 {
+            format!("{:e}", self.value)
+        } else {
+            format!("{}", self.value)
+        };
+        if self.unit.is_empty() {
+            value
+        } else {
+            format!("{} {}", value, self.unit)
+        }
+    }
+}
+
+/// Every checkable claim, in speech order
+pub fn extract(sentences: &[Sentence]) -> Vec<Claim> {
+    let mut claims = Vec::new();
+
+    for (index, sentence) in sentences.iter().enumerate() {
+        let text = &sentence.text;
+        if text.trim_end().ends_with('?') {
+            continue;
+        }
+
+        let quantities = quantities(text);
+        let cues_of = |regex: &Regex| -> Vec<String> { regex.find_iter(text).map(|m| m.as_str().to_string()).collect() };
+        let attribution = cues_of(&ATTRIBUTION);
+        let trend = cues_of(&TREND);
+        // "more than 3 million" bounds a figure rather than comparing two
+        let comparison: Vec<String> = COMPARISON
+            .find_iter(text)
+            .filter(|m| {
+                !m.as_str().to_lowercase().ends_with("than")
+                    || !text[m.end()..]
+                        .trim_start()
+                        .starts_with(|c: char| c.is_ascii_digit() || c == '$')
+            })
+            .map(|m| m.as_str().to_string())
+            .collect();
+
+        // Trend verbs need a figure or a comparison to be checkable: "prices
+        // rose 20 percent" is, "our movement is growing" isn't
+        let trend_checkable = !trend.is_empty() && (!quantities.is_empty() || !comparison.is_empty());
+        let kind = if !attribution.is_empty() && (!quantities.is_empty() || trend_checkable || !comparison.is_empty()) {
+            ClaimKind::Attribution
+        } else if trend_checkable {
+            ClaimKind::Trend
+        } else if !comparison.is_empty() {
+            ClaimKind::Comparison
+        } else if !quantities.is_empty() {
+            ClaimKind::Statistic
+        } else {
+            continue;
+        };
+
+        if kind != ClaimKind::Attribution && PLEDGE.is_match(text) {
+            continue;
+        }
+
+        let mut cues = attribution;
+        if kind != ClaimKind::Comparison {
+            cues.extend(trend);
+        }
+        cues.extend(comparison);
+
+        claims.push(Claim {
+            text: text.clone(),
+            kind,
+            start: sentence.start,
+            end: sentence.end,
+            speaker: sentence.speaker.map(str::to_string),
+            quantities,
+            cues,
+            context_before: index.checked_sub(1).map(|i| sentences[i].text.clone()).unwrap_or_default(),
+            context_after: sentences.get(index + 1).map(|s| s.text.clone()).unwrap_or_default(),
+        });
+    }
+
+    claims
+}
+
+/// Figures in `text`, normalized
+pub fn quantities(text: &str) -> Vec<Quantity> {
+    let char_at = |byte: usize| text[..byte].chars().count();
+    let bills = entities::bill_ranges(text);
+    let mut found = Vec::new();
+
+    for caps in QUANTITY.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let raw = caps["num"].to_lowercase();
+        let dollar = caps.name("dollar").is_some();
+        let scale = caps.name("scale").map(|m| m.as_str().to_lowercase());
+        let abbr = caps.name("abbr").map(|m| m.as_str().to_lowercase());
+        let unit = caps.name("unit").map(|m| m.as_str().to_lowercase());
+
+        let is_word = raw.starts_with(|c: char| c.is_alphabetic());
+        let before = text[..whole.start()].chars().next_back();
+        let after = text[whole.end()..].chars().next();
+        // Part of a word, a time (9:30), a fraction, a numeric date or a
+        // bill number (H.R. 1234)
+        if before.is_some_and(|c| c.is_alphanumeric() || c == ':' || c == '/')
+            || after.is_some_and(|c| c == ':' || c == '/')
+            || bills.iter().any(|&(start, end)| start < whole.end() && whole.start() < end)
+        {
+            continue;
+        }
+        // Number words only count with a scale or unit: "one million", not "one"
+        if is_word && scale.is_none() && unit.is_none() {
+            continue;
+        }
+        // Abbreviated scales only follow dollar signs: "$35M", not "35M"
+        if abbr.is_some() && !dollar {
+            continue;
+        }
+
+        let Some(mut value) = number_value(&raw) else {
+            continue;
+        };
+        let factor = match scale.as_deref().or(abbr.as_deref()) {
+            Some("thousand" | "k") => 1e3,
+            Some("million" | "m" | "mn") => 1e6,
+            Some("billion" | "b" | "bn") => 1e9,
+            Some("trillion" | "t" | "tn") => 1e12,
+            _ => 1.0,
+        };
+        value *= factor;
+
+        let mut end = whole.end();
+        let unit = match unit.as_deref() {
+            _ if dollar => "USD".to_string(),
+            Some("dollars" | "bucks") => "USD".to_string(),
+            Some(u) if u.starts_with("percentage") => "percentage points".to_string(),
+            Some(_) => "percent".to_string(),
+            None => {
+                let plain = !raw.contains([',', '.']) && factor == 1.0;
+                // "in 2020", "since 1980", "November 5"
+                if plain && (1900.0..=2100.0).contains(&value) {
+                    continue;
+                }
+                let previous_word = text[..whole.start()]
+                    .split_whitespace()
+                    .next_back()
+                    .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase());
+                if previous_word.is_some_and(|w| MONTHS.contains(&w.as_str())) {
+                    continue;
+                }
+                match counted_noun(&text[whole.end()..]) {
+                    Some((noun, offset)) => {
+                        end = whole.end() + offset;
+                        noun
+                    }
+                    None => String::new(),
+                }
+            }
+        };
+
+        found.push(Quantity {
+            text: text[whole.start()..end].trim().to_string(),
+            value,
+            unit,
+            span: TextSpan {
+                start: char_at(whole.start()),
+                end: char_at(end),
+            },
+        });
+    }
+
+    let lower = text.to_lowercase();
+    for (word, factor) in MULTIPLIERS {
+        for (byte, _) in lower.match_indices(word) {
+            let end = byte + word.len();
+            let bounded = !lower[..byte].ends_with(|c: char| c.is_alphanumeric())
+                && !lower[end..].starts_with(|c: char| c.is_alphanumeric());
+            if bounded {
+                found.push(Quantity {
+                    text: text[byte..end].to_string(),
+                    value: *factor,
+                    unit: "times".to_string(),
+                    span: TextSpan {
+                        start: char_at(byte),
+                        end: char_at(end),
+                    },
+                });
+            }
+        }
+    }
+
+    found.sort_by_key(|q| q.span.start);
+    found
+}
+
+/// Digits ("1,200.5") or number words ("twenty five", "a hundred")
+fn number_value(raw: &str) -> Option<f64> {
+    if raw.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return raw.replace(',', "").parse().ok();
+    }
+    let lookup = |word: &str| NUMBER_WORDS.iter().find(|(w, _)| *w == word).map(|(_, v)| *v);
+    if let Some(value) = lookup(raw) {
+        return Some(value);
+    }
+    // "twenty-five", "two hundred"
+    let (first, second) = raw.split_once([' ', '-'])?;
+    let (first, second) = (lookup(first)?, lookup(second)?);
+    Some(if second == 100.0 { first * second } else { first + second })
+}
+
+/// The noun a bare number counts, and how far into `rest` it ends
+fn counted_noun(rest: &str) -> Option<(String, usize)> {
+    let mut offset = rest.len() - rest.trim_start().len();
+    for word in rest[offset..].split_inclusive(char::is_whitespace).take(4) {
+        let bare = word.trim_end().trim_end_matches(|c: char| !c.is_alphanumeric());
+        let clean = bare.to_lowercase();
+        if !clean.starts_with(|c: char| c.is_alphabetic()) {
+            return None;
+        }
+        let word_end = offset + bare.len();
+        offset += word.len();
+        if COUNT_FILLERS.contains(&clean.as_str()) {
+            // A clause break ends the search: "3 million, and more"
+            if bare.len() != word.trim_end().len() {
+                return None;
+            }
+            continue;
+        }
+        if is_stopword(&clean) {
+            return None;
+        }
+        return Some((clean, word_end));
+    }
+    None
+}
@@ -0,0 +1,574 @@
+//! Corrections to the recognized text: the candidate's surname and the local
+//! place names Whisper keeps spelling wrong the same few ways.
+//!
+//! Corrections files are TOML, passed with `--corrections` (repeat to layer
+//! a campaign file over a shared one):
+//!
+//! ```toml
+//! # Canonical spellings; a run of words that reads or sounds close enough
+//! # to one of them is snapped to it
+//! names = ["Okonkwo", "Wilkes-Barre", "Schuylkill"]
+//! # How close, 0-1 (default 0.85)
+//! fuzzy_threshold = 0.85
+//!
+//! # Literal phrase -> replacement; whole words, any case or punctuation
+//! [replace]
+//! "wilkes barry" = "Wilkes-Barre"
+//!
+//! # Regular expressions, applied in order; `$1` refers to a group
+//! [[regex]]
+//! pattern = '\bSen\.? Okon(?:qu|kw)o\b'
+//! replacement = "Sen. Okonkwo"
+//! ```
+//!
+//! Literal replacements run first, then the expressions, then the names.
+//! Closeness for names is the better of spelling (edit distance) and sound
+//! (edit distance between rough phonetic keys, so "Schoolkill" still lands).
+//! Sound only counts for names with a long enough key, and a one-word name
+//! must still be spelled nearly right, so "Case" never becomes Casey. Only
+//! capitalized words are snapped, so "reading" stays when Reading is on the
+//! list, and a sentence's first word, capitalized anyway, has to match on
+//! spelling alone. Corrections run right after transcription, before
+//! redaction and analysis, and every one is logged in the JSON.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use anyhow::{Context, Result};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::text::{char_span, edit_distance, normalize, token_spans, NormalizedText, TextSpan};
+use crate::{estimate_word_timings, BasicSegment, WordTiming};
+
+const DEFAULT_FUZZY_THRESHOLD: f32 = 0.85;
+/// Names shorter than this (letters) are only corrected literally
+const MIN_FUZZY_LETTERS: usize = 4;
+/// Sound alone counts a little less than spelling
+const PHONETIC_WEIGHT: f32 = 0.95;
+/// A sound match still has to be spelled at least this close
+const MIN_PHONETIC_SPELLING: f32 = 0.5;
+/// Shorter sound keys ("ks" for Casey) match too many ordinary words
+const MIN_PHONETIC_KEY: usize = 4;
+/// How far below the threshold a one-word name's spelling may fall when its
+/// sound carries the match
+const ONE_WORD_SPELLING_SLACK: f32 = 0.1;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CorrectionRule {
+    Literal,
+    Regex,
+    Name,
+}
+
+/// One applied correction, for auditing
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct Correction {
+    /// Id of the segment it was in
+    pub segment: usize,
+    pub rule: CorrectionRule,
+    /// As recognized
+    pub original: String,
+    pub replacement: String,
+    /// When the corrected words were spoken
+    pub start: f64,
+    pub end: f64,
+    /// True when the segment had no word timings lining up with its text
+    /// and the times are interpolated
+    #[serde(default)]
+    pub estimated: bool,
+    /// For names, how close the original was, 0-1
+    #[serde(default)]
+    pub similarity: Option<f32>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct CorrectionsFile {
+    #[serde(default)]
+    names: Vec<String>,
+    #[serde(default)]
+    fuzzy_threshold: Option<f32>,
+    #[serde(default)]
+    replace: BTreeMap<String, String>,
+    #[serde(default)]
+    regex: Vec<RegexRule>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegexRule {
+    pattern: String,
+    replacement: String,
+}
+
+/// A canonical name with what it is compared by
+struct CanonicalName {
+    name: String,
+    /// Lowercase letters and digits only: "wilkesbarre"
+    letters: String,
+    sound: String,
+    words: usize,
+}
+
+pub struct Corrector {
+    /// Literal phrases, over normalized text
+    phrases: AhoCorasick,
+    replacements: Vec<String>,
+    patterns: Vec<(Regex, String)>,
+    names: Vec<CanonicalName>,
+    fuzzy_threshold: f32,
+}
+
+/// A replacement of a char span of the segment text
+struct Edit {
+    span: TextSpan,
+    replacement: String,
+    rule: CorrectionRule,
+    similarity: Option<f32>,
+}
+
+impl Corrector {
+    pub async fn load(files: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut parsed = Vec::with_capacity(files.len());
+        for path in files {
+            let path = path.as_ref();
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read corrections file {:?}", path))?;
+            let file: CorrectionsFile = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse corrections file {:?}", path))?;
+            parsed.push((path, file));
+        }
+        Self::from_files(parsed)
+    }
+
+    /// Layer parsed files, later ones overriding earlier ones
+    fn from_files<'a>(files: impl IntoIterator<Item = (&'a Path, CorrectionsFile)>) -> Result<Self> {
+        let mut literals: BTreeMap<String, String> = BTreeMap::new();
+        let mut patterns = Vec::new();
+        let mut names: Vec<String> = Vec::new();
+        let mut fuzzy_threshold = DEFAULT_FUZZY_THRESHOLD;
+
+        for (path, file) in files {
+            for (phrase, replacement) in file.replace {
+                let phrase = normalize(&phrase);
+                if !phrase.is_empty() {
+                    literals.insert(phrase, replacement);
+                }
+            }
+            for rule in file.regex {
+                let regex = Regex::new(&rule.pattern)
+                    .with_context(|| format!("Invalid correction pattern {:?} in {:?}", rule.pattern, path))?;
+                patterns.push((regex, rule.replacement));
+            }
+            names.extend(file.names.into_iter().map(|n| n.trim().to_string()).filter(|n| !n.is_empty()));
+            if let Some(threshold) = file.fuzzy_threshold {
+                fuzzy_threshold = threshold.clamp(0.0, 1.0);
+            }
+        }
+        names.sort();
+        names.dedup();
+
+        let (phrases, replacements): (Vec<String>, Vec<String>) = literals.into_iter().unzip();
+        let phrases = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&phrases)
+            .context("Failed to compile correction phrases")?;
+        let names = names
+            .into_iter()
+            .map(|name| CanonicalName {
+                letters: letters(&name),
+                sound: sound_key(&name),
+                words: name.split_whitespace().count(),
+                name,
+            })
+            .collect();
+
+        Ok(Self {
+            phrases,
+            replacements,
+            patterns,
+            names,
+            fuzzy_threshold,
+        })
+    }
+
+    /// Apply every correction to the segment's text and words, returning
+    /// what was changed
+    pub fn correct(&self, segment: &mut BasicSegment) -> Vec<Correction> {
+        let mut corrections = Vec::new();
+
+        let edits = self.literal_edits(&segment.text);
+        corrections.extend(apply(segment, edits));
+        for (regex, replacement) in &self.patterns {
+            let edits = regex
+                .captures_iter(&segment.text)
+                .filter_map(|captures| {
+                    let whole = captures.get(0)?;
+                    let mut expanded = String::new();
+                    captures.expand(replacement, &mut expanded);
+                    (expanded != whole.as_str()).then(|| Edit {
+                        span: char_span(&segment.text, whole.start(), whole.end()),
+                        replacement: expanded,
+                        rule: CorrectionRule::Regex,
+                        similarity: None,
+                    })
+                })
+                .collect();
+            corrections.extend(apply(segment, edits));
+        }
+        let edits = self.name_edits(&segment.text);
+        corrections.extend(apply(segment, edits));
+
+        corrections
+    }
+
+    fn literal_edits(&self, text: &str) -> Vec<Edit> {
+        let normalized = NormalizedText::new(text);
+        self.phrases
+            .find_iter(&normalized.text)
+            .filter(|m| normalized.is_word(m.start(), m.end()))
+            .map(|m| Edit {
+                span: normalized.span(m.start(), m.end()),
+                replacement: self.replacements[m.pattern().as_usize()].clone(),
+                rule: CorrectionRule::Literal,
+                similarity: None,
+            })
+            .filter(|edit| text.chars().skip(edit.span.start).take(edit.span.end - edit.span.start).ne(edit.replacement.chars()))
+            .collect()
+    }
+
+    /// Runs of capitalized words close enough to a canonical name
+    fn name_edits(&self, text: &str) -> Vec<Edit> {
+        if self.names.is_empty() {
+            return Vec::new();
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let tokens: Vec<TextSpan> = token_spans(text).into_iter().filter_map(|t| word_core(&chars, t)).collect();
+
+        let mut edits = Vec::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            let mut best: Option<(f32, usize, &CanonicalName)> = None;
+            for name in &self.names {
+                // Whisper splits and joins names: "Wilkes Barry", "Okon Kwo"
+                for length in name.words.saturating_sub(1).max(1)..=name.words + 1 {
+                    let Some(window) = tokens.get(i..i + length) else {
+                        continue;
+                    };
+                    let span = TextSpan {
+                        start: window[0].start,
+                        end: window[length - 1].end,
+                    };
+                    // Words in between must be plain spaces or hyphens
+                    let original: String = chars[span.start..span.end].iter().collect();
+                    if original.contains(|c: char| !c.is_alphanumeric() && !matches!(c, ' ' | '-' | '\'' | '’' | '.'))
+                        || !chars[span.start].is_uppercase()
+                    {
+                        continue;
+                    }
+                    let score = self.similarity(&original, name, starts_sentence(&chars, span.start));
+                    if score >= self.fuzzy_threshold && best.is_none_or(|(s, _, _)| score > s) {
+                        best = Some((score, length, name));
+                    }
+                }
+            }
+
+            match best {
+                Some((score, length, name)) => {
+                    let span = TextSpan {
+                        start: tokens[i].start,
+                        end: tokens[i + length - 1].end,
+                    };
+                    if chars[span.start..span.end].iter().copied().ne(name.name.chars()) {
+                        edits.push(Edit {
+                            span,
+                            replacement: name.name.clone(),
+                            rule: CorrectionRule::Name,
+                            similarity: Some(score),
+                        });
+                    }
+                    i += length;
+                }
+                None => i += 1,
+            }
+        }
+        edits
+    }
+
+    /// 0-1, the better of spelling and sound; spelling alone for the first
+    /// word of a sentence
+    fn similarity(&self, original: &str, name: &CanonicalName, sentence_start: bool) -> f32 {
+        let spelled = letters(original);
+        if spelled.chars().count() < MIN_FUZZY_LETTERS || name.letters.chars().count() < MIN_FUZZY_LETTERS {
+            return if spelled == name.letters { 1.0 } else { 0.0 };
+        }
+        let spelling = closeness(&spelled, &name.letters);
+        let min_spelling = if name.words == 1 {
+            self.fuzzy_threshold - ONE_WORD_SPELLING_SLACK
+        } else {
+            MIN_PHONETIC_SPELLING
+        };
+        let sound = sound_key(original);
+        let phonetic = !sentence_start
+            && spelling >= min_spelling
+            && sound.len() >= MIN_PHONETIC_KEY
+            && name.sound.len() >= MIN_PHONETIC_KEY;
+        if phonetic {
+            spelling.max(closeness(&sound, &name.sound) * PHONETIC_WEIGHT)
+        } else {
+            spelling
+        }
+    }
+}
+
+/// Apply non-overlapping edits to the segment, keeping its word timings in
+/// step with the text
+fn apply(segment: &mut BasicSegment, mut edits: Vec<Edit>) -> Vec<Correction> {
+    if edits.is_empty() {
+        return Vec::new();
+    }
+    edits.sort_by_key(|e| e.span.start);
+    let mut end = 0;
+    edits.retain(|e| {
+        let keep = e.span.start >= end;
+        if keep {
+            end = e.span.end;
+        }
+        keep
+    });
+
+    let chars: Vec<char> = segment.text.chars().collect();
+    let tokens = token_spans(&segment.text);
+    let aligned = !segment.words.is_empty() && segment.words.len() == tokens.len();
+    let char_count = chars.len().max(1);
+    let at = |c: usize| segment.start + (segment.end - segment.start) * c as f64 / char_count as f64;
+
+    let mut text = String::with_capacity(segment.text.len());
+    let mut text_chars = 0;
+    let mut cursor = 0;
+    let mut corrections = Vec::with_capacity(edits.len());
+    // Old token ranges each edit touched (merged where edits share a token)
+    // and the char range of the new text they became
+    let mut groups: Vec<(usize, usize, usize, usize)> = Vec::new();
+
+    for edit in edits {
+        let first = tokens.iter().position(|t| t.end > edit.span.start).unwrap_or(tokens.len().saturating_sub(1));
+        let last = tokens.iter().rposition(|t| t.start < edit.span.end).unwrap_or(first).max(first);
+        let (start, end) = if aligned {
+            (segment.words[first].start, segment.words[last].end)
+        } else {
+            (at(edit.span.start), at(edit.span.end))
+        };
+
+        text.extend(&chars[cursor..edit.span.start]);
+        text_chars += edit.span.start - cursor;
+        let new_start = text_chars - edit.span.start.saturating_sub(tokens.get(first).map_or(edit.span.start, |t| t.start));
+        text.push_str(&edit.replacement);
+        text_chars += edit.replacement.chars().count();
+        let new_end = text_chars + tokens.get(last).map_or(edit.span.end, |t| t.end).saturating_sub(edit.span.end);
+        match groups.last_mut() {
+            Some(group) if group.1 >= first => {
+                group.1 = group.1.max(last);
+                group.3 = new_end;
+            }
+            _ => groups.push((first, last, new_start, new_end)),
+        }
+
+        corrections.push(Correction {
+            segment: segment.id,
+            rule: edit.rule,
+            original: chars[edit.span.start..edit.span.end].iter().collect(),
+            replacement: edit.replacement,
+            start,
+            end,
+            estimated: !aligned,
+            similarity: edit.similarity,
+        });
+        cursor = edit.span.end;
+    }
+    text.extend(&chars[cursor..]);
+
+    if aligned {
+        segment.words = retime_words(&segment.words, &groups, &text)
+            .unwrap_or_else(|| estimate_word_timings(&text, segment.start, segment.end, segment.confidence));
+    } else {
+        // No way to tell which word is which: fix the words that are
+        // exactly what was corrected
+        for correction in &corrections {
+            let original = normalize(&correction.original);
+            if original.contains(' ') || correction.replacement.split_whitespace().count() != 1 {
+                continue;
+            }
+            for word in &mut segment.words {
+                if normalize(&word.text) == original {
+                    word.text = correction.replacement.clone();
+                }
+            }
+        }
+    }
+    segment.text = text;
+    corrections
+}
+
+/// One word per token of the corrected text: the words of each edited group
+/// give way to the tokens it became, spread over the group's time by length
+fn retime_words(words: &[WordTiming], groups: &[(usize, usize, usize, usize)], text: &str) -> Option<Vec<WordTiming>> {
+    let new_tokens = token_spans(text);
+    let chars: Vec<char> = text.chars().collect();
+    let token_text = |t: &TextSpan| chars[t.start..t.end].iter().collect::<String>();
+
+    let mut retimed = Vec::with_capacity(new_tokens.len());
+    let mut next_token = new_tokens.iter();
+    let mut i = 0;
+    while i < words.len() {
+        match groups.iter().find(|(first, ..)| *first == i) {
+            Some(&(first, last, new_start, new_end)) => {
+                let became: Vec<&TextSpan> = new_tokens.iter().filter(|t| t.start >= new_start && t.end <= new_end).collect();
+                for _ in 0..became.len() {
+                    next_token.next()?;
+                }
+                let (start, end) = (words[first].start, words[last].end);
+                let confidence = words[first..=last].iter().map(|w| w.confidence).fold(f32::INFINITY, f32::min);
+                let total: usize = became.iter().map(|t| t.end - t.start).sum::<usize>().max(1);
+                let mut cursor = start;
+                for token in became {
+                    let length = (end - start) * (token.end - token.start) as f64 / total as f64;
+                    retimed.push(WordTiming {
+                        text: token_text(token),
+                        start: cursor,
+                        end: cursor + length,
+                        confidence,
+                    });
+                    cursor += length;
+                }
+                i = last + 1;
+            }
+            None => {
+                let mut word = words[i].clone();
+                word.text = token_text(next_token.next()?);
+                retimed.push(word);
+                i += 1;
+            }
+        }
+    }
+    (retimed.len() == new_tokens.len()).then_some(retimed)
+}
+
+/// Whether the word at `start` opens the text or follows a full stop, so its
+/// capital says nothing about it being a name
+fn starts_sentence(chars: &[char], start: usize) -> bool {
+    chars[..start]
+        .iter()
+        .rev()
+        .find(|c| !c.is_whitespace() && !matches!(c, '"' | '“' | '\'' | '‘' | '(' | '['))
+        .is_none_or(|c| matches!(c, '.' | '!' | '?' | '…'))
+}
+
+/// The word inside a token: surrounding punctuation and a possessive 's off
+fn word_core(chars: &[char], token: TextSpan) -> Option<TextSpan> {
+    let token_chars = &chars[token.start..token.end];
+    let first = token_chars.iter().position(|c| c.is_alphanumeric())?;
+    let mut last = token_chars.iter().rposition(|c| c.is_alphanumeric())? + 1;
+    if last >= first + 3 && matches!(token_chars[last - 2], '\'' | '’') && token_chars[last - 1] == 's' {
+        last -= 2;
+    }
+    Some(TextSpan {
+        start: token.start + first,
+        end: token.start + last,
+    })
+}
+
+/// Lowercase letters and digits only
+fn letters(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// 1 minus edit distance over the longer length
+fn closeness(a: &str, b: &str) -> f32 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - edit_distance(a, b) as f32 / longest as f32
+}
+
+/// Rough sound of a name, after Metaphone: letter groups that sound alike
+/// folded together, vowels dropped after the first letter, repeats merged
+fn sound_key(text: &str) -> String {
+    let mut word = letters(text);
+    for (from, to) in [
+        ("sch", "sk"), ("tch", "ch"), ("ph", "f"), ("gh", "g"), ("ck", "k"), ("qu", "kw"),
+        ("dg", "j"), ("wr", "r"), ("kn", "n"), ("x", "ks"), ("cz", "ch"),
+    ] {
+        word = word.replace(from, to);
+    }
+
+    let chars: Vec<char> = word.chars().collect();
+    let mut key = String::with_capacity(chars.len());
+    for (i, &c) in chars.iter().enumerate() {
+        let next = chars.get(i + 1).copied();
+        let sound = match c {
+            'a' | 'e' | 'i' | 'o' | 'u' | 'y' | 'h' | 'w' if i > 0 => continue,
+            'a' | 'e' | 'i' | 'o' | 'u' | 'y' => 'a',
+            'c' if matches!(next, Some('e' | 'i' | 'y')) => 's',
+            'c' | 'q' | 'k' | 'g' => 'k',
+            'z' => 's',
+            'v' => 'f',
+            'b' => 'p',
+            'd' => 't',
+            c => c,
+        };
+        if !key.ends_with(sound) {
+            key.push(sound);
+        }
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corrector(toml: &str) -> Corrector {
+        let file: CorrectionsFile = toml::from_str(toml).unwrap();
+        Corrector::from_files([(Path::new("test.toml"), file)]).unwrap()
+    }
+
+    fn corrected(corrector: &Corrector, text: &str) -> String {
+        let mut segment = BasicSegment {
+            id: 0,
+            start: 0.0,
+            end: 5.0,
+            text: text.to_string(),
+            confidence: 0.9,
+            speaker: None,
+            words: Vec::new(),
+        };
+        corrector.correct(&mut segment);
+        segment.text
+    }
+
+    #[test]
+    fn short_names_leave_ordinary_words_alone() {
+        let corrector = corrector(r#"names = ["Casey", "Carey"]"#);
+
+        assert_eq!(corrected(&corrector, "Case in point, the bill failed."), "Case in point, the bill failed.");
+        assert_eq!(corrected(&corrector, "Karen said Carry the load."), "Karen said Carry the load.");
+    }
+
+    #[test]
+    fn near_misses_snap_to_names() {
+        let corrector = corrector(r#"names = ["Okonkwo", "Wilkes-Barre", "Schuylkill"]"#);
+
+        assert_eq!(
+            corrected(&corrector, "We met Okon Kwo in Wilkes Barry and Schoolkill County."),
+            "We met Okonkwo in Wilkes-Barre and Schuylkill County."
+        );
+        // The first word of a sentence only snaps on spelling
+        assert_eq!(corrected(&corrector, "Schoolkill County voted."), "Schoolkill County voted.");
+    }
+}
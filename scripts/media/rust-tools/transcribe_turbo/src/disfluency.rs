@@ -0,0 +1,319 @@
+//! Fillers, repeated words and restarts.
+//!
+//! Whisper keeps some of the "um"s and false starts of live speech. Each is
+//! tagged with its span in the segment text so the JSON stays verbatim, and
+//! `--clean-read` drops them from the captions and press transcripts.
+//!
+//! - fillers: "um", "uh" and friends anywhere; "you know", "I mean" and
+//!   "like" only when set off by commas, where they carry no meaning
+//! - repeats: the first copy of a word or short phrase said twice in a row
+//!   ("the the", "I think I think"), except emphatic ones ("very very")
+//! - restarts: a cut-off word picked up again ("gover- government"), or a
+//!   short fragment abandoned at a dash when the next clause starts over with
+//!   the same word ("We need to — we must")
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::text::{char_slice, TextSpan};
+use crate::TranscriptSegment;
+
+/// Fillers that never carry meaning
+const FILLERS: &[&str] = &["um", "umm", "uh", "uhh", "uhm", "er", "erm", "ah", "ahh", "eh", "hmm", "mm", "mhm"];
+/// Fillers only when set off by commas: "and, you know, it matters"
+const PARENTHETICAL_FILLERS: &[&[&str]] = &[&["you", "know"], &["i", "mean"], &["like"], &["you", "see"]];
+/// Words people double on purpose
+const EMPHATIC: &[&str] = &["very", "really", "no", "yes", "so", "had", "that", "bye", "ha", "go", "far", "much"];
+/// Longest phrase checked for an immediate repeat
+const MAX_REPEAT_WORDS: usize = 3;
+/// Fragments longer than this before a dash are a change of thought, not a restart
+const MAX_RESTART_WORDS: usize = 4;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DisfluencyKind {
+    Filler,
+    Repeat,
+    Restart,
+}
+
+/// A filler, repeat or restart in a segment
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Disfluency {
+    pub kind: DisfluencyKind,
+    pub text: String,
+    /// Where it occurs in the segment text
+    pub span: TextSpan,
+}
+
+/// How disfluent one speaker was
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct SpeakerDisfluency {
+    /// None when the transcript has no speaker labels
+    pub speaker: Option<String>,
+    pub words: usize,
+    pub fillers: usize,
+    pub repeats: usize,
+    pub restarts: usize,
+    /// Fillers per 100 words
+    pub filler_rate: f32,
+}
+
+/// A whitespace-separated token of the segment text
+struct Token<'a> {
+    raw: &'a str,
+    /// Char offsets of `raw`
+    start: usize,
+    end: usize,
+    /// Lowercased, surrounding punctuation removed
+    bare: String,
+}
+
+impl Token<'_> {
+    fn ends_clause(&self) -> bool {
+        self.raw.ends_with([',', ';', ':', '.', '!', '?'])
+    }
+
+    fn ends_sentence(&self) -> bool {
+        self.raw.ends_with(['.', '!', '?'])
+    }
+
+    /// "gover-", "to—" or a lone "—"
+    fn is_cut_off(&self) -> bool {
+        self.raw.ends_with(['-', '—', '–'])
+    }
+}
+
+/// Disfluencies in `text`, in order, none overlapping
+pub fn detect(text: &str) -> Vec<Disfluency> {
+    let tokens = tokenize(text);
+    let mut marks: Vec<Option<DisfluencyKind>> = vec![None; tokens.len()];
+
+    for (i, token) in tokens.iter().enumerate() {
+        if FILLERS.contains(&token.bare.as_str()) {
+            marks[i] = Some(DisfluencyKind::Filler);
+        }
+    }
+
+    for phrase in PARENTHETICAL_FILLERS {
+        for i in 0..tokens.len().saturating_sub(phrase.len() - 1) {
+            let run = &tokens[i..i + phrase.len()];
+            let matches = run.iter().zip(phrase.iter()).all(|(t, w)| t.bare == *w);
+            let opened = i == 0 || tokens[i - 1].ends_clause();
+            let closed = run.last().is_some_and(|t| t.raw.ends_with(',')) || i + phrase.len() == tokens.len();
+            // Inner words must run on: "you, know" isn't the phrase
+            let unbroken = run[..run.len() - 1].iter().all(|t| !t.ends_clause());
+            if matches && opened && closed && unbroken && marks[i..i + phrase.len()].iter().all(Option::is_none) {
+                marks[i..i + phrase.len()].fill(Some(DisfluencyKind::Filler));
+            }
+        }
+    }
+
+    for i in 0..tokens.len() {
+        for n in 1..=MAX_REPEAT_WORDS {
+            if i + 2 * n > tokens.len() || marks[i..i + 2 * n].iter().any(Option::is_some) {
+                continue;
+            }
+            let (first, second) = (&tokens[i..i + n], &tokens[i + n..i + 2 * n]);
+            let same = first.iter().zip(second).all(|(a, b)| !a.bare.is_empty() && a.bare == b.bare);
+            // "Go. Go now" is two sentences, not a stumble
+            let runs_on = first.iter().all(|t| !t.ends_sentence());
+            let emphatic = n == 1 && EMPHATIC.contains(&first[0].bare.as_str());
+            if same && runs_on && !emphatic {
+                marks[i..i + n].fill(Some(DisfluencyKind::Repeat));
+                break;
+            }
+        }
+    }
+
+    for i in 0..tokens.len() {
+        let token = &tokens[i];
+        if !token.is_cut_off() || marks[i].is_some() {
+            continue;
+        }
+        let Some(next) = tokens.get(i + 1) else {
+            continue;
+        };
+
+        // A cut-off word: "gover- government". A suspended hyphen, as in
+        // "pre- and post-election", isn't picked up again and stays
+        let stem = token.bare.trim_end_matches(['-', '—', '–']);
+        if token.raw.ends_with('-') && !stem.is_empty() && !token.raw.ends_with("--") {
+            if next.bare.starts_with(stem) {
+                marks[i] = Some(DisfluencyKind::Restart);
+            }
+            continue;
+        }
+
+        // An abandoned fragment: "We need to — we must"
+        let fragment_start = (0..i).rev().find(|&j| tokens[j].ends_clause()).map_or(0, |j| j + 1);
+        let words = tokens[fragment_start..=i].iter().filter(|t| !t.bare.is_empty()).count();
+        let starts_over = tokens[fragment_start].bare == next.bare;
+        if words <= MAX_RESTART_WORDS && starts_over && marks[fragment_start..=i].iter().all(Option::is_none) {
+            marks[fragment_start..=i].fill(Some(DisfluencyKind::Restart));
+        }
+    }
+
+    // Neighbouring tokens of one kind form one span
+    let mut found: Vec<Disfluency> = Vec::new();
+    let mut previous: Option<(usize, DisfluencyKind)> = None;
+    for (i, mark) in marks.iter().enumerate() {
+        let Some(kind) = *mark else {
+            previous = None;
+            continue;
+        };
+        // Fillers stay one per word so they can be counted, "you know" aside
+        let joins = previous.is_some_and(|(p, k)| {
+            p + 1 == i && k == kind && (kind != DisfluencyKind::Filler || is_phrase_continuation(&tokens, i))
+        });
+        match found.last_mut() {
+            Some(last) if joins => last.span.end = tokens[i].end,
+            _ => found.push(Disfluency {
+                kind,
+                text: String::new(),
+                span: TextSpan {
+                    start: tokens[i].start,
+                    end: tokens[i].end,
+                },
+            }),
+        }
+        previous = Some((i, kind));
+    }
+
+    for disfluency in &mut found {
+        disfluency.text = char_slice(text, disfluency.span).trim_end_matches([',', ';', ':', '.', '!', '?']).to_string();
+    }
+    found
+}
+
+/// Filler, repeat and restart counts per speaker, in order of first appearance
+pub fn speaker_rates(segments: &[TranscriptSegment]) -> Vec<SpeakerDisfluency> {
+    let mut rates: Vec<SpeakerDisfluency> = Vec::new();
+
+    for segment in segments {
+        let index = match rates.iter().position(|r| r.speaker == segment.speaker) {
+            Some(index) => index,
+            None => {
+                rates.push(SpeakerDisfluency {
+                    speaker: segment.speaker.clone(),
+                    words: 0,
+                    fillers: 0,
+                    repeats: 0,
+                    restarts: 0,
+                    filler_rate: 0.0,
+                });
+                rates.len() - 1
+            }
+        };
+        let rate = &mut rates[index];
+        rate.words += segment.text.split_whitespace().count();
+        for disfluency in &segment.disfluencies {
+            match disfluency.kind {
+                DisfluencyKind::Filler => rate.fillers += 1,
+                DisfluencyKind::Repeat => rate.repeats += 1,
+                DisfluencyKind::Restart => rate.restarts += 1,
+            }
+        }
+    }
+
+    for rate in &mut rates {
+        rate.filler_rate = if rate.words > 0 { 100.0 * rate.fillers as f32 / rate.words as f32 } else { 0.0 };
+    }
+    rates
+}
+
+/// Drop the tagged spans from `text`, tidying the punctuation and
+/// capitalization left behind: "Um, we need, uh, to act" reads "We need to act"
+pub fn clean(text: &str, disfluencies: &[Disfluency]) -> String {
+    if disfluencies.is_empty() {
+        return text.to_string();
+    }
+
+    let tokens = tokenize(text);
+    let dropped: Vec<bool> = tokens
+        .iter()
+        .map(|t| disfluencies.iter().any(|d| d.span.start < t.end && t.start < d.span.end))
+        .collect();
+
+    let mut kept: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !dropped[i] {
+            kept.push(tokens[i].raw.to_string());
+            i += 1;
+            continue;
+        }
+
+        let run_start = i;
+        while i < tokens.len() && dropped[i] {
+            i += 1;
+        }
+        let last = &tokens[i - 1];
+        let sentence_start = kept.last().is_none_or(|k| k.ends_with(['.', '!', '?']));
+
+        // A sentence ending inside the dropped run moves to the word before it
+        if last.ends_sentence() {
+            if let Some(previous) = kept.last_mut() {
+                let terminal: String = last.raw.chars().rev().take_while(|c| matches!(c, '.' | '!' | '?')).collect();
+                *previous = format!("{}{}", previous.trim_end_matches([',', ';', ':']), terminal);
+            }
+        } else if last.raw.ends_with(',') {
+            // ", uh," leaves one pause at most, and none mid-clause
+            if let Some(previous) = kept.last_mut() {
+                if previous.ends_with(',') && tokens[run_start..i].iter().all(|t| !t.is_cut_off()) {
+                    previous.pop();
+                }
+            }
+        }
+
+        let opens_sentence = sentence_start || last.ends_sentence();
+        if opens_sentence && i < tokens.len() && !dropped[i] {
+            let next = tokens[i].raw;
+            let mut chars = next.chars();
+            if let Some(first) = chars.next() {
+                kept.push(first.to_uppercase().chain(chars).collect());
+                i += 1;
+            }
+        }
+    }
+
+    kept.join(" ")
+}
+
+/// Filler words that belong to one parenthetical phrase ("you know")
+fn is_phrase_continuation(tokens: &[Token], i: usize) -> bool {
+    !FILLERS.contains(&tokens[i].bare.as_str()) && !tokens[i - 1].ends_clause()
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut char_index = 0;
+    let mut current: Option<(usize, usize)> = None; // (byte start, char start)
+
+    for (byte, c) in text.char_indices() {
+        if c.is_whitespace() {
+            if let Some((byte_start, char_start)) = current.take() {
+                tokens.push(token(&text[byte_start..byte], char_start, char_index));
+            }
+        } else if current.is_none() {
+            current = Some((byte, char_index));
+        }
+        char_index += 1;
+    }
+    if let Some((byte_start, char_start)) = current {
+        tokens.push(token(&text[byte_start..], char_start, char_index));
+    }
+
+    tokens
+}
+
+fn token(raw: &str, start: usize, end: usize) -> Token<'_> {
+    Token {
+        raw,
+        start,
+        end,
+        bare: raw
+            .trim_matches(|c: char| !c.is_alphanumeric() && c != '\'' && c != '’')
+            .to_lowercase(),
+    }
+}
@@ -0,0 +1,239 @@
+//! Acoustic emphasis: how much louder, more animated in pitch, and faster or
+//! slower a segment is than the same speaker's usual delivery.
+//!
+//! Per segment we measure RMS loudness, F0 (autocorrelation pitch tracking
+//! over voiced frames) and speaking rate. Each is turned into a z-score
+//! against the speaker's own baseline, so a naturally loud speaker does not
+//! read as permanently emphatic, and the weighted sum is squashed into
+//! `emphasis_level` in 0..1.
+
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::audio::AudioSignal;
+use crate::BasicSegment;
+
+/// Pitch tracking runs at roughly this rate; voice F0 sits far below Nyquist
+const PITCH_SAMPLE_RATE: u32 = 8_000;
+const FRAME_SECONDS: f64 = 0.040;
+const HOP_SECONDS: f64 = 0.020;
+const MIN_F0_HZ: f32 = 75.0;
+const MAX_F0_HZ: f32 = 400.0;
+/// Normalized autocorrelation peak a frame needs to count as voiced
+const VOICING_THRESHOLD: f32 = 0.5;
+/// Frames quieter than this are treated as silence, dBFS
+const SILENCE_DB: f32 = -50.0;
+
+/// Weights of the loudness, pitch range, pitch variability and rate z-scores
+const WEIGHTS: [f32; 4] = [0.45, 0.2, 0.2, 0.15];
+/// Smallest spread used for a baseline, so near-identical segments don't
+/// turn tiny differences into huge z-scores
+const SPREAD_FLOORS: [f32; 4] = [3.0, 2.0, 1.0, 0.5];
+
+/// Raw acoustic measurements for one segment
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct AcousticFeatures {
+    /// RMS loudness in dBFS
+    pub loudness_db: f32,
+    /// Loudness relative to the speaker's median segment, in dB
+    pub loudness_delta_db: f32,
+    /// Median F0 over voiced frames, in Hz
+    pub f0_median_hz: Option<f32>,
+    /// Spread between the 10th and 90th percentile F0, in semitones
+    pub f0_range_semitones: Option<f32>,
+    /// Standard deviation of F0, in semitones
+    pub f0_std_semitones: Option<f32>,
+    /// Words per second
+    pub speaking_rate: f32,
+    /// Speaking rate divided by the speaker's median rate
+    pub rate_ratio: f32,
+}
+
+/// Acoustic features and emphasis level for every segment, in order
+pub fn measure(audio: &AudioSignal, segments: &[BasicSegment]) -> Vec<(AcousticFeatures, f32)> {
+    let mut features: Vec<AcousticFeatures> = segments
+        .par_iter()
+        .map(|segment| segment_features(audio, segment))
+        .collect();
+
+    // Group segments by speaker; unlabelled segments share one baseline
+    let mut by_speaker: HashMap<Option<&str>, Vec<usize>> = HashMap::new();
+    for (index, segment) in segments.iter().enumerate() {
+        by_speaker.entry(segment.speaker.as_deref()).or_default().push(index);
+    }
+
+    let mut emphasis = vec![0.0; segments.len()];
+    for indices in by_speaker.values() {
+        let metrics: Vec<[Option<f32>; 4]> = indices.iter().map(|&i| metric_vector(&features[i])).collect();
+        let baselines: Vec<(f32, f32)> = (0..4)
+            .map(|m| baseline(metrics.iter().filter_map(|v| v[m]), SPREAD_FLOORS[m]))
+            .collect();
+
+        for (&index, values) in indices.iter().zip(&metrics) {
+            let mut score = 0.0;
+            for m in 0..4 {
+                let Some(value) = values[m] else { continue };
+                let (center, spread) = baselines[m];
+                let z = (value - center) / spread;
+                // A marked slow-down is as emphatic as a speed-up
+                score += WEIGHTS[m] * if m == 3 { z.abs() } else { z };
+            }
+            emphasis[index] = squash(score);
+
+            let feature = &mut features[index];
+            feature.loudness_delta_db = feature.loudness_db - baselines[0].0;
+            if baselines[3].0 > 0.0 {
+                feature.rate_ratio = feature.speaking_rate / baselines[3].0;
+            }
+        }
+    }
+
+    features.into_iter().zip(emphasis).collect()
+}
+
+/// Emphasis guessed from punctuation and shouted words, for transcripts without audio
+pub fn from_text(text: &str) -> f32 {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return 0.0;
+    }
+
+    let shouted = words
+        .iter()
+        .filter(|w| {
+            let letters: Vec<char> = w.chars().filter(|c| c.is_alphabetic()).collect();
+            letters.len() > 1 && letters.iter().all(|c| c.is_uppercase())
+        })
+        .count();
+    let shouted_ratio = shouted as f32 / words.len() as f32;
+    let exclamation_count = text.matches('!').count() as f32;
+    let question_count = text.matches('?').count() as f32;
+
+    (exclamation_count * 0.3 + shouted_ratio * 2.0 + question_count * 0.2).min(1.0)
+}
+
+fn segment_features(audio: &AudioSignal, segment: &BasicSegment) -> AcousticFeatures {
+    let samples = audio.slice(segment.start, segment.end);
+    let loudness_db = rms_db(samples);
+
+    let semitones: Vec<f32> = pitch_track(samples, audio.sample_rate)
+        .into_iter()
+        .map(|f0| 12.0 * (f0 / 100.0).log2())
+        .collect();
+    let (f0_median_hz, f0_range_semitones, f0_std_semitones) = if semitones.len() >= 3 {
+        let mut sorted = semitones.clone();
+        sorted.sort_by(f32::total_cmp);
+        let median = percentile(&sorted, 0.5);
+        let range = percentile(&sorted, 0.9) - percentile(&sorted, 0.1);
+        (Some(100.0 * 2f32.powf(median / 12.0)), Some(range), Some(std_dev(&semitones)))
+    } else {
+        (None, None, None)
+    };
+
+    let duration = (segment.end - segment.start).max(0.001) as f32;
+    let words = if segment.words.is_empty() {
+        segment.text.split_whitespace().count()
+    } else {
+        segment.words.len()
+    };
+
+    AcousticFeatures {
+        loudness_db,
+        loudness_delta_db: 0.0,
+        f0_median_hz,
+        f0_range_semitones,
+        f0_std_semitones,
+        speaking_rate: words as f32 / duration,
+        rate_ratio: 1.0,
+    }
+}
+
+fn metric_vector(features: &AcousticFeatures) -> [Option<f32>; 4] {
+    [
+        Some(features.loudness_db).filter(|db| *db > SILENCE_DB),
+        features.f0_range_semitones,
+        features.f0_std_semitones,
+        Some(features.speaking_rate).filter(|rate| *rate > 0.0),
+    ]
+}
+
+/// Median and standard deviation (floored) of a speaker's values
+fn baseline(values: impl Iterator<Item = f32>, floor: f32) -> (f32, f32) {
+    let mut values: Vec<f32> = values.collect();
+    if values.is_empty() {
+        return (0.0, floor);
+    }
+    values.sort_by(f32::total_cmp);
+    (percentile(&values, 0.5), std_dev(&values).max(floor))
+}
+
+/// Logistic curve centred one standard deviation above baseline
+fn squash(score: f32) -> f32 {
+    1.0 / (1.0 + (-1.5 * (score - 1.0)).exp())
+}
+
+pub fn rms_db(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return -120.0;
+    }
+    let mean_square = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+    10.0 * mean_square.max(1e-12).log10()
+}
+
+/// F0 in Hz of every voiced frame
+fn pitch_track(samples: &[f32], sample_rate: u32) -> Vec<f32> {
+    // Decimate by block averaging, which doubles as a crude low-pass
+    let factor = (sample_rate / PITCH_SAMPLE_RATE).max(1) as usize;
+    let signal: Vec<f32> = samples
+        .chunks(factor)
+        .map(|block| block.iter().sum::<f32>() / block.len() as f32)
+        .collect();
+    let rate = sample_rate as f32 / factor as f32;
+
+    let frame = (FRAME_SECONDS * rate as f64) as usize;
+    let hop = (HOP_SECONDS * rate as f64) as usize;
+    let min_lag = (rate / MAX_F0_HZ) as usize;
+    let max_lag = (rate / MIN_F0_HZ) as usize;
+    if frame <= max_lag || hop == 0 || signal.len() < frame {
+        return Vec::new();
+    }
+
+    (0..=(signal.len() - frame) / hop)
+        .filter_map(|i| {
+            let window = &signal[i * hop..i * hop + frame];
+            if rms_db(window) < SILENCE_DB {
+                return None;
+            }
+
+            let (lag, peak) = (min_lag..=max_lag)
+                .map(|lag| (lag, normalized_autocorrelation(window, lag)))
+                .max_by(|a, b| a.1.total_cmp(&b.1))?;
+            (peak >= VOICING_THRESHOLD).then(|| rate / lag as f32)
+        })
+        .collect()
+}
+
+fn normalized_autocorrelation(window: &[f32], lag: usize) -> f32 {
+    let (head, tail) = (&window[..window.len() - lag], &window[lag..]);
+    let cross: f32 = head.iter().zip(tail).map(|(a, b)| a * b).sum();
+    let energy = head.iter().map(|a| a * a).sum::<f32>() * tail.iter().map(|b| b * b).sum::<f32>();
+    if energy <= 0.0 {
+        0.0
+    } else {
+        cross / energy.sqrt()
+    }
+}
+
+/// `sorted` must be ascending and non-empty
+pub fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let index = ((sorted.len() - 1) as f32 * p).round() as usize;
+    sorted[index]
+}
+
+fn std_dev(values: &[f32]) -> f32 {
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    (values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / values.len() as f32).sqrt()
+}
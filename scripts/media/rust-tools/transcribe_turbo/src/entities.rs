@@ -0,0 +1,552 @@
+//! Named entities: people, organizations and places from a gazetteer, and
+//! bill numbers, dates and dollar amounts from pattern rules.
+//!
+//! The gazetteer lives in `data/gazetteer.toml`; see that file for the
+//! override format used by campaign files. Names match on normalized text
+//! like taxonomy terms, but only where the speaker's words are capitalized
+//! (acronyms fully), so "the Senate" is found and "the senate of my school"
+//! is not. Where candidates overlap the longest wins: "Latin America" is one
+//! place, not "America".
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use anyhow::{Context, Result};
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::sentences::span_times;
+use crate::text::{char_slice, normalize, NormalizedText, TextSpan};
+use crate::TranscriptSegment;
+
+const BUILTIN_GAZETTEER: &str = include_str!("../data/gazetteer.toml");
+
+/// Words that may stay lowercase inside a capitalized name
+const NAME_PARTICLES: &[&str] = &["the", "of", "and", "for", "de", "la", "van", "von"];
+/// Words a capitalized run can start with that aren't part of an act's name
+const DETERMINERS: &[&str] = &["The", "This", "That", "Our", "Their", "His", "Her", "My", "Your", "A", "An", "Every", "Any"];
+
+const MONTHS: &[&str] = &[
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+    "December",
+];
+const ORDINALS: &[&str] = &[
+    "first", "second", "third", "fourth", "fifth", "sixth", "seventh", "eighth", "ninth", "tenth", "eleventh",
+    "twelfth", "thirteenth", "fourteenth", "fifteenth", "sixteenth", "seventeenth", "eighteenth", "nineteenth",
+];
+/// Spoken numbers that come before "million dollars"
+const SPOKEN_AMOUNTS: &[(&str, &str)] = &[
+    ("a", "1"), ("one", "1"), ("two", "2"), ("three", "3"), ("four", "4"), ("five", "5"), ("six", "6"),
+    ("seven", "7"), ("eight", "8"), ("nine", "9"), ("ten", "10"), ("a hundred", "100"),
+];
+
+/// "H.R. 1234", "HR1234", "S. 5", "SB 5", "H.J.Res. 7"
+static BILL_NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(H\.?\s?J\.?\s?Res\.?|S\.?\s?J\.?\s?Res\.?|H\.?\s?Res\.?|S\.\s?Res\.?|H\.?\s?R\.?|S\.|SB|HB|AB|SF|HF|LB|SJR|HJR)\s?(\d{1,5})\b").unwrap()
+});
+/// "House Bill 12", "Senate Joint Resolution No. 4"
+static SPOKEN_BILL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(House|Senate|Assembly)\s+(Joint\s+)?(Bill|Resolution)\s+(?:No\.?\s*|Number\s+)?(\d{1,5})\b").unwrap()
+});
+/// "Inflation Reduction Act", "CHIPS and Science Act", "Civil Rights Act of 1964"
+static NAMED_ACT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b[A-Z][\w'’-]*(?:\s+(?:[A-Z][\w'’-]*|of|and|for|to|on|&)){0,7}\s+Act\b(?:\s+of\s+(?:19|20)\d{2}\b)?").unwrap()
+});
+/// "November 5", "Nov. 5th, 2024", "January the twentieth"
+static MONTH_DAY: LazyLock<Regex> = LazyLock::new(|| {
+    let months = MONTHS
+        .iter()
+        .map(|m| format!("{}|{}\\.", m, &m[..3]))
+        .chain(["Sept\\.".to_string()])
+        .collect::<Vec<_>>()
+        .join("|");
+    let ordinals = format!(
+        "(?:twenty|thirty)[- ](?:{0})|{0}|twentieth|thirtieth",
+        ORDINALS[..9].join("|")
+    );
+    let ordinals = format!("{}|{}", ordinals, ORDINALS[9..].join("|"));
+    Regex::new(&format!(
+        r"\b({})\s+(?:the\s+)?(?:(\d{{1,2}})(?:st|nd|rd|th)?|({}))\b(?:,?\s+((?:19|20)\d{{2}})\b)?",
+        months, ordinals
+    ))
+    .unwrap()
+});
+/// "11/5/2024", "11/5/24"
+static NUMERIC_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{1,2})/(\d{1,2})/(\d{4}|\d{2})\b").unwrap());
+/// A bare year after a preposition: "by 2030", "since 1965"
+static YEAR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?i:in|since|by|until|before|after|through|from)\s+((?:19|20)\d{2})\b").unwrap()
+});
+/// "$1.2 trillion", "$15", "$35M"
+static DOLLARS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\s?(\d{1,3}(?:,\d{3})+|\d+)(\.\d+)?(?:\s?(thousand|million|billion|trillion|[kKmMbBtT]n?)\b)?").unwrap()
+});
+/// "35 million dollars", "a trillion dollars"
+static SPOKEN_DOLLARS: LazyLock<Regex> = LazyLock::new(|| {
+    let words = SPOKEN_AMOUNTS.iter().map(|(w, _)| *w).collect::<Vec<_>>().join("|");
+    Regex::new(&format!(
+        r"(?i)\b(\d{{1,3}}(?:,\d{{3}})+|\d+|{})(\.\d+)?\s+(?:(thousand|million|billion|trillion)\s+)?dollars\b",
+        words
+    ))
+    .unwrap()
+});
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    Person,
+    Organization,
+    Place,
+    Bill,
+    Date,
+    Money,
+}
+
+impl EntityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntityKind::Person => "person",
+            EntityKind::Organization => "organization",
+            EntityKind::Place => "place",
+            EntityKind::Bill => "bill",
+            EntityKind::Date => "date",
+            EntityKind::Money => "money",
+        }
+    }
+}
+
+/// An entity mentioned in a segment
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct EntityMention {
+    /// Canonical name: "Joe Biden" for "President Biden", "H.R. 1234" for "HR1234"
+    pub name: String,
+    pub kind: EntityKind,
+    /// The words as spoken
+    pub text: String,
+    /// Where the mention occurs in the segment text
+    pub span: TextSpan,
+}
+
+/// Every mention of one entity across the transcript
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct EntityIndexEntry {
+    pub name: String,
+    pub kind: EntityKind,
+    pub mentions: usize,
+    /// Seconds into the recording of the first mention
+    pub first_mention: f64,
+    /// Ids of the segments that mention it
+    pub segments: Vec<usize>,
+    /// The different ways it was mentioned, as spoken
+    #[serde(default)]
+    pub forms: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct GazetteerFile {
+    #[serde(default)]
+    people: Vec<EntrySpec>,
+    #[serde(default)]
+    organizations: Vec<EntrySpec>,
+    #[serde(default)]
+    places: Vec<EntrySpec>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EntrySpec {
+    Plain(String),
+    Detailed {
+        name: String,
+        #[serde(default)]
+        aliases: Vec<String>,
+    },
+}
+
+struct Entry {
+    name: String,
+    kind: EntityKind,
+    /// Spellings besides the name, as written in the gazetteer
+    aliases: Vec<String>,
+}
+
+/// What an automaton pattern stands for
+struct Target {
+    entry: usize,
+    /// Spelled in capitals ("ICE", "U.S."), so the mention must be too
+    acronym: bool,
+}
+
+struct Candidate {
+    span: TextSpan,
+    name: String,
+    kind: EntityKind,
+}
+
+/// Finds entity mentions in segment text
+pub struct EntityExtractor {
+    entries: Vec<Entry>,
+    automaton: AhoCorasick,
+    targets: Vec<Target>,
+}
+
+impl EntityExtractor {
+    /// Built-in gazetteer with each override file layered on in order
+    pub async fn load(overrides: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut files = vec![toml::from_str(BUILTIN_GAZETTEER).context("Built-in gazetteer is invalid")?];
+
+        for path in overrides {
+            let path = path.as_ref();
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read gazetteer {:?}", path))?;
+            files.push(toml::from_str(&content)
+                .with_context(|| format!("Failed to parse gazetteer {:?}", path))?);
+        }
+
+        Self::from_files(files)
+    }
+
+    fn from_files(files: Vec<GazetteerFile>) -> Result<Self> {
+        let mut entries: Vec<Entry> = Vec::new();
+
+        for file in files {
+            // A removed name drops the entity; a removed alias just that spelling
+            let removed: Vec<String> = file.remove.iter().map(|n| normalize(n)).collect();
+            entries.retain(|e| !removed.contains(&normalize(&e.name)));
+            for entry in &mut entries {
+                entry.aliases.retain(|a| !removed.contains(&normalize(a)));
+            }
+
+            let specs = file
+                .people
+                .into_iter()
+                .map(|spec| (spec, EntityKind::Person))
+                .chain(file.organizations.into_iter().map(|spec| (spec, EntityKind::Organization)))
+                .chain(file.places.into_iter().map(|spec| (spec, EntityKind::Place)));
+            for (spec, kind) in specs {
+                let (name, aliases) = match spec {
+                    EntrySpec::Plain(name) => (name, Vec::new()),
+                    EntrySpec::Detailed { name, aliases } => (name, aliases),
+                };
+                let name = name.trim().to_string();
+                if normalize(&name).is_empty() {
+                    continue;
+                }
+
+                match entries.iter_mut().find(|e| e.kind == kind && normalize(&e.name) == normalize(&name)) {
+                    Some(existing) => {
+                        for alias in aliases {
+                            if !existing.aliases.contains(&alias) {
+                                existing.aliases.push(alias);
+                            }
+                        }
+                    }
+                    None => entries.push(Entry { name, kind, aliases }),
+                }
+            }
+        }
+
+        let mut patterns: Vec<String> = Vec::new();
+        let mut targets: Vec<Target> = Vec::new();
+        for (index, entry) in entries.iter().enumerate() {
+            for spelling in std::iter::once(&entry.name).chain(&entry.aliases) {
+                let pattern = normalize(spelling);
+                if pattern.is_empty() || patterns.contains(&pattern) {
+                    continue;
+                }
+                let letters: Vec<char> = spelling.chars().filter(|c| c.is_alphabetic()).collect();
+                patterns.push(pattern);
+                targets.push(Target {
+                    entry: index,
+                    acronym: letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase()),
+                });
+            }
+        }
+
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .build(&patterns)
+            .context("Failed to compile gazetteer")?;
+
+        Ok(Self {
+            entries,
+            automaton,
+            targets,
+        })
+    }
+
+    /// Entity mentions in `text`, in order, none overlapping
+    pub fn find(&self, text: &str) -> Vec<EntityMention> {
+        let mut candidates = self.gazetteer_candidates(text);
+        candidates.extend(pattern_candidates(text));
+
+        // Longest first, so "Latin America" beats "America"
+        candidates.sort_by(|a, b| {
+            (b.span.end - b.span.start)
+                .cmp(&(a.span.end - a.span.start))
+                .then(a.span.start.cmp(&b.span.start))
+        });
+        let mut chosen: Vec<Candidate> = Vec::new();
+        for candidate in candidates {
+            if chosen.iter().all(|c| c.span.end <= candidate.span.start || candidate.span.end <= c.span.start) {
+                chosen.push(candidate);
+            }
+        }
+        chosen.sort_by_key(|c| c.span.start);
+
+        chosen
+            .into_iter()
+            .map(|c| EntityMention {
+                text: char_slice(text, c.span).to_string(),
+                name: c.name,
+                kind: c.kind,
+                span: c.span,
+            })
+            .collect()
+    }
+
+    fn gazetteer_candidates(&self, text: &str) -> Vec<Candidate> {
+        let normalized = NormalizedText::new(text);
+        self.automaton
+            .find_overlapping_iter(&normalized.text)
+            .filter(|m| normalized.is_word(m.start(), m.end()))
+            .filter_map(|m| {
+                let target = &self.targets[m.pattern().as_usize()];
+                let mut span = normalized.span(m.start(), m.end());
+                // Normalizing drops the closing dot of "U.S."
+                if char_slice(text, span).contains('.') && text.chars().nth(span.end) == Some('.') {
+                    span.end += 1;
+                }
+                if !capitalized(char_slice(text, span), target.acronym) {
+                    return None;
+                }
+                let entry = &self.entries[target.entry];
+                Some(Candidate {
+                    span,
+                    name: entry.name.clone(),
+                    kind: entry.kind,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Every mentioned entity with its mention count, most mentioned first
+pub fn index(segments: &[TranscriptSegment]) -> Vec<EntityIndexEntry> {
+    let mut entries: Vec<EntityIndexEntry> = Vec::new();
+    let mut positions: HashMap<(EntityKind, String), usize> = HashMap::new();
+
+    for segment in segments {
+        for mention in &segment.entities {
+            let key = (mention.kind, mention.name.clone());
+            let index = *positions.entry(key).or_insert_with(|| {
+                entries.push(EntityIndexEntry {
+                    name: mention.name.clone(),
+                    kind: mention.kind,
+                    mentions: 0,
+                    first_mention: span_times(segment, mention.span).0,
+                    segments: Vec::new(),
+                    forms: Vec::new(),
+                });
+                entries.len() - 1
+            });
+
+            let entry = &mut entries[index];
+            entry.mentions += 1;
+            if entry.segments.last() != Some(&segment.id) {
+                entry.segments.push(segment.id);
+            }
+            if !entry.forms.contains(&mention.text) {
+                entry.forms.push(mention.text.clone());
+            }
+        }
+    }
+
+    entries.sort_by(|a, b| b.mentions.cmp(&a.mentions).then(a.first_mention.total_cmp(&b.first_mention)));
+    entries
+}
+
+/// True when the mention is written like a name: content words capitalized,
+/// or every letter for acronyms
+fn capitalized(mention: &str, acronym: bool) -> bool {
+    if acronym {
+        return mention.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase());
+    }
+    mention
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty() && !NAME_PARTICLES.contains(&w.to_lowercase().as_str()))
+        .all(|w| w.chars().next().is_some_and(|c| c.is_uppercase() || c.is_numeric()))
+}
+
+fn pattern_candidates(text: &str) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mut push = |start: usize, end: usize, name: String, kind: EntityKind| {
+        candidates.push(Candidate {
+            span: TextSpan {
+                start: text[..start].chars().count(),
+                end: text[..end].chars().count(),
+            },
+            name,
+            kind,
+        });
+    };
+
+    for caps in bill_numbers(text) {
+        let whole = caps.get(0).unwrap();
+        let prefix: String = caps[1].chars().filter(|c| c.is_alphabetic()).collect::<String>().to_uppercase();
+        let prefix = match prefix.as_str() {
+            "HR" => "H.R.",
+            "S" => "S.",
+            "HJRES" => "H.J.Res.",
+            "SJRES" => "S.J.Res.",
+            "HRES" => "H.Res.",
+            "SRES" => "S.Res.",
+            other => other,
+        };
+        push(whole.start(), whole.end(), format!("{} {}", prefix, &caps[2]), EntityKind::Bill);
+    }
+
+    for caps in SPOKEN_BILL.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let prefix = match (&caps[1], caps.get(2).is_some(), &caps[3]) {
+            ("House", true, _) => "H.J.Res.",
+            ("Senate", true, _) => "S.J.Res.",
+            ("House", false, "Resolution") => "H.Res.",
+            ("Senate", false, "Resolution") => "S.Res.",
+            ("House", false, _) => "HB",
+            ("Senate", false, _) => "SB",
+            _ => "AB",
+        };
+        push(whole.start(), whole.end(), format!("{} {}", prefix, &caps[4]), EntityKind::Bill);
+    }
+
+    for found in NAMED_ACT.find_iter(text) {
+        // "Our Affordable Care Act" is about the Affordable Care Act
+        let mut start = found.start();
+        let mut rest = found.as_str();
+        while let Some((first, tail)) = rest.split_once(char::is_whitespace) {
+            if !DETERMINERS.contains(&first) {
+                break;
+            }
+            let tail = tail.trim_start();
+            start += rest.len() - tail.len();
+            rest = tail;
+        }
+        if rest != "Act" {
+            let name = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+            push(start, found.end(), name, EntityKind::Bill);
+        }
+    }
+
+    for caps in MONTH_DAY.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let month = MONTHS
+            .iter()
+            .find(|m| caps[1].starts_with(&m[..3]))
+            .copied()
+            .unwrap_or(&caps[1]);
+        let day = match (caps.get(2), caps.get(3)) {
+            (Some(digits), _) => digits.as_str().parse().ok(),
+            (_, Some(words)) => ordinal_day(words.as_str()),
+            _ => None,
+        };
+        let Some(day) = day.filter(|d| (1..=31).contains(d)) else {
+            continue;
+        };
+        let name = match caps.get(4) {
+            Some(year) => format!("{} {}, {}", month, day, year.as_str()),
+            None => format!("{} {}", month, day),
+        };
+        push(whole.start(), whole.end(), name, EntityKind::Date);
+    }
+
+    for caps in NUMERIC_DATE.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let (month, day): (usize, u32) = (caps[1].parse().unwrap_or(0), caps[2].parse().unwrap_or(0));
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            continue;
+        }
+        let year = if caps[3].len() == 2 { format!("20{}", &caps[3]) } else { caps[3].to_string() };
+        push(whole.start(), whole.end(), format!("{} {}, {}", MONTHS[month - 1], day, year), EntityKind::Date);
+    }
+
+    for caps in YEAR.captures_iter(text) {
+        let year = caps.get(1).unwrap();
+        push(year.start(), year.end(), year.as_str().to_string(), EntityKind::Date);
+    }
+
+    for caps in DOLLARS.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        push(whole.start(), whole.end(), dollar_name(&caps[1], &caps), EntityKind::Money);
+    }
+
+    for caps in SPOKEN_DOLLARS.captures_iter(text) {
+        let whole = caps.get(0).unwrap();
+        let amount = caps[1].to_lowercase();
+        let amount = SPOKEN_AMOUNTS
+            .iter()
+            .find(|(word, _)| *word == amount)
+            .map(|(_, digits)| digits.to_string())
+            .unwrap_or(amount);
+        push(whole.start(), whole.end(), dollar_name(&amount, &caps), EntityKind::Money);
+    }
+
+    candidates
+}
+
+/// Written bill numbers ("H.R. 1234", "SB 5")
+fn bill_numbers(text: &str) -> impl Iterator<Item = Captures<'_>> {
+    BILL_NUMBER.captures_iter(text).filter(|caps| {
+        // "U.S. 5 million" isn't Senate bill 5
+        let start = caps.get(0).unwrap().start();
+        !text[..start].chars().next_back().is_some_and(|c| c == '.' || c.is_alphanumeric())
+    })
+}
+
+/// Byte ranges of the bill numbers in `text`, written or spoken, whose
+/// digits are names rather than figures
+pub fn bill_ranges(text: &str) -> Vec<(usize, usize)> {
+    bill_numbers(text)
+        .chain(SPOKEN_BILL.captures_iter(text))
+        .map(|caps| {
+            let whole = caps.get(0).unwrap();
+            (whole.start(), whole.end())
+        })
+        .collect()
+}
+
+/// "$1.2 trillion" from the amount, the decimals in group 2 and the scale in group 3
+fn dollar_name(amount: &str, caps: &Captures) -> String {
+    let decimals = caps.get(2).map_or("", |m| m.as_str());
+    let scale = caps.get(3).map(|m| match m.as_str().to_lowercase().as_str() {
+        "k" => "thousand".to_string(),
+        "m" | "mn" => "million".to_string(),
+        "b" | "bn" => "billion".to_string(),
+        "t" | "tn" => "trillion".to_string(),
+        word => word.to_string(),
+    });
+    match scale {
+        Some(scale) => format!("${}{} {}", amount, decimals, scale),
+        None => format!("${}{}", amount, decimals),
+    }
+}
+
+/// 20 for "twentieth", 31 for "thirty-first"
+fn ordinal_day(words: &str) -> Option<u32> {
+    let words = words.to_lowercase();
+    let position = |word: &str| ORDINALS.iter().position(|o| *o == word).map(|p| p as u32 + 1);
+    match words.split([' ', '-']).collect::<Vec<_>>().as_slice() {
+        ["twentieth"] => Some(20),
+        ["thirtieth"] => Some(30),
+        ["twenty", unit] => position(unit).map(|u| 20 + u),
+        ["thirty", unit] => position(unit).map(|u| 30 + u),
+        [word] => position(word),
+        _ => None,
+    }
+}
@@ -0,0 +1,504 @@
+//! Word and character error rates of a transcript against a reference, for
+//! judging whether a bigger model is worth its runtime on our own recordings.
+//!
+//! Both sides are normalized the same way before scoring: lowercased,
+//! punctuation and non-speech cues ("[APPLAUSE]", "(laughter)") dropped, and
+//! optionally digits spelled out, so "$5 million" in Whisper output matches
+//! "five million dollars" in a stenographer's record. Errors are counted on
+//! one cheapest edit path, ties going to substitutions, then deletions.
+
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, Context, Result};
+use regex::{Captures, Regex};
+
+use crate::import::{self, split_speaker_prefix};
+
+/// Hypothesis extensions tried for each reference in batch mode, best first
+const HYPOTHESIS_EXTENSIONS: &[&str] = &["json", "vtt", "srt"];
+/// Reference transcripts in batch mode are `<name>.txt`
+const REFERENCE_EXTENSION: &str = "txt";
+/// Longer digit runs (account numbers, IDs) are read digit by digit
+const MAX_CARDINAL_DIGITS: usize = 15;
+/// Narrowest band of the edit-distance table tried first
+const MIN_BAND: usize = 64;
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+    "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+const SCALES: [&str; 5] = ["", "thousand", "million", "billion", "trillion"];
+
+/// Bracketed cues, and parenthesized notes of up to three words
+static ANNOTATION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[[^\]]*\]|\(\s*[^()\s]+(?:\s+[^()\s]+){0,2}\s*\)").unwrap()
+});
+
+static NUMBER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?P<currency>[$£€])?\b(?P<int>\d{1,3}(?:,\d{3})+|\d+)(?:\.(?P<frac>\d+))?(?P<suffix>st|nd|rd|th|s)?\b(?P<percent>%)?(?:\s+(?P<scale>thousand|million|billion|trillion)\b)?",
+    )
+    .unwrap()
+});
+
+/// What to even out before comparing
+#[derive(Debug, Clone, Copy)]
+pub struct Normalization {
+    pub lowercase: bool,
+    pub strip_punctuation: bool,
+    pub spell_numbers: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorCounts {
+    /// Tokens in the reference
+    pub reference: usize,
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+}
+
+impl ErrorCounts {
+    pub fn errors(&self) -> usize {
+        self.substitutions + self.deletions + self.insertions
+    }
+
+    /// Errors per reference token; above 1 when the hypothesis adds a lot
+    pub fn rate(&self) -> f64 {
+        if self.reference == 0 {
+            if self.errors() == 0 { 0.0 } else { 1.0 }
+        } else {
+            self.errors() as f64 / self.reference as f64
+        }
+    }
+
+    fn add(&mut self, other: &ErrorCounts) {
+        self.reference += other.reference;
+        self.substitutions += other.substitutions;
+        self.deletions += other.deletions;
+        self.insertions += other.insertions;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Score {
+    pub words: ErrorCounts,
+    pub chars: ErrorCounts,
+}
+
+/// A transcript under test, with the run details our own JSON records
+pub struct Hypothesis {
+    pub text: String,
+    pub model: Option<String>,
+    pub processing_time: Option<f64>,
+}
+
+/// One scored hypothesis, a row of the batch table
+pub struct EvalRow {
+    pub name: String,
+    /// Which set of hypotheses it came from, e.g. the output directory of one model
+    pub system: String,
+    pub model: Option<String>,
+    pub processing_time: Option<f64>,
+    pub score: Score,
+}
+
+pub async fn load_hypothesis(path: &Path) -> Result<Hypothesis> {
+    let imported = import::read_transcript(path).await?;
+    let text = imported.segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+    Ok(match imported.transcript {
+        Some(transcript) => Hypothesis {
+            text,
+            model: Some(transcript.model_used),
+            processing_time: Some(transcript.processing_time),
+        },
+        None => Hypothesis { text, model: None, processing_time: None },
+    })
+}
+
+/// Plain text with optional `SPEAKER:` labels, or a caption or JSON transcript
+pub async fn load_reference(path: &Path) -> Result<String> {
+    let extension = path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+    if let Some("srt" | "vtt" | "json") = extension.as_deref() {
+        return Ok(load_hypothesis(path).await?.text);
+    }
+
+    let content = tokio::fs::read_to_string(path).await
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    Ok(content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(|line| split_speaker_prefix(line).1)
+        .collect::<Vec<_>>()
+        .join(" "))
+}
+
+/// `(name, reference, hypothesis per directory)` for every reference that
+/// each hypothesis directory has a transcript for
+pub fn pair_files(reference_dir: &Path, hypothesis_dirs: &[PathBuf]) -> Result<Vec<(String, PathBuf, Vec<PathBuf>)>> {
+    let mut references: Vec<PathBuf> = std::fs::read_dir(reference_dir)
+        .with_context(|| format!("Failed to list {:?}", reference_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|e| e.to_str()).is_some_and(|e| e.eq_ignore_ascii_case(REFERENCE_EXTENSION))
+        })
+        .collect();
+    references.sort();
+
+    let mut pairs = Vec::new();
+    for reference in references {
+        let Some(name) = reference.file_stem().map(|s| s.to_string_lossy().to_string()) else { continue };
+        let hypotheses: Vec<Option<PathBuf>> = hypothesis_dirs
+            .iter()
+            .map(|dir| {
+                HYPOTHESIS_EXTENSIONS
+                    .iter()
+                    .map(|extension| dir.join(format!("{}.{}", name, extension)))
+                    .find(|path| path.is_file())
+            })
+            .collect();
+
+        // Every system is scored on the same files, or the totals don't compare
+        if let Some(missing) = hypothesis_dirs.iter().zip(&hypotheses).find(|(_, h)| h.is_none()) {
+            tracing::warn!("Skipping {}: no {}.json, .vtt or .srt in {:?}", name, name, missing.0);
+            continue;
+        }
+        pairs.push((name, reference, hypotheses.into_iter().flatten().collect()));
+    }
+
+    if pairs.is_empty() {
+        return Err(anyhow!(
+            "No <name>.{} reference in {:?} has a matching hypothesis",
+            REFERENCE_EXTENSION,
+            reference_dir
+        ));
+    }
+    Ok(pairs)
+}
+
+/// Normalized words of a transcript
+pub fn normalize(text: &str, options: Normalization) -> Vec<String> {
+    let mut text = text.to_string();
+    if options.strip_punctuation {
+        text = ANNOTATION.replace_all(&text, " ").into_owned();
+    }
+    if options.spell_numbers {
+        text = NUMBER.replace_all(&text, spell_number).into_owned();
+    }
+    if options.lowercase {
+        text = text.to_lowercase();
+    }
+    if options.strip_punctuation {
+        // "don't" and "dont" compare equal; "health-care" is two words
+        text = text
+            .chars()
+            .filter(|c| !matches!(c, '\'' | '’'))
+            .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+            .collect();
+    }
+    text.split_whitespace().map(str::to_string).collect()
+}
+
+pub fn score(reference: &[String], hypothesis: &[String]) -> Score {
+    let reference_chars: Vec<char> = reference.join(" ").chars().collect();
+    let hypothesis_chars: Vec<char> = hypothesis.join(" ").chars().collect();
+    Score {
+        words: edit_counts(reference, hypothesis),
+        chars: edit_counts(&reference_chars, &hypothesis_chars),
+    }
+}
+
+/// Substitutions, deletions and insertions on a cheapest path from
+/// `reference` to `hypothesis`. A path with at most `band` edits never strays
+/// more than `band` cells off the diagonal, so only that band of the table is
+/// filled, widening it until the result fits; a long recording's CER then
+/// costs seconds rather than a full characters-squared table.
+fn edit_counts<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> ErrorCounts {
+    let longest = reference.len().max(hypothesis.len());
+    let mut band = reference.len().abs_diff(hypothesis.len()).max(MIN_BAND);
+    loop {
+        let counts = banded_edit_counts(reference, hypothesis, band);
+        if counts.errors() <= band || band >= longest {
+            return ErrorCounts { reference: reference.len(), ..counts };
+        }
+        band *= 2;
+    }
+}
+
+fn banded_edit_counts<T: PartialEq>(reference: &[T], hypothesis: &[T], band: usize) -> ErrorCounts {
+    let cost = |c: &ErrorCounts| c.errors();
+    // Cells outside the band; never cheaper than a real path
+    let unreachable = ErrorCounts { deletions: usize::MAX / 4, ..Default::default() };
+    let mut previous: Vec<ErrorCounts> = (0..=hypothesis.len())
+        .map(|j| if j <= band { ErrorCounts { insertions: j, ..Default::default() } } else { unreachable })
+        .collect();
+    let mut current = vec![unreachable; hypothesis.len() + 1];
+
+    for (i, r) in reference.iter().enumerate() {
+        let row = i + 1;
+        let low = row.saturating_sub(band);
+        let high = (row + band).min(hypothesis.len());
+        if low == 0 {
+            current[0] = ErrorCounts { deletions: row, ..Default::default() };
+        } else {
+            current[low - 1] = unreachable;
+        }
+        for j in low.max(1)..=high {
+            let mut best = previous[j - 1];
+            if *r != hypothesis[j - 1] {
+                best.substitutions += 1;
+            }
+            let mut deletion = previous[j];
+            deletion.deletions += 1;
+            let mut insertion = current[j - 1];
+            insertion.insertions += 1;
+            for candidate in [deletion, insertion] {
+                if cost(&candidate) < cost(&best) {
+                    best = candidate;
+                }
+            }
+            current[j] = best;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[hypothesis.len()]
+}
+
+/// Aligned text table of the rows, with a corpus total per system: errors
+/// summed over all files, not an average of per-file rates
+pub fn table(rows: &[EvalRow]) -> String {
+    let mut systems: Vec<&str> = Vec::new();
+    for row in rows {
+        if !systems.contains(&row.system.as_str()) {
+            systems.push(&row.system);
+        }
+    }
+
+    let mut lines: Vec<[String; 10]> = vec![[
+        "File", "System", "Model", "Ref words", "WER", "Sub", "Del", "Ins", "CER", "Time",
+    ]
+    .map(str::to_string)];
+    for row in rows {
+        lines.push(table_line(&row.name, &row.system, row.model.as_deref(), row.processing_time, &row.score));
+    }
+    if rows.len() > systems.len() {
+        for system in &systems {
+            let mine: Vec<&EvalRow> = rows.iter().filter(|r| r.system == *system).collect();
+            let mut total = Score::default();
+            for row in &mine {
+                total.words.add(&row.score.words);
+                total.chars.add(&row.score.chars);
+            }
+            let model = mine[0].model.as_deref().filter(|m| mine.iter().all(|r| r.model.as_deref() == Some(*m)));
+            let time = mine.iter().map(|r| r.processing_time).sum::<Option<f64>>();
+            lines.push(table_line("TOTAL", system, model, time, &total));
+        }
+    }
+
+    let widths: Vec<usize> = (0..10).map(|c| lines.iter().map(|l| l[c].chars().count()).max().unwrap_or(0)).collect();
+    let mut out = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        if index == 1 || (index == rows.len() + 1 && index < lines.len()) {
+            let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            out.push_str(&rule.join("  "));
+            out.push('\n');
+        }
+        let cells: Vec<String> = line
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(c, (cell, width))| {
+                // Text columns left-aligned, figures right-aligned
+                if c < 3 { format!("{:<width$}", cell) } else { format!("{:>width$}", cell) }
+            })
+            .collect();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn table_line(name: &str, system: &str, model: Option<&str>, time: Option<f64>, score: &Score) -> [String; 10] {
+    [
+        name.to_string(),
+        system.to_string(),
+        model.unwrap_or("-").to_string(),
+        score.words.reference.to_string(),
+        format!("{:.1}%", score.words.rate() * 100.0),
+        score.words.substitutions.to_string(),
+        score.words.deletions.to_string(),
+        score.words.insertions.to_string(),
+        format!("{:.1}%", score.chars.rate() * 100.0),
+        time.map(|t| format!("{:.1}s", t)).unwrap_or_else(|| "-".to_string()),
+    ]
+}
+
+/// "$1,200" -> "one thousand two hundred dollars", "3rd" -> "third",
+/// "1990s" -> "nineteen nineties", "2.5%" -> "two point five percent"
+fn spell_number(caps: &Captures) -> String {
+    let int = caps["int"].replace(',', "");
+    let frac = caps.name("frac").map(|m| m.as_str());
+    let suffix = caps.name("suffix").map(|m| m.as_str().to_ascii_lowercase());
+    let scale = caps.name("scale").map(|m| m.as_str().to_ascii_lowercase());
+    let currency = caps.name("currency").map(|m| match m.as_str() {
+        "$" => ("dollar", "cent"),
+        "£" => ("pound", "penny"),
+        _ => ("euro", "cent"),
+    });
+    // "4th" or "1990s" with a fraction isn't a number we can read
+    if frac.is_some() && suffix.is_some() {
+        return caps[0].to_string();
+    }
+
+    let is_year = int.len() == 4
+        && !caps["int"].contains(',')
+        && caps.name("percent").is_none()
+        && matches!(suffix.as_deref(), None | Some("s"));
+    let mut words = match (frac, currency, scale.as_deref()) {
+        // "$5.50" is five dollars fifty cents, but "$1.5 million" is a decimal
+        (Some(cents), Some((unit, subunit)), None) if cents.len() == 2 => {
+            let dollars: u64 = int.parse().unwrap_or(0);
+            let cents: u64 = cents.parse().unwrap_or(0);
+            let mut parts = Vec::new();
+            if dollars > 0 || cents == 0 {
+                parts.push(format!("{} {}", cardinal(&int), plural(unit, dollars)));
+            }
+            if cents > 0 {
+                let subunit = if subunit == "penny" && cents != 1 { "pence".to_string() } else { plural(subunit, cents) };
+                parts.push(format!("{} {}", cardinal(&cents.to_string()), subunit));
+            }
+            return parts.join(" ");
+        }
+        (Some(frac), _, _) => {
+            let digits: Vec<&str> = frac.chars().map(|d| ONES[d.to_digit(10).unwrap_or(0) as usize]).collect();
+            format!("{} point {}", cardinal(&int), digits.join(" "))
+        }
+        (None, None, None) if is_year => year(&int),
+        _ => cardinal(&int),
+    };
+
+    match suffix.as_deref() {
+        Some("s") => words = pluralize_last(&words),
+        Some(_) => words = ordinalize_last(&words),
+        None => {}
+    }
+    if caps.name("percent").is_some() {
+        words.push_str(" percent");
+    }
+    if let Some(scale) = &scale {
+        words.push(' ');
+        words.push_str(scale);
+    }
+    if let Some((unit, _)) = currency {
+        let amount = if frac.is_none() && scale.is_none() { int.parse().unwrap_or(0) } else { 0 };
+        words.push(' ');
+        words.push_str(&plural(unit, amount));
+    }
+    words
+}
+
+/// Digits as a cardinal number, American style ("one hundred five")
+fn cardinal(digits: &str) -> String {
+    // "007" and long IDs are read out digit by digit
+    if digits.len() > MAX_CARDINAL_DIGITS || (digits.len() > 1 && digits.starts_with('0')) {
+        return digit_by_digit(digits);
+    }
+    let Ok(mut value) = digits.parse::<u64>() else {
+        return digit_by_digit(digits);
+    };
+    if value == 0 {
+        return ONES[0].to_string();
+    }
+
+    let mut groups = Vec::new();
+    let mut scale = 0;
+    while value > 0 {
+        let group = (value % 1000) as usize;
+        if group > 0 {
+            let mut words = below_thousand(group);
+            if scale > 0 {
+                words.push(' ');
+                words.push_str(SCALES[scale]);
+            }
+            groups.push(words);
+        }
+        value /= 1000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+fn below_thousand(value: usize) -> String {
+    let mut words = Vec::new();
+    if value >= 100 {
+        words.push(format!("{} hundred", ONES[value / 100]));
+    }
+    let rest = value % 100;
+    if rest >= 20 {
+        words.push(TENS[rest / 10].to_string());
+        if !rest.is_multiple_of(10) {
+            words.push(ONES[rest % 10].to_string());
+        }
+    } else if rest > 0 {
+        words.push(ONES[rest].to_string());
+    }
+    words.join(" ")
+}
+
+/// Four digits read as a year: "1990" -> "nineteen ninety", "1905" ->
+/// "nineteen oh five", "2008" -> "two thousand eight"
+fn year(digits: &str) -> String {
+    let value: usize = digits.parse().unwrap_or(0);
+    if !(1100..2100).contains(&value) || (2000..2010).contains(&value) {
+        return cardinal(digits);
+    }
+    let (century, rest) = (value / 100, value % 100);
+    match rest {
+        0 => format!("{} hundred", below_thousand(century)),
+        1..=9 => format!("{} oh {}", below_thousand(century), ONES[rest]),
+        _ => format!("{} {}", below_thousand(century), below_thousand(rest)),
+    }
+}
+
+fn digit_by_digit(digits: &str) -> String {
+    digits
+        .chars()
+        .filter_map(|d| d.to_digit(10))
+        .map(|d| ONES[d as usize])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn plural(unit: &str, amount: u64) -> String {
+    if amount == 1 { unit.to_string() } else { format!("{}s", unit) }
+}
+
+fn pluralize_last(words: &str) -> String {
+    match words.strip_suffix('y') {
+        Some(stem) => format!("{}ies", stem),
+        None => format!("{}s", words),
+    }
+}
+
+fn ordinalize_last(words: &str) -> String {
+    let (head, last) = words.rsplit_once(' ').map_or(("", words), |(h, l)| (h, l));
+    let ordinal = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ => match last.strip_suffix('y') {
+            Some(stem) => format!("{}ieth", stem),
+            None => format!("{}th", last),
+        },
+    };
+    if head.is_empty() { ordinal } else { format!("{} {}", head, ordinal) }
+}
@@ -0,0 +1,360 @@
+//! Readers for transcripts produced elsewhere (SRT, WebVTT, Whisper JSON) or
+//! by an earlier run of this tool, so they can be analyzed or converted
+//! without touching audio.
+
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{schema, BasicSegment, TranscriptResult, WordTiming};
+
+/// SRT/VTT markup tags (`<i>`, `<font …>`, `<v Name>`, `<c.yellow>`) and VTT
+/// karaoke timestamps (`<00:00:01.000>`); any other `<` is caption text
+static TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)</?(?:[biu]|c|v|lang|ruby|rt|font|span)(?:[.\s][^<>]*)?>|<(?:\d+:)?\d{2}:\d{2}\.\d{3}>").unwrap());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Srt,
+    Vtt,
+    WhisperJson,
+    TranscriptJson,
+}
+
+impl ImportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ImportFormat::Srt => "srt",
+            ImportFormat::Vtt => "vtt",
+            ImportFormat::WhisperJson => "whisper-json",
+            ImportFormat::TranscriptJson => "transcript-json",
+        }
+    }
+}
+
+pub struct ImportedTranscript {
+    pub format: ImportFormat,
+    pub segments: Vec<BasicSegment>,
+    pub language: Option<String>,
+    /// Set when the input was our own JSON, so nothing is lost on conversion
+    pub transcript: Option<TranscriptResult>,
+}
+
+pub async fn read_transcript(path: &Path) -> Result<ImportedTranscript> {
+    let content = tokio::fs::read_to_string(path).await
+        .with_context(|| format!("Failed to read {:?}", path))?;
+    let content = content.trim_start_matches('\u{feff}');
+
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "srt" => parse_srt(content),
+        "vtt" => parse_vtt(content),
+        "json" => parse_json(content),
+        _ => {
+            // Sniff the content when the extension does not tell us
+            let trimmed = content.trim_start();
+            if trimmed.starts_with("WEBVTT") {
+                parse_vtt(content)
+            } else if trimmed.starts_with('{') {
+                parse_json(content)
+            } else {
+                parse_srt(content)
+            }
+        }
+    }
+    .with_context(|| format!("Failed to parse {:?}", path))
+}
+
+fn parse_srt(content: &str) -> Result<ImportedTranscript> {
+    let timing = Regex::new(
+        r"^\s*(\d+:\d{2}:\d{2}[,.]\d{1,3})\s*-->\s*(\d+:\d{2}:\d{2}[,.]\d{1,3})",
+    )?;
+
+    let mut segments = Vec::new();
+    for block in split_blocks(content) {
+        let mut lines = block.iter().copied();
+        let Some(mut line) = lines.next() else { continue };
+
+        // The numeric counter is optional in the wild
+        if !timing.is_match(line) {
+            match lines.next() {
+                Some(next) => line = next,
+                None => continue,
+            }
+        }
+        let Some(captures) = timing.captures(line) else { continue };
+
+        let start = parse_timestamp(&captures[1])?;
+        let end = parse_timestamp(&captures[2])?;
+        let raw_text = lines.collect::<Vec<_>>().join(" ");
+        let (speaker, text) = split_speaker_prefix(&strip_tags(&raw_text));
+
+        push_segment(&mut segments, start, end, text, speaker, 1.0, vec![]);
+    }
+
+    if segments.is_empty() {
+        return Err(anyhow!("No SRT cues found"));
+    }
+
+    Ok(ImportedTranscript {
+        format: ImportFormat::Srt,
+        segments,
+        language: None,
+        transcript: None,
+    })
+}
+
+fn parse_vtt(content: &str) -> Result<ImportedTranscript> {
+    let timing = Regex::new(
+        r"^\s*((?:\d+:)?\d{2}:\d{2}\.\d{1,3})\s*-->\s*((?:\d+:)?\d{2}:\d{2}\.\d{1,3})",
+    )?;
+    let voice = Regex::new(r"<v(?:\.[^ >]+)*\s+([^>]+)>")?;
+
+    let mut segments = Vec::new();
+    let mut language = None;
+
+    for (index, block) in split_blocks(content).into_iter().enumerate() {
+        if index == 0 && block.first().is_some_and(|l| l.starts_with("WEBVTT")) {
+            language = block
+                .iter()
+                .find_map(|l| l.strip_prefix("Language:"))
+                .map(|l| l.trim().to_string());
+            continue;
+        }
+        if block.first().is_some_and(|l| l.starts_with("NOTE") || l.starts_with("STYLE") || l.starts_with("REGION")) {
+            continue;
+        }
+
+        // Skip an optional cue identifier line
+        let Some(position) = block.iter().position(|l| timing.is_match(l)) else { continue };
+        let captures = timing.captures(block[position]).unwrap();
+        let start = parse_timestamp(&captures[1])?;
+        let end = parse_timestamp(&captures[2])?;
+
+        let raw_text = block[position + 1..].join(" ");
+        let tagged_speaker = voice.captures(&raw_text).map(|c| c[1].trim().to_string());
+        let (prefixed_speaker, text) = split_speaker_prefix(&strip_tags(&raw_text));
+
+        push_segment(&mut segments, start, end, text, tagged_speaker.or(prefixed_speaker), 1.0, vec![]);
+    }
+
+    if segments.is_empty() {
+        return Err(anyhow!("No WebVTT cues found"));
+    }
+
+    Ok(ImportedTranscript {
+        format: ImportFormat::Vtt,
+        segments,
+        language,
+        transcript: None,
+    })
+}
+
+#[derive(Deserialize)]
+struct WhisperOutput {
+    #[serde(default)]
+    language: Option<String>,
+    segments: Vec<WhisperSegment>,
+}
+
+#[derive(Deserialize)]
+struct WhisperSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    #[serde(default)]
+    avg_logprob: Option<f64>,
+    /// WhisperX adds diarization labels
+    #[serde(default)]
+    speaker: Option<String>,
+    #[serde(default)]
+    words: Vec<WhisperWord>,
+}
+
+#[derive(Deserialize)]
+struct WhisperWord {
+    word: String,
+    #[serde(default)]
+    start: Option<f64>,
+    #[serde(default)]
+    end: Option<f64>,
+    #[serde(default, alias = "score")]
+    probability: Option<f32>,
+}
+
+fn parse_json(content: &str) -> Result<ImportedTranscript> {
+    let document: Value = serde_json::from_str(content).context("Invalid JSON")?;
+
+    // Our own output always carries statistics next to the segments
+    if document.get("schema_version").is_some() || document.get("statistics").is_some() {
+        let transcript = schema::load_transcript(content)?;
+        let segments = transcript
+            .segments
+            .iter()
+            .map(|s| BasicSegment {
+                id: s.id,
+                start: s.start,
+                end: s.end,
+                text: s.text.clone(),
+                confidence: s.confidence,
+                speaker: s.speaker.clone(),
+                words: s.words.clone(),
+            })
+            .collect();
+
+        return Ok(ImportedTranscript {
+            format: ImportFormat::TranscriptJson,
+            segments,
+            language: Some(transcript.language.clone()),
+            transcript: Some(transcript),
+        });
+    }
+
+    let whisper: WhisperOutput = serde_json::from_value(document)
+        .context("JSON is neither a transcribe-turbo transcript nor Whisper output")?;
+
+    let mut segments = Vec::new();
+    for segment in whisper.segments {
+        // avg_logprob is a mean token log-probability; exp() maps it back to 0..1
+        let confidence = segment
+            .avg_logprob
+            .map(|lp| lp.exp().clamp(0.0, 1.0) as f32)
+            .unwrap_or(1.0);
+
+        let words = segment
+            .words
+            .iter()
+            .filter_map(|w| {
+                Some(WordTiming {
+                    text: w.word.trim().to_string(),
+                    start: w.start?,
+                    end: w.end?,
+                    confidence: w.probability.unwrap_or(confidence),
+                })
+            })
+            .filter(|w| !w.text.is_empty())
+            .collect();
+
+        push_segment(
+            &mut segments,
+            segment.start,
+            segment.end,
+            segment.text.trim().to_string(),
+            segment.speaker,
+            confidence,
+            words,
+        );
+    }
+
+    if segments.is_empty() {
+        return Err(anyhow!("No segments found in Whisper JSON"));
+    }
+
+    Ok(ImportedTranscript {
+        format: ImportFormat::WhisperJson,
+        segments,
+        language: whisper.language,
+        transcript: None,
+    })
+}
+
+fn push_segment(
+    segments: &mut Vec<BasicSegment>,
+    start: f64,
+    end: f64,
+    text: String,
+    speaker: Option<String>,
+    confidence: f32,
+    words: Vec<WordTiming>,
+) {
+    if text.is_empty() {
+        return;
+    }
+    segments.push(BasicSegment {
+        id: segments.len() + 1,
+        start,
+        end,
+        text,
+        confidence,
+        speaker,
+        words,
+    });
+}
+
+fn split_blocks(content: &str) -> Vec<Vec<&str>> {
+    let mut blocks = Vec::new();
+    let mut current = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                blocks.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        blocks.push(current);
+    }
+
+    blocks
+}
+
+/// Accepts `HH:MM:SS,mmm`, `HH:MM:SS.mmm` and `MM:SS.mmm`
+fn parse_timestamp(value: &str) -> Result<f64> {
+    let value = value.trim().replace(',', ".");
+    let parts: Vec<&str> = value.split(':').collect();
+
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (h.parse::<f64>()?, m.parse::<f64>()?, s.parse::<f64>()?),
+        [m, s] => (0.0, m.parse::<f64>()?, s.parse::<f64>()?),
+        _ => return Err(anyhow!("Invalid timestamp: {}", value)),
+    };
+
+    Ok(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+fn strip_tags(text: &str) -> String {
+    let out = TAG
+        .replace_all(text, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&");
+
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Split a leading `SPEAKER NAME:` or `>> Name:` label off caption text
+pub fn split_speaker_prefix(text: &str) -> (Option<String>, String) {
+    let chevrons = text.trim_start().starts_with(">>");
+    let text = text.trim_start().trim_start_matches(">>").trim();
+
+    if let Some((label, rest)) = text.split_once(':') {
+        let label = label.trim();
+        let name_like = !label.is_empty()
+            && label.split_whitespace().count() <= 4
+            && label.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '.' | '-' | '\''));
+        // Without a `>>` marker only all-caps labels are trusted, so
+        // "Note: ..." or "Today: ..." stay part of the text
+        let is_caps = label.chars().any(|c| c.is_alphabetic())
+            && label.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase());
+
+        if name_like && (chevrons || is_caps) && !rest.trim().is_empty() {
+            return (Some(label.to_string()), rest.trim().to_string());
+        }
+    }
+
+    (None, text.to_string())
+}
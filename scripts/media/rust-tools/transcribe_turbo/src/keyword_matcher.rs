@@ -0,0 +1,250 @@
+//! Compiled multi-pattern keyword matcher.
+//!
+//! Every taxonomy term, alias and inflected form is compiled into one
+//! Aho-Corasick automaton that runs over a normalized copy of the segment text
+//! (lowercase, punctuation folded to spaces), so "Health-care," matches
+//! "health care". Matches must start and end on word boundaries, which keeps
+//! "ice" out of "price" and "wall" out of "Wallace", and a phrase may not run
+//! across a clause break: "the minimum, wage earners" is not "minimum wage".
+//! Terms spelled in capitals in the taxonomy ("ICE", "ACA") only match where
+//! the speaker's text is in capitals too, as entity acronyms do.
+
+use std::collections::HashMap;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use anyhow::{Context, Result};
+
+use crate::taxonomy::{KeywordHit, Taxonomy};
+use crate::text::{char_slice, normalize, NormalizedText};
+
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Term {
+        category: usize,
+        term: usize,
+        /// Spelled in capitals ("ICE"), so the mention must be too
+        acronym: bool,
+    },
+    Exclusion,
+}
+
+pub struct KeywordMatcher<'a> {
+    taxonomy: &'a Taxonomy,
+    automaton: AhoCorasick,
+    targets: Vec<Target>,
+}
+
+impl<'a> KeywordMatcher<'a> {
+    pub fn new(taxonomy: &'a Taxonomy) -> Result<Self> {
+        let mut patterns: Vec<String> = Vec::new();
+        let mut targets: Vec<Target> = Vec::new();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+
+        let mut add = |pattern: String, target: Target| {
+            if pattern.is_empty() || seen.contains_key(&pattern) {
+                return;
+            }
+            seen.insert(pattern.clone(), patterns.len());
+            patterns.push(pattern);
+            targets.push(target);
+        };
+
+        // Exclusions go first so they win ties against terms with the same text
+        for exclusion in &taxonomy.exclusions {
+            add(normalize(exclusion), Target::Exclusion);
+        }
+
+        for (category_index, category) in taxonomy.categories.iter().enumerate() {
+            for (term_index, term) in category.terms.iter().enumerate() {
+                for surface in std::iter::once(&term.term).chain(&term.aliases) {
+                    let acronym = term.acronyms.contains(surface);
+                    let target = Target::Term {
+                        category: category_index,
+                        term: term_index,
+                        acronym,
+                    };
+                    let surface = normalize(surface);
+                    if term.inflect && !acronym {
+                        for form in inflections(&surface) {
+                            add(form, target);
+                        }
+                    } else {
+                        add(surface, target);
+                    }
+                }
+            }
+        }
+
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .build(&patterns)
+            .context("Failed to compile keyword matcher")?;
+
+        Ok(Self {
+            taxonomy,
+            automaton,
+            targets,
+        })
+    }
+
+    /// Distinct terms found in `text`, each with the character spans it matched
+    pub fn find(&self, text: &str) -> Vec<KeywordHit> {
+        let normalized = NormalizedText::new(text);
+
+        let mut candidates: Vec<(usize, usize, Target)> = self
+            .automaton
+            .find_overlapping_iter(&normalized.text)
+            .filter(|m| normalized.is_word(m.start(), m.end()))
+            .map(|m| (m.start(), m.end(), self.targets[m.pattern().as_usize()]))
+            .filter(|&(start, end, target)| {
+                let mention = char_slice(text, normalized.span(start, end));
+                let acronym = matches!(target, Target::Term { acronym: true, .. });
+                !crosses_clause(mention) && (!acronym || in_capitals(mention))
+            })
+            .collect();
+
+        // Leftmost-longest among the whole-word matches only, so a rejected
+        // partial word never hides a valid shorter match
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then((b.1 - b.0).cmp(&(a.1 - a.0))));
+
+        let mut hits: Vec<KeywordHit> = Vec::new();
+        let mut covered_until = 0;
+        for (start, end, target) in candidates {
+            if start < covered_until {
+                continue;
+            }
+            covered_until = end;
+
+            let Target::Term { category, term, .. } = target else { continue };
+            let category = &self.taxonomy.categories[category];
+            let term = &category.terms[term];
+            let span = normalized.span(start, end);
+
+            match hits.iter_mut().find(|h| h.term == term.term) {
+                Some(hit) => hit.spans.push(span),
+                None => hits.push(KeywordHit {
+                    term: term.term.clone(),
+                    category: category.name.clone(),
+                    weight: term.weight,
+                    spans: vec![span],
+                }),
+            }
+        }
+
+        hits.sort_by(|a, b| a.term.cmp(&b.term));
+        hits
+    }
+}
+
+/// True when a clause or sentence break falls inside the mention; the dots of
+/// "E.P.A." and hyphens of "minimum-wage" don't count
+fn crosses_clause(mention: &str) -> bool {
+    let chars: Vec<char> = mention.chars().collect();
+    chars.iter().enumerate().any(|(i, &c)| match c {
+        ',' | ';' | ':' | '!' | '?' | '—' | '–' => true,
+        '.' => chars.get(i + 1).is_some_and(|next| !next.is_alphanumeric()),
+        _ => false,
+    })
+}
+
+fn in_capitals(mention: &str) -> bool {
+    mention.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase())
+}
+
+/// The phrase plus simple English inflections of its last word
+/// ("tax" -> "taxes", "taxed", "taxing")
+pub fn inflections(phrase: &str) -> Vec<String> {
+    let mut forms = vec![phrase.to_string()];
+
+    let (head, word) = match phrase.rsplit_once(' ') {
+        Some((head, word)) => (format!("{} ", head), word),
+        None => (String::new(), phrase),
+    };
+
+    // Leave acronyms-with-digits, very short words and words that already
+    // look inflected alone
+    if word.len() < 3 || !word.chars().all(|c| c.is_ascii_lowercase()) || word.ends_with('s') {
+        return forms;
+    }
+
+    let stem_y = word.strip_suffix('y').filter(|s| !s.ends_with(['a', 'e', 'i', 'o', 'u']));
+    let stem_e = word.strip_suffix('e');
+
+    let mut push = |form: String| forms.push(format!("{}{}", head, form));
+
+    if let Some(stem) = stem_y {
+        push(format!("{}ies", stem));
+        push(format!("{}ied", stem));
+        push(format!("{}ying", stem));
+    } else if word.ends_with(['x', 'z']) || word.ends_with("ch") || word.ends_with("sh") {
+        push(format!("{}es", word));
+        push(format!("{}ed", word));
+        push(format!("{}ing", word));
+    } else if let Some(stem) = stem_e {
+        push(format!("{}s", word));
+        push(format!("{}d", word));
+        push(format!("{}ing", stem));
+    } else {
+        push(format!("{}s", word));
+        push(format!("{}ed", word));
+        push(format!("{}ing", word));
+    }
+
+    forms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sentences that used to trip the substring matcher, plus inflection and
+    /// punctuation cases that must keep matching
+    const REGRESSION_CORPUS: &str = include_str!("../data/keyword_regression.tsv");
+
+    #[test]
+    fn regression_corpus() {
+        let taxonomy = Taxonomy::default();
+        let matcher = KeywordMatcher::new(&taxonomy).unwrap();
+        let mut failures = Vec::new();
+
+        for line in REGRESSION_CORPUS.lines() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, '\t');
+            let (expect, term, sentence) = (
+                fields.next().unwrap(),
+                fields.next().unwrap(),
+                fields.next().unwrap(),
+            );
+
+            let found = matcher.find(sentence).iter().any(|h| h.term == term);
+            let wanted = expect == "+";
+            if found != wanted {
+                failures.push(format!(
+                    "{} {:?} in {:?}",
+                    if wanted { "missed" } else { "false positive" },
+                    term,
+                    sentence
+                ));
+            }
+        }
+
+        assert!(failures.is_empty(), "keyword regressions:\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn hits_carry_character_offsets() {
+        let taxonomy = Taxonomy::default();
+        let matcher = KeywordMatcher::new(&taxonomy).unwrap();
+        let text = "Café owners said: lower taxes, and protect the Affordable-Care Act!";
+
+        let hits = matcher.find(text);
+        let tax = hits.iter().find(|h| h.term == "tax").unwrap();
+        let aca = hits.iter().find(|h| h.term == "affordable care act").unwrap();
+
+        assert_eq!(crate::text::char_slice(text, tax.spans[0]), "taxes");
+        assert_eq!(crate::text::char_slice(text, aca.spans[0]), "Affordable-Care Act");
+        assert_eq!(aca.category, "healthcare");
+    }
+}
@@ -1,89 +1,321 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use clap::builder::{PossibleValuesParser, TypedValueParser};
 use anyhow::{Result, Context};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::collections::{BTreeMap, HashMap};
 use rayon::prelude::*;
 use tokio::fs;
 use chrono::{DateTime, Utc};
-use regex::Regex;
-use tracing::{info, warn, error};
+use tracing::{info, error, warn};
+
+use audio::AudioSignal;
+use chapters::Chapter;
+use claims::Claim;
+use corrections::{Correction, CorrectionRule, Corrector};
+use disfluency::{Disfluency, SpeakerDisfluency};
+use emphasis::AcousticFeatures;
+use entities::{EntityExtractor, EntityIndexEntry, EntityMention};
+use keyword_matcher::KeywordMatcher;
+use profanity::{Profanity, ProfanityLexicon, Severity};
+use quotes::{QuoteContext, QuoteScores};
+use reactions::AudienceReaction;
+use redaction::{AudioRedaction, Redaction, Redactor};
+use script::{Delivery, PreparedScript, ScriptComparison};
+use sentences::Sentence;
+use sentiment::{Sentiment, SentimentAnalyzer};
+use stance::{LexiconStanceClassifier, Stance, StanceClassifier};
+use summary::{Summary, TalkingPointGroup};
+use taxonomy::{KeywordHit, Taxonomy};
+use turns::SpeakerStats;
+
+mod alignment;
+mod audio;
+mod chapters;
+mod claims;
+mod corrections;
+mod disfluency;
+mod emphasis;
+mod entities;
+mod eval;
+mod import;
+mod keyword_matcher;
+mod profanity;
+mod quotes;
+mod reactions;
+mod redaction;
+mod schema;
+mod script;
+mod sentences;
+mod sentiment;
+mod stance;
+mod summary;
+mod taxonomy;
+mod text;
+mod turns;
+mod writers;
 
 #[derive(Parser)]
 #[command(name = "transcribe-turbo")]
 #[command(about = "Lightning-fast political speech transcription with AI")]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    
     /// Input audio/video file
-    input: PathBuf,
+    input: Option<PathBuf>,
     
     /// Output directory
-    #[arg(short, long, default_value = "./transcripts")]
+    #[arg(short, long, global = true, default_value = "./transcripts")]
     output: PathBuf,
     
     /// Whisper model size
-    #[arg(short, long, default_value = "base")]
+    #[arg(short, long, global = true, default_value = "base")]
     model: String,
     
     /// Enable political keyword detection
-    #[arg(long)]
+    #[arg(long, global = true)]
     political_mode: bool,
     
     /// Enable speaker detection/diarization
-    #[arg(long)]
+    #[arg(long, global = true)]
     speaker_detection: bool,
     
     /// Confidence threshold (0.0-1.0)
-    #[arg(long, default_value = "0.8")]
+    #[arg(long, global = true, default_value = "0.8")]
     confidence: f32,
     
-    /// Output format: srt, vtt, txt, json, all
-    #[arg(short, long, default_value = "srt")]
+    /// Output formats, comma-separated: srt, vtt, sbv, lrc, audacity, chapters, claims, compliance, script, txt, md, json, jsonl, html, csv, tsv, parquet, or all
+    #[arg(short, long, global = true, default_value = "srt")]
     format: String,
     
     /// Enable word-level timestamps
-    #[arg(long)]
+    #[arg(long, global = true)]
     word_timestamps: bool,
     
     /// Language code (auto-detect if not specified)
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     language: Option<String>,
     
-    /// Custom keywords file
-    #[arg(long)]
-    keywords: Option<PathBuf>,
+    /// Keyword taxonomy file (TOML/YAML, or one term per line); repeat to layer campaign overrides
+    #[arg(long, global = true)]
+    keywords: Vec<PathBuf>,
+    
+    /// Sentiment lexicon file (word<TAB>valence); repeat to layer overrides on the built-in lexicon
+    #[arg(long, global = true)]
+    sentiment_lexicon: Vec<PathBuf>,
+    
+    /// Bullets in the summary of the MD/HTML reports (3, 5 or 10; the JSON always keeps ten)
+    #[arg(long, global = true, default_value = "5", value_parser = PossibleValuesParser::new(["3", "5", "10"]).map(|n| n.parse::<usize>().unwrap()))]
+    summary_bullets: usize,
+    
+    /// Stance cue lexicon (TOML, see data/stance_lexicon.toml); repeat to layer overrides on the built-in cues
+    #[arg(long, global = true)]
+    stance_lexicon: Vec<PathBuf>,
+    
+    /// Named-entity gazetteer (TOML, see data/gazetteer.toml); repeat to layer campaign people, organizations and places
+    #[arg(long, global = true)]
+    gazetteer: Vec<PathBuf>,
+    
+    /// Drop fillers, repeated words and restarts from SRT/VTT/SBV/LRC captions and TXT/MD transcripts (JSON stays verbatim)
+    #[arg(long, global = true)]
+    clean_read: bool,
+    
+    /// Corrections file (TOML: literal and regex replacements, canonical names that near misses snap to); repeat to layer
+    #[arg(long, global = true)]
+    corrections: Vec<PathBuf>,
+    
+    /// Replace emails, phone numbers, street addresses and SSN-like numbers with [REDACTED] in every output, and write <name>.redactions.json
+    #[arg(long, global = true)]
+    redact: bool,
+    
+    /// Names to redact as well (one per line, any case); repeat to combine lists
+    #[arg(long, global = true, requires = "redact")]
+    redact_names: Vec<PathBuf>,
+    
+    /// Also write <name>.redacted.<ext>, a copy of the media with the redacted words bleeped or silenced
+    #[arg(long, global = true, value_enum, requires = "redact")]
+    redact_audio: Option<AudioRedaction>,
+    
+    /// Prepared remarks (text or Markdown) to compare the delivery against; adds the script diff to the outputs
+    #[arg(long, global = true)]
+    script: Option<PathBuf>,
+    
+    /// Profanity lexicon (TOML, see data/profanity.toml); repeat to layer overrides on the built-in words
+    #[arg(long, global = true)]
+    profanity_lexicon: Vec<PathBuf>,
+    
+    /// Mask flagged words in SRT/VTT/SBV/LRC captions ("f***") and write a compliance report
+    #[arg(long, global = true)]
+    broadcast_safe: bool,
+    
+    /// Lowest severity --broadcast-safe masks; milder flags are still listed in the report
+    #[arg(long, global = true, value_enum, default_value = "strong")]
+    mask_severity: Severity,
+    
+    /// Insert [APPLAUSE]-style cues for detected audience reactions into SRT/VTT/SBV captions
+    #[arg(long, global = true)]
+    reaction_cues: bool,
+    
+    /// Audio or video to measure acoustic emphasis and audience reactions from (defaults to the input; needed for `analyze`)
+    #[arg(long, global = true)]
+    audio: Option<PathBuf>,
     
     /// Number of parallel processing threads
-    #[arg(long, default_value = "0")]
+    #[arg(long, global = true, default_value = "0")]
     threads: usize,
     
     /// Beam size for search
-    #[arg(long, default_value = "5")]
+    #[arg(long, global = true, default_value = "5")]
     beam_size: usize,
     
     /// Enable noise reduction
-    #[arg(long)]
+    #[arg(long, global = true)]
     noise_reduction: bool,
     
     /// Enhance speech for political content
-    #[arg(long)]
+    #[arg(long, global = true)]
     speech_enhancement: bool,
+    
+    /// Media path or URL the HTML report plays back (defaults to the recording, or its redacted copy with --redact-audio, relative to the output directory)
+    #[arg(long, global = true)]
+    media_src: Option<String>,
+    
+    /// Insert a [HH:MM:SS] marker into TXT/MD transcripts every N seconds
+    #[arg(long, global = true)]
+    timestamp_interval: Option<f64>,
+    
+    /// Event title for the transcript header
+    #[arg(long, global = true)]
+    event_title: Option<String>,
+    
+    /// Event location for the transcript header
+    #[arg(long, global = true)]
+    event_location: Option<String>,
+    
+    /// Event date for the transcript header
+    #[arg(long, global = true)]
+    event_date: Option<String>,
+    
+    /// Also write a one-row-per-word table alongside CSV/TSV/Parquet output
+    #[arg(long, global = true)]
+    word_rows: bool,
+}
+
+impl Cli {
+    /// The recording behind the transcript: `--audio`, the media being
+    /// aligned, or the input when it was transcribed here
+    fn media(&self) -> Option<&Path> {
+        self.audio.as_deref().or(match &self.command {
+            Some(Command::RoughAlign { media, .. }) => Some(media.as_path()),
+            Some(_) => None,
+            None => self.input.as_deref(),
+        })
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the JSON Schema for the .json output, or check files against it
+    Schema {
+        /// Validate an existing transcript JSON file instead of printing the schema
+        #[arg(long, conflicts_with = "migrate")]
+        validate: Option<PathBuf>,
+        
+        /// Upgrade a transcript JSON file from an older schema version and print it
+        #[arg(long)]
+        migrate: Option<PathBuf>,
+    },
+    
+    /// Run political analysis on an existing SRT, VTT, Whisper JSON or transcript JSON file
+    Analyze {
+        /// Transcript file to analyze
+        input: PathBuf,
+    },
+    
+    /// Convert an existing transcript into other formats (e.g. `convert in.vtt --format srt`)
+    Convert {
+        /// Transcript file to convert
+        input: PathBuf,
+    },
+    
+    /// Estimate timing for a known transcript (stenographer or official record) from the pauses in its audio, keeping its exact wording (a heuristic, not forced alignment)
+    RoughAlign {
+        /// Audio or video the text was spoken in
+        media: PathBuf,
+        
+        /// The transcript, as plain text; `SPEAKER:` labels start a new speaker
+        text: PathBuf,
+    },
+    
+    /// Score transcripts against a reference: WER, CER and substitutions, deletions and insertions
+    Eval {
+        /// Transcript to score (SRT, VTT, Whisper JSON or transcript JSON), or with a reference directory a directory of them; repeat to compare models
+        #[arg(long, required = true)]
+        hyp: Vec<PathBuf>,
+        
+        /// Reference transcript (plain text, `SPEAKER:` labels ignored), or a directory of <name>.txt references
+        #[arg(long = "ref")]
+        reference: PathBuf,
+        
+        /// Compare case as well
+        #[arg(long)]
+        keep_case: bool,
+        
+        /// Compare punctuation and bracketed cues such as [APPLAUSE] as well
+        #[arg(long)]
+        keep_punctuation: bool,
+        
+        /// Spell out numbers on both sides ("$5 million" -> "five million dollars") so digits match words
+        #[arg(long)]
+        numbers_to_words: bool,
+    },
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 struct TranscriptSegment {
     id: usize,
     start: f64,
     end: f64,
     text: String,
     confidence: f32,
+    #[serde(default)]
     speaker: Option<String>,
-    political_keywords: Vec<String>,
-    sentiment: Option<String>,
+    #[serde(default)]
+    political_keywords: Vec<KeywordHit>,
+    #[serde(default)]
+    sentiment: Option<Sentiment>,
+    /// 0-1, from loudness, pitch and rate against the speaker's baseline when audio is available
+    #[serde(default)]
     emphasis_level: Option<f32>,
+    #[serde(default)]
+    acoustics: Option<AcousticFeatures>,
+    #[serde(default)]
+    words: Vec<WordTiming>,
+    /// People, organizations, places, bills, dates and dollar amounts in `text`
+    #[serde(default)]
+    entities: Vec<EntityMention>,
+    /// Fillers, repeated words and restarts in `text`
+    #[serde(default)]
+    disfluencies: Vec<Disfluency>,
+    /// Profanity and slurs in `text`
+    #[serde(default)]
+    profanity: Vec<Profanity>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+struct WordTiming {
+    text: String,
+    start: f64,
+    end: f64,
+    confidence: f32,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 struct TranscriptResult {
+    /// Version of this JSON contract, see `schema.rs`
+    schema_version: u32,
     filename: String,
     duration: f64,
     language: String,
@@ -92,10 +324,32 @@ struct TranscriptResult {
     timestamp: DateTime<Utc>,
     segments: Vec<TranscriptSegment>,
     statistics: TranscriptStats,
+    #[serde(default)]
     political_analysis: Option<PoliticalAnalysis>,
+    /// Applause, cheering, laughter, booing and music detected in the audio
+    #[serde(default)]
+    audience_reactions: Vec<AudienceReaction>,
+    /// Topic chapters covering the whole recording
+    #[serde(default)]
+    chapters: Vec<Chapter>,
+    /// Every named entity mentioned, most mentioned first
+    #[serde(default)]
+    entities: Vec<EntityIndexEntry>,
+    /// Sentences with figures, comparisons or cited findings to fact-check
+    #[serde(default)]
+    claims: Vec<Claim>,
+    /// The delivery compared with the prepared remarks given with `--script`
+    #[serde(default)]
+    script: Option<ScriptComparison>,
+    /// Personal information replaced with [REDACTED]: kinds and times only
+    #[serde(default)]
+    redactions: Vec<Redaction>,
+    /// Spellings fixed with `--corrections`, for auditing
+    #[serde(default)]
+    corrections: Vec<Correction>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 struct TranscriptStats {
     total_segments: usize,
     total_words: usize,
@@ -103,106 +357,118 @@ struct TranscriptStats {
     speech_duration: f64,
     silence_duration: f64,
     speakers_detected: usize,
+    /// Talk time, speaking rate and turn-taking per speaker
+    #[serde(default)]
+    speakers: Vec<SpeakerStats>,
+    /// Fillers, repeats and restarts per speaker
+    #[serde(default)]
+    disfluency: Vec<SpeakerDisfluency>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 struct PoliticalAnalysis {
     key_themes: Vec<String>,
-    talking_points: Vec<String>,
+    /// Keyword mentions rolled up by taxonomy category
+    #[serde(default)]
+    issue_areas: Vec<IssueArea>,
+    /// Extractive summary: up to ten ranked bullets and a one-paragraph abstract
+    #[serde(default)]
+    summary: Summary,
+    /// The strongest sentences on each issue area, linked to their timestamps
+    talking_points: Vec<TalkingPointGroup>,
     quotable_moments: Vec<QuotableMoment>,
-    sentiment_distribution: HashMap<String, f32>,
+    sentiment_distribution: SentimentDistribution,
     policy_mentions: Vec<PolicyMention>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+struct SentimentDistribution {
+    /// Fraction of segments per label (positive / negative / neutral)
+    shares: HashMap<String, f32>,
+    /// Mean compound score over all segments
+    mean_compound: f32,
+    /// Mean compound score weighted by segment duration
+    duration_weighted_mean: f32,
+    /// Mean compound score per speaker
+    speaker_means: BTreeMap<String, f32>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+struct IssueArea {
+    category: String,
+    label: String,
+    mentions: usize,
+    weighted_score: f32,
+    /// Distinct terms seen, most frequent first
+    terms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 struct QuotableMoment {
     start: f64,
     end: f64,
+    /// One to three whole sentences, or a long sentence cut at a clause break
     text: String,
+    #[serde(default)]
+    speaker: Option<String>,
+    /// Overall quote score, 0-1; moments are listed best first
     viral_potential: f32,
-    context: String,
+    #[serde(default)]
+    scores: QuoteScores,
+    /// Rhetorical devices found: tricolon, contrast, repetition
+    #[serde(default)]
+    devices: Vec<String>,
+    context: QuoteContext,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
 struct PolicyMention {
+    /// Issue area label from the taxonomy
     policy: String,
-    stance: String,
+    #[serde(default)]
+    category: String,
+    /// Taxonomy terms the sentence mentions
+    #[serde(default)]
+    terms: Vec<String>,
+    stance: Stance,
     confidence: f32,
+    /// Start of the sentence the stance was read from
     timestamp: f64,
-}
-
-struct PoliticalKeywords {
-    economy: Vec<&'static str>,
-    healthcare: Vec<&'static str>,
-    education: Vec<&'static str>,
-    environment: Vec<&'static str>,
-    immigration: Vec<&'static str>,
-    foreign_policy: Vec<&'static str>,
-    social_issues: Vec<&'static str>,
-    general: Vec<&'static str>,
-}
-
-impl Default for PoliticalKeywords {
-    fn default() -> Self {
-        Self {
-            economy: vec![
-                "economy", "jobs", "employment", "unemployment", "inflation", "recession",
-                "growth", "gdp", "budget", "deficit", "debt", "tax", "taxes", "spending",
-                "investment", "business", "trade", "tariff", "minimum wage", "income",
-                "poverty", "wealth", "inequality", "stimulus", "bailout", "economic"
-            ],
-            healthcare: vec![
-                "healthcare", "health care", "medicine", "hospital", "insurance", "medicare",
-                "medicaid", "affordable care act", "obamacare", "prescription", "drugs",
-                "medical", "doctor", "nurse", "pandemic", "covid", "vaccine", "public health",
-                "mental health", "addiction", "opioid", "pharmaceutical", "coverage"
-            ],
-            education: vec![
-                "education", "school", "schools", "university", "college", "student", "students",
-                "teacher", "teachers", "learning", "curriculum", "funding", "budget",
-                "graduation", "literacy", "achievement", "standardized testing", "charter",
-                "public education", "higher education", "student loan", "debt", "tuition"
-            ],
-            environment: vec![
-                "environment", "climate", "global warming", "carbon", "emissions", "pollution",
-                "clean energy", "renewable", "solar", "wind", "nuclear", "fossil fuel",
-                "oil", "gas", "coal", "green", "sustainability", "conservation", "EPA",
-                "paris agreement", "greenhouse gas", "environmental"
-            ],
-            immigration: vec![
-                "immigration", "immigrant", "immigrants", "border", "deportation", "asylum",
-                "refugee", "daca", "dreamers", "citizenship", "naturalization", "visa",
-                "legal immigration", "illegal immigration", "sanctuary", "wall", "barrier",
-                "ice", "customs", "border patrol", "comprehensive reform"
-            ],
-            foreign_policy: vec![
-                "foreign policy", "international", "diplomacy", "war", "peace", "military",
-                "defense", "nato", "alliance", "treaty", "sanctions", "trade war",
-                "china", "russia", "iran", "israel", "palestine", "afghanistan", "iraq",
-                "syria", "north korea", "terrorism", "security", "intelligence"
-            ],
-            social_issues: vec![
-                "abortion", "reproductive rights", "gun control", "second amendment", "firearms",
-                "marriage equality", "lgbtq", "transgender", "discrimination", "civil rights",
-                "racism", "police", "criminal justice", "prison", "reform", "voting rights",
-                "gerrymandering", "supreme court", "constitution", "amendment"
-            ],
-            general: vec![
-                "america", "american", "democracy", "freedom", "liberty", "justice", "equality",
-                "opportunity", "progress", "change", "reform", "conservative", "liberal",
-                "bipartisan", "compromise", "leadership", "values", "future", "generation",
-                "community", "family", "working families", "middle class", "seniors"
-            ]
-        }
-    }
+    #[serde(default)]
+    end: f64,
+    #[serde(default)]
+    speaker: Option<String>,
+    /// The sentence itself
+    #[serde(default)]
+    evidence: String,
+    /// Phrases that decided the stance, as spoken ("won't cut", "repeal")
+    #[serde(default)]
+    cues: Vec<String>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::init();
+    tracing_subscriber::fmt::init();
     
     let cli = Cli::parse();
     
+    if let Some(command) = &cli.command {
+        return match command {
+            Command::Schema { validate, migrate } => run_schema(validate.as_ref(), migrate.as_ref()).await,
+            Command::Analyze { input } => run_import(&cli, input, true).await,
+            Command::Convert { input } => run_import(&cli, input, false).await,
+            Command::RoughAlign { media, text } => run_rough_align(&cli, media, text).await,
+            Command::Eval { hyp, reference, keep_case, keep_punctuation, numbers_to_words } => {
+                let normalization = eval::Normalization {
+                    lowercase: !keep_case,
+                    strip_punctuation: !keep_punctuation,
+                    spell_numbers: *numbers_to_words,
+                };
+                run_eval(hyp, reference, normalization).await
+            }
+        };
+    }
+    
     // Set up thread pool
     if cli.threads > 0 {
         rayon::ThreadPoolBuilder::new()
@@ -211,24 +477,31 @@ async fn main() -> Result<()> {
             .context("Failed to initialize thread pool")?;
     }
     
-    info!("Starting transcription of: {:?}", cli.input);
+    let Some(input) = cli.input.clone() else {
+        return Err(anyhow::anyhow!("No input file given (see --help)"));
+    };
+    
+    info!("Starting transcription of: {:?}", input);
     let start_time = std::time::Instant::now();
     
     // Validate input file
-    if !cli.input.exists() {
-        error!("Input file does not exist: {:?}", cli.input);
+    if !input.exists() {
+        error!("Input file does not exist: {:?}", input);
         return Err(anyhow::anyhow!("Input file not found"));
     }
     
+    // Fail on a bad --format before spending time on transcription
+    writers::resolve_formats(&cli.format)?;
+    
     // Create output directory
     fs::create_dir_all(&cli.output).await
         .context("Failed to create output directory")?;
     
-    // Load political keywords
-    let keywords = load_keywords(&cli).await?;
+    // Load political keywords and the sentiment and stance lexicons
+    let analyzers = load_analyzers(&cli).await?;
     
     // Process audio/video file
-    let transcript = process_file(&cli, &keywords).await?;
+    let transcript = process_file(&cli, &input, &analyzers).await?;
     
     let processing_time = start_time.elapsed().as_secs_f64();
     info!("Transcription completed in {:.2}s", processing_time);
@@ -242,43 +515,357 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn load_keywords(cli: &Cli) -> Result<PoliticalKeywords> {
-    let mut keywords = PoliticalKeywords::default();
+async fn run_import(cli: &Cli, input: &PathBuf, analyze: bool) -> Result<()> {
+    let start_time = std::time::Instant::now();
     
-    if let Some(keywords_file) = &cli.keywords {
-        if keywords_file.exists() {
-            let content = fs::read_to_string(keywords_file).await
-                .context("Failed to read keywords file")?;
-            
-            // Parse custom keywords file (simple line-based format)
-            for line in content.lines() {
-                let line = line.trim();
-                if !line.is_empty() && !line.starts_with('#') {
-                    keywords.general.push(Box::leak(line.to_string().into_boxed_str()));
+    writers::resolve_formats(&cli.format)?;
+    fs::create_dir_all(&cli.output).await
+        .context("Failed to create output directory")?;
+    
+    let imported = import::read_transcript(input).await?;
+    info!("Imported {} segments from {:?} ({})", imported.segments.len(), input, imported.format.name());
+    
+    let transcript = match imported.transcript {
+        // Our own JSON converts losslessly, unless it has to be corrected or redacted
+        Some(mut transcript) if !analyze && !cli.redact && cli.corrections.is_empty() => {
+            // JSON from before the profanity flags still gets masked
+            if cli.broadcast_safe {
+                let lexicon = ProfanityLexicon::load(&cli.profanity_lexicon).await?;
+                let lexicon = lexicon.for_language(&transcript.language);
+                for segment in &mut transcript.segments {
+                    segment.profanity = lexicon.find(&segment.text);
                 }
             }
+            if let Some(path) = &cli.script {
+                let prepared = PreparedScript::load(path).await?;
+                transcript.script = Some(script::compare(&prepared, &transcript.segments)?);
+            }
+            transcript
+        }
+        previous => {
+            // Redacting an analyzed transcript analyzes the redacted text again
+            let reanalyze = analyze || previous.is_some_and(|t| t.political_analysis.is_some());
+            let analyzers = load_analyzers(cli).await?;
+            let source = SourceInfo {
+                filename: input.file_name().unwrap().to_string_lossy().to_string(),
+                language: cli.language.clone()
+                    .or(imported.language)
+                    .unwrap_or_else(|| "auto".to_string()),
+                model_used: format!("imported:{}", imported.format.name()),
+                audio: if reanalyze || cli.reaction_cues {
+                    load_audio(cli, None).await?
+                } else {
+                    None
+                },
+            };
+            let transcript = build_transcript(&source, imported.segments, &analyzers, reanalyze).await?;
+            save_redacted_audio(cli, &transcript).await?;
+            transcript
+        }
+    };
+    
+    let processing_time = start_time.elapsed().as_secs_f64();
+    save_transcript(cli, &transcript, processing_time).await?;
+    
+    if analyze {
+        print_summary(&transcript, processing_time);
+    }
+    
+    Ok(())
+}
+
+async fn run_rough_align(cli: &Cli, media: &PathBuf, text: &PathBuf) -> Result<()> {
+    let start_time = std::time::Instant::now();
+    
+    writers::resolve_formats(&cli.format)?;
+    fs::create_dir_all(&cli.output).await
+        .context("Failed to create output directory")?;
+    
+    let known = alignment::load_text(text).await?;
+    let audio = AudioSignal::load(media).await
+        .with_context(|| format!("Failed to load audio {:?}", media))?;
+    let segments = alignment::align(&audio, &known)?;
+    info!("Roughly aligned {} segments of {:?} to {:?}", segments.len(), text, media);
+    
+    let analyzers = load_analyzers(cli).await?;
+    let source = SourceInfo {
+        filename: media.file_name().unwrap().to_string_lossy().to_string(),
+        language: cli.language.clone().unwrap_or_else(|| "auto".to_string()),
+        model_used: "rough-alignment".to_string(),
+        audio: (cli.political_mode || cli.reaction_cues).then_some(audio),
+    };
+    let transcript = build_transcript(&source, segments, &analyzers, cli.political_mode).await?;
+    save_redacted_audio(cli, &transcript).await?;
+    
+    let processing_time = start_time.elapsed().as_secs_f64();
+    save_transcript(cli, &transcript, processing_time).await?;
+    
+    print_summary(&transcript, processing_time);
+    print_doubtful_words(&transcript);
+    
+    Ok(())
+}
+
+async fn run_eval(hypotheses: &[PathBuf], reference: &Path, normalization: eval::Normalization) -> Result<()> {
+    // A reference directory pairs <name>.txt with <name>.json/.vtt/.srt in each hypothesis directory
+    let pairs = if reference.is_dir() {
+        if let Some(file) = hypotheses.iter().find(|h| !h.is_dir()) {
+            return Err(anyhow::anyhow!("{:?} is not a directory; with a reference directory every --hyp must be one", file));
+        }
+        eval::pair_files(reference, hypotheses)?
+    } else {
+        let name = reference.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        vec![(name, reference.to_path_buf(), hypotheses.to_vec())]
+    };
+    
+    let mut jobs = Vec::new();
+    for (name, reference_path, hypothesis_paths) in pairs {
+        let reference_words = eval::normalize(&eval::load_reference(&reference_path).await?, normalization);
+        for (path, dir) in hypothesis_paths.iter().zip(hypotheses) {
+            let hypothesis = eval::load_hypothesis(path).await?;
+            // Systems are told apart by directory in batch mode, by file otherwise
+            let system = if reference.is_dir() { dir } else { path };
+            let system = system.file_name().unwrap_or(system.as_os_str()).to_string_lossy().to_string();
+            jobs.push((name.clone(), system, reference_words.clone(), hypothesis));
+        }
+    }
+    
+    let rows: Vec<eval::EvalRow> = jobs
+        .into_par_iter()
+        .map(|(name, system, reference_words, hypothesis)| eval::EvalRow {
+            score: eval::score(&reference_words, &eval::normalize(&hypothesis.text, normalization)),
+            name,
+            system,
+            model: hypothesis.model,
+            processing_time: hypothesis.processing_time,
+        })
+        .collect();
+    
+    if let [row] = rows.as_slice() {
+        let (words, chars) = (&row.score.words, &row.score.chars);
+        println!("\n📏 {} vs {}", hypotheses[0].display(), reference.display());
+        println!("📝 Reference: {} words", words.reference);
+        println!(
+            "🎯 WER: {:.1}% ({} substitutions, {} deletions, {} insertions)",
+            words.rate() * 100.0, words.substitutions, words.deletions, words.insertions
+        );
+        println!(
+            "🔤 CER: {:.1}% ({} substitutions, {} deletions, {} insertions over {} characters)",
+            chars.rate() * 100.0, chars.substitutions, chars.deletions, chars.insertions, chars.reference
+        );
+        if let Some(time) = row.processing_time {
+            println!("🚀 Processing Time: {:.2}s ({})", time, row.model.as_deref().unwrap_or("unknown model"));
+        }
+    } else {
+        print!("{}", eval::table(&rows));
+    }
+    
+    Ok(())
+}
+
+/// Runs of words the alignment is unsure of, where the text and the audio
+/// probably disagree
+fn print_doubtful_words(transcript: &TranscriptResult) {
+    let mut runs: Vec<(f64, f64, Vec<&str>)> = Vec::new();
+    let mut previous_doubtful = false;
+    for word in transcript.segments.iter().flat_map(|s| &s.words) {
+        let doubtful = word.confidence < alignment::LOW_CONFIDENCE;
+        match runs.last_mut() {
+            Some(run) if doubtful && previous_doubtful => {
+                run.1 = word.end;
+                run.2.push(&word.text);
+            }
+            _ if doubtful => runs.push((word.start, word.end, vec![&word.text])),
+            _ => {}
         }
+        previous_doubtful = doubtful;
+    }
+    if runs.is_empty() {
+        println!("🎯 Alignment: every word fits the audio");
+        return;
     }
     
-    Ok(keywords)
+    let count: usize = runs.iter().map(|r| r.2.len()).sum();
+    println!("⚠️  Alignment: {} words below {:.0}% confidence; check the text against the audio at:", count, alignment::LOW_CONFIDENCE * 100.0);
+    for (start, end, words) in runs.iter().take(10) {
+        let mut text = words.iter().take(8).copied().collect::<Vec<_>>().join(" ");
+        if words.len() > 8 {
+            text.push_str(" ...");
+        }
+        println!("   {}-{}: {}", writers::format_clock(*start), writers::format_clock(*end), text);
+    }
+    if runs.len() > 10 {
+        println!("   ... and {} more", runs.len() - 10);
+    }
 }
 
-async fn process_file(cli: &Cli, keywords: &PoliticalKeywords) -> Result<TranscriptResult> {
-    info!("Processing file: {:?}", cli.input);
+async fn run_schema(validate: Option<&PathBuf>, migrate: Option<&PathBuf>) -> Result<()> {
+    if let Some(path) = validate {
+        let content = fs::read_to_string(path).await
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        let document: serde_json::Value = serde_json::from_str(&content)
+            .context("Invalid JSON")?;
+        
+        let problems = schema::validate(&document)?;
+        if problems.is_empty() {
+            println!("✅ {} is valid (schema v{})", path.display(), schema::SCHEMA_VERSION);
+            return Ok(());
+        }
+        
+        for problem in &problems {
+            println!("❌ {}", problem);
+        }
+        return Err(anyhow::anyhow!("{} failed schema validation ({} problems)", path.display(), problems.len()));
+    }
+    
+    if let Some(path) = migrate {
+        let content = fs::read_to_string(path).await
+            .with_context(|| format!("Failed to read {:?}", path))?;
+        let document = schema::migrate(serde_json::from_str(&content).context("Invalid JSON")?)?;
+        println!("{}", serde_json::to_string_pretty(&document)?);
+        return Ok(());
+    }
+    
+    println!("{}", serde_json::to_string_pretty(&schema::json_schema())?);
+    Ok(())
+}
+
+/// Keyword taxonomy and lexicons, loaded once and shared by every segment
+struct Analyzers {
+    keywords: Taxonomy,
+    sentiment: SentimentAnalyzer,
+    stance: Box<dyn StanceClassifier>,
+    entities: EntityExtractor,
+    profanity: ProfanityLexicon,
+    script: Option<PreparedScript>,
+    /// Set when `--corrections` files are given
+    corrector: Option<Corrector>,
+    /// Set under `--redact`
+    redactor: Option<Redactor>,
+}
+
+async fn load_analyzers(cli: &Cli) -> Result<Analyzers> {
+    Ok(Analyzers {
+        keywords: Taxonomy::load(&cli.keywords).await?,
+        sentiment: SentimentAnalyzer::load(&cli.sentiment_lexicon).await?,
+        stance: Box::new(LexiconStanceClassifier::load(&cli.stance_lexicon).await?),
+        entities: EntityExtractor::load(&cli.gazetteer).await?,
+        profanity: ProfanityLexicon::load(&cli.profanity_lexicon).await?,
+        script: match &cli.script {
+            Some(path) => Some(PreparedScript::load(path).await?),
+            None => None,
+        },
+        corrector: if cli.corrections.is_empty() { None } else { Some(Corrector::load(&cli.corrections).await?) },
+        redactor: if cli.redact { Some(Redactor::load(&cli.redact_names).await?) } else { None },
+    })
+}
+
+/// Decode the media emphasis is measured from. An explicit `--audio` must
+/// load; otherwise a missing decoder just falls back to text-based emphasis.
+async fn load_audio(cli: &Cli, input: Option<&PathBuf>) -> Result<Option<AudioSignal>> {
+    if let Some(path) = &cli.audio {
+        let audio = AudioSignal::load(path).await
+            .with_context(|| format!("Failed to load audio {:?}", path))?;
+        return Ok(Some(audio));
+    }
+    
+    let Some(path) = input else {
+        return Ok(None);
+    };
+    match AudioSignal::load(path).await {
+        Ok(audio) => Ok(Some(audio)),
+        Err(e) => {
+            warn!("No acoustic emphasis for {:?}: {:#}", path, e);
+            Ok(None)
+        }
+    }
+}
+
+async fn process_file(
+    cli: &Cli,
+    input: &PathBuf,
+    analyzers: &Analyzers,
+) -> Result<TranscriptResult> {
+    info!("Processing file: {:?}", input);
     
     // Extract audio if needed (placeholder - would use FFmpeg bindings)
-    let audio_path = if is_video_file(&cli.input) {
-        extract_audio(&cli.input).await?
+    let audio_path = if is_video_file(input) {
+        extract_audio(input).await?
     } else {
-        cli.input.clone()
+        input.clone()
     };
     
     // Transcribe using Whisper (placeholder - would use actual Whisper integration)
     let segments = transcribe_audio(&audio_path, cli).await?;
     
+    let source = SourceInfo {
+        filename: input.file_name().unwrap().to_string_lossy().to_string(),
+        language: cli.language.clone().unwrap_or_else(|| "auto".to_string()),
+        model_used: cli.model.clone(),
+        audio: if cli.political_mode || cli.reaction_cues {
+            load_audio(cli, Some(input)).await?
+        } else {
+            None
+        },
+    };
+    
+    let transcript = build_transcript(&source, segments, analyzers, cli.political_mode).await?;
+    save_redacted_audio(cli, &transcript).await?;
+    Ok(transcript)
+}
+
+/// Where a set of segments came from
+struct SourceInfo {
+    filename: String,
+    language: String,
+    model_used: String,
+    /// Decoded media for the acoustic measurements, when available
+    audio: Option<AudioSignal>,
+}
+
+async fn build_transcript(
+    source: &SourceInfo,
+    mut segments: Vec<BasicSegment>,
+    analyzers: &Analyzers,
+    political_mode: bool,
+) -> Result<TranscriptResult> {
+    // Spellings are fixed first, so the name lists and every stage see them
+    let mut corrections: Vec<Correction> = match &analyzers.corrector {
+        Some(corrector) => segments.iter_mut().flat_map(|s| corrector.correct(s)).collect(),
+        None => Vec::new(),
+    };
+    if !corrections.is_empty() {
+        info!("Applied {} corrections", corrections.len());
+    }
+    
+    // Personal information goes before anything reads the text
+    let redactions: Vec<Redaction> = match &analyzers.redactor {
+        Some(redactor) => segments.iter_mut().flat_map(|s| redactor.redact(s)).collect(),
+        None => Vec::new(),
+    };
+    if !redactions.is_empty() {
+        info!("Redacted {} spans of personal information", redactions.len());
+    }
+    // The correction log must not give away what was redacted
+    for correction in &mut corrections {
+        let redacted = redactions.iter().any(|r| {
+            r.segment == correction.segment && r.start < correction.end && correction.start < r.end
+        });
+        if redacted {
+            correction.original = redaction::MARKER.to_string();
+            correction.replacement = redaction::MARKER.to_string();
+        }
+    }
+    
+    let audience_reactions = source.audio.as_ref().map(reactions::detect).unwrap_or_default();
+    if !audience_reactions.is_empty() {
+        info!("Detected {} audience reactions", audience_reactions.len());
+    }
+    
     // Enhance with political analysis if enabled
-    let enhanced_segments = if cli.political_mode {
-        enhance_political_analysis(segments, keywords).await?
+    let mut enhanced_segments = if political_mode {
+        let matcher = KeywordMatcher::new(&analyzers.keywords)?;
+        enhance_political_analysis(segments, &matcher, &analyzers.sentiment, source.audio.as_ref()).await?
     } else {
         segments.into_iter().map(|s| TranscriptSegment {
             id: s.id,
@@ -290,29 +877,58 @@ async fn process_file(cli: &Cli, keywords: &PoliticalKeywords) -> Result<Transcr
             political_keywords: vec![],
             sentiment: None,
             emphasis_level: None,
+            acoustics: None,
+            words: s.words,
+            entities: vec![],
+            disfluencies: vec![],
+            profanity: vec![],
         }).collect()
     };
     
+    // Entities, disfluencies and profanity are tagged whether or not political mode is on
+    let profanity = analyzers.profanity.for_language(&source.language);
+    enhanced_segments.par_iter_mut().for_each(|segment| {
+        segment.entities = analyzers.entities.find(&segment.text);
+        segment.disfluencies = disfluency::detect(&segment.text);
+        segment.profanity = profanity.find(&segment.text);
+    });
+    let entities = entities::index(&enhanced_segments);
+    
+    let script = analyzers.script.as_ref().map(|s| script::compare(s, &enhanced_segments)).transpose()?;
+    
     // Calculate statistics
     let stats = calculate_statistics(&enhanced_segments);
     
+    // Chapters and the political analysis work on whole sentences
+    let sentences = sentences::split(&enhanced_segments);
+    let chapters = chapters::detect(&sentences, &analyzers.keywords);
+    let claims = claims::extract(&sentences);
+    
     // Generate political analysis if enabled
-    let political_analysis = if cli.political_mode {
-        Some(generate_political_analysis(&enhanced_segments, keywords).await?)
+    let political_analysis = if political_mode {
+        Some(generate_political_analysis(&enhanced_segments, &sentences, analyzers, &audience_reactions).await?)
     } else {
         None
     };
     
     Ok(TranscriptResult {
-        filename: cli.input.file_name().unwrap().to_string_lossy().to_string(),
+        schema_version: schema::SCHEMA_VERSION,
+        filename: source.filename.clone(),
         duration: enhanced_segments.last().map(|s| s.end).unwrap_or(0.0),
-        language: cli.language.clone().unwrap_or_else(|| "auto".to_string()),
-        model_used: cli.model.clone(),
+        language: source.language.clone(),
+        model_used: source.model_used.clone(),
         processing_time: 0.0, // Will be set by caller
         timestamp: Utc::now(),
         segments: enhanced_segments,
         statistics: stats,
         political_analysis,
+        audience_reactions,
+        chapters,
+        entities,
+        claims,
+        script,
+        redactions,
+        corrections,
     })
 }
 
@@ -356,7 +972,7 @@ async fn transcribe_audio(audio_path: &PathBuf, cli: &Cli) -> Result<Vec<BasicSe
     tokio::time::sleep(tokio::time::Duration::from_millis(processing_delay)).await;
     
     // Generate mock segments for demonstration
-    Ok(vec![
+    let mut segments = vec![
         BasicSegment {
             id: 1,
             start: 0.0,
@@ -364,6 +980,7 @@ async fn transcribe_audio(audio_path: &PathBuf, cli: &Cli) -> Result<Vec<BasicSe
             text: "Thank you for joining us today for this important discussion about healthcare reform.".to_string(),
             confidence: 0.95,
             speaker: Some("Speaker 1".to_string()),
+            words: vec![],
         },
         BasicSegment {
             id: 2,
@@ -372,6 +989,7 @@ async fn transcribe_audio(audio_path: &PathBuf, cli: &Cli) -> Result<Vec<BasicSe
             text: "We need to ensure that every American has access to affordable healthcare without compromise.".to_string(),
             confidence: 0.92,
             speaker: Some("Speaker 1".to_string()),
+            words: vec![],
         },
         BasicSegment {
             id: 3,
@@ -380,8 +998,42 @@ async fn transcribe_audio(audio_path: &PathBuf, cli: &Cli) -> Result<Vec<BasicSe
             text: "Our economy depends on healthy workers and families who aren't burdened by medical debt.".to_string(),
             confidence: 0.89,
             speaker: Some("Speaker 1".to_string()),
+            words: vec![],
         },
-    ])
+    ];
+    
+    if cli.word_timestamps {
+        for segment in &mut segments {
+            segment.words = estimate_word_timings(&segment.text, segment.start, segment.end, segment.confidence);
+        }
+    }
+    
+    Ok(segments)
+}
+
+fn estimate_word_timings(text: &str, start: f64, end: f64, confidence: f32) -> Vec<WordTiming> {
+    // Spread the segment duration over its words in proportion to their length
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let total_chars: usize = words.iter().map(|w| w.chars().count()).sum();
+    if total_chars == 0 {
+        return vec![];
+    }
+    
+    let seconds_per_char = (end - start).max(0.0) / total_chars as f64;
+    let mut cursor = start;
+    words
+        .into_iter()
+        .map(|word| {
+            let word_start = cursor;
+            cursor += word.chars().count() as f64 * seconds_per_char;
+            WordTiming {
+                text: word.to_string(),
+                start: word_start,
+                end: cursor,
+                confidence,
+            }
+        })
+        .collect()
 }
 
 #[derive(Debug)]
@@ -392,20 +1044,32 @@ struct BasicSegment {
     text: String,
     confidence: f32,
     speaker: Option<String>,
+    words: Vec<WordTiming>,
 }
 
 async fn enhance_political_analysis(
     segments: Vec<BasicSegment>,
-    keywords: &PoliticalKeywords,
+    matcher: &KeywordMatcher<'_>,
+    sentiment: &SentimentAnalyzer,
+    audio: Option<&AudioSignal>,
 ) -> Result<Vec<TranscriptSegment>> {
     info!("Enhancing with political analysis");
     
+    let measured: Vec<Option<(AcousticFeatures, f32)>> = match audio {
+        Some(audio) => emphasis::measure(audio, &segments).into_iter().map(Some).collect(),
+        None => segments.iter().map(|_| None).collect(),
+    };
+    
     let enhanced: Vec<TranscriptSegment> = segments
         .into_par_iter()
-        .map(|segment| {
-            let political_keywords = detect_political_keywords(&segment.text, keywords);
-            let sentiment = analyze_sentiment(&segment.text);
-            let emphasis_level = calculate_emphasis(&segment.text);
+        .zip(measured)
+        .map(|(segment, measured)| {
+            let political_keywords = matcher.find(&segment.text);
+            let sentiment = sentiment.analyze(&segment.text);
+            let (acoustics, emphasis_level) = match measured {
+                Some((features, level)) => (Some(features), level),
+                None => (None, emphasis::from_text(&segment.text)),
+            };
             
             TranscriptSegment {
                 id: segment.id,
@@ -417,6 +1081,11 @@ async fn enhance_political_analysis(
                 political_keywords,
                 sentiment: Some(sentiment),
                 emphasis_level: Some(emphasis_level),
+                acoustics,
+                words: segment.words,
+                entities: vec![],
+                disfluencies: vec![],
+                profanity: vec![],
             }
         })
         .collect();
@@ -424,61 +1093,15 @@ async fn enhance_political_analysis(
     Ok(enhanced)
 }
 
-fn detect_political_keywords(text: &str, keywords: &PoliticalKeywords) -> Vec<String> {
-    let text_lower = text.to_lowercase();
-    let mut found_keywords = Vec::new();
-    
-    for category_keywords in [
-        &keywords.economy,
-        &keywords.healthcare,
-        &keywords.education,
-        &keywords.environment,
-        &keywords.immigration,
-        &keywords.foreign_policy,
-        &keywords.social_issues,
-        &keywords.general,
-    ] {
-        for keyword in category_keywords {
-            if text_lower.contains(keyword) {
-                found_keywords.push(keyword.to_string());
-            }
-        }
-    }
-    
-    found_keywords.sort();
-    found_keywords.dedup();
-    found_keywords
-}
-
-fn analyze_sentiment(text: &str) -> String {
-    // Simple sentiment analysis based on keywords
-    let positive_words = ["good", "great", "excellent", "wonderful", "amazing", "fantastic", "success", "progress", "improve", "better"];
-    let negative_words = ["bad", "terrible", "awful", "horrible", "disaster", "failure", "crisis", "problem", "decline", "worse"];
-    
-    let text_lower = text.to_lowercase();
-    let positive_count = positive_words.iter().filter(|&word| text_lower.contains(word)).count();
-    let negative_count = negative_words.iter().filter(|&word| text_lower.contains(word)).count();
-    
-    match positive_count.cmp(&negative_count) {
-        std::cmp::Ordering::Greater => "positive".to_string(),
-        std::cmp::Ordering::Less => "negative".to_string(),
-        std::cmp::Ordering::Equal => "neutral".to_string(),
-    }
-}
-
-fn calculate_emphasis(text: &str) -> f32 {
-    // Calculate emphasis based on punctuation, caps, etc.
-    let exclamation_count = text.matches('!').count() as f32;
-    let caps_ratio = text.chars().filter(|c| c.is_uppercase()).count() as f32 / text.len() as f32;
-    let question_count = text.matches('?').count() as f32;
-    
-    (exclamation_count * 0.3 + caps_ratio * 10.0 + question_count * 0.2).min(1.0)
-}
-
 fn calculate_statistics(segments: &[TranscriptSegment]) -> TranscriptStats {
     let total_segments = segments.len();
     let total_words = segments.iter().map(|s| s.text.split_whitespace().count()).sum();
-    let average_confidence = segments.iter().map(|s| s.confidence).sum::<f32>() / total_segments as f32;
+    // An empty transcript has no confidence to average; 0/0 would serialize as null
+    let average_confidence = if total_segments == 0 {
+        0.0
+    } else {
+        segments.iter().map(|s| s.confidence).sum::<f32>() / total_segments as f32
+    };
     
     let speech_duration = segments.last().map(|s| s.end).unwrap_or(0.0);
     let silence_duration = 0.0; // Would calculate actual silence periods
@@ -496,12 +1119,16 @@ fn calculate_statistics(segments: &[TranscriptSegment]) -> TranscriptStats {
         speech_duration,
         silence_duration,
         speakers_detected,
+        speakers: turns::speaker_stats(segments),
+        disfluency: disfluency::speaker_rates(segments),
     }
 }
 
 async fn generate_political_analysis(
     segments: &[TranscriptSegment],
-    keywords: &PoliticalKeywords,
+    sentences: &[Sentence<'_>],
+    analyzers: &Analyzers,
+    audience_reactions: &[AudienceReaction],
 ) -> Result<PoliticalAnalysis> {
     info!("Generating political analysis");
     
@@ -509,10 +1136,44 @@ async fn generate_political_analysis(
     let mut theme_counts: HashMap<String, usize> = HashMap::new();
     for segment in segments {
         for keyword in &segment.political_keywords {
-            *theme_counts.entry(keyword.clone()).or_insert(0) += 1;
+            *theme_counts.entry(keyword.term.clone()).or_insert(0) += 1;
         }
     }
     
+    // Roll the same mentions up by issue area
+    let mut issue_areas: Vec<IssueArea> = Vec::new();
+    for segment in segments {
+        for keyword in &segment.political_keywords {
+            let index = match issue_areas.iter().position(|a| a.category == keyword.category) {
+                Some(index) => index,
+                None => {
+                    issue_areas.push(IssueArea {
+                        category: keyword.category.clone(),
+                        label: analyzers
+                            .keywords
+                            .category(&keyword.category)
+                            .map(|c| c.label.clone())
+                            .unwrap_or_else(|| keyword.category.clone()),
+                        mentions: 0,
+                        weighted_score: 0.0,
+                        terms: vec![],
+                    });
+                    issue_areas.len() - 1
+                }
+            };
+            let area = &mut issue_areas[index];
+            area.mentions += 1;
+            area.weighted_score += keyword.weight;
+            if !area.terms.contains(&keyword.term) {
+                area.terms.push(keyword.term.clone());
+            }
+        }
+    }
+    for area in &mut issue_areas {
+        area.terms.sort_by_key(|term| std::cmp::Reverse(theme_counts.get(term).copied().unwrap_or(0)));
+    }
+    issue_areas.sort_by(|a, b| b.weighted_score.total_cmp(&a.weighted_score));
+    
     let mut key_themes: Vec<String> = theme_counts
         .into_iter()
         .filter(|(_, count)| *count >= 2)
@@ -520,52 +1181,24 @@ async fn generate_political_analysis(
         .collect();
     key_themes.sort();
     
-    // Generate talking points
-    let talking_points = segments
-        .iter()
-        .filter(|s| s.political_keywords.len() > 2)
-        .map(|s| s.text.clone())
-        .take(5)
-        .collect();
+    // Rank sentences for the summary and talking points
+    let ranks = summary::lexrank(sentences);
+    let summary = summary::summarize(sentences, &ranks);
+    let talking_points = summary::talking_points(sentences, &ranks, &analyzers.keywords);
     
     // Find quotable moments
-    let quotable_moments = segments
-        .iter()
-        .filter(|s| s.emphasis_level.unwrap_or(0.0) > 0.3 || s.political_keywords.len() > 1)
-        .map(|s| QuotableMoment {
-            start: s.start,
-            end: s.end,
-            text: s.text.clone(),
-            viral_potential: s.emphasis_level.unwrap_or(0.0) + (s.political_keywords.len() as f32 * 0.1),
-            context: format!("Political discussion at {:.1}s", s.start),
-        })
-        .collect();
+    let quotable_moments = quotes::extract(sentences, audience_reactions);
     
     // Sentiment distribution
-    let mut sentiment_distribution = HashMap::new();
-    for segment in segments {
-        if let Some(sentiment) = &segment.sentiment {
-            *sentiment_distribution.entry(sentiment.clone()).or_insert(0.0) += 1.0;
-        }
-    }
+    let sentiment_distribution = sentiment_distribution(segments);
     
-    let total_segments = segments.len() as f32;
-    for value in sentiment_distribution.values_mut() {
-        *value /= total_segments;
-    }
-    
-    // Policy mentions (simplified)
-    let policy_mentions = vec![
-        PolicyMention {
-            policy: "Healthcare".to_string(),
-            stance: "Support".to_string(),
-            confidence: 0.8,
-            timestamp: 5.0,
-        }
-    ];
+    // Stance toward each issue, sentence by sentence
+    let policy_mentions = stance::detect(sentences, &analyzers.keywords, analyzers.stance.as_ref());
     
     Ok(PoliticalAnalysis {
         key_themes,
+        issue_areas,
+        summary,
         talking_points,
         quotable_moments,
         sentiment_distribution,
@@ -573,113 +1206,110 @@ async fn generate_political_analysis(
     })
 }
 
-async fn save_transcript(cli: &Cli, transcript: &TranscriptResult, processing_time: f64) -> Result<()> {
-    let base_name = cli.input.file_stem().unwrap().to_string_lossy();
-    
-    let mut transcript_with_time = transcript.clone();
-    transcript_with_time.processing_time = processing_time;
+fn sentiment_distribution(segments: &[TranscriptSegment]) -> SentimentDistribution {
+    let scored: Vec<(&TranscriptSegment, &Sentiment)> = segments
+        .iter()
+        .filter_map(|s| s.sentiment.as_ref().map(|sentiment| (s, sentiment)))
+        .collect();
+    if scored.is_empty() {
+        return SentimentDistribution::default();
+    }
     
-    match cli.format.as_str() {
-        "srt" => save_srt(cli, &transcript_with_time, &base_name).await?,
-        "vtt" => save_vtt(cli, &transcript_with_time, &base_name).await?,
-        "txt" => save_txt(cli, &transcript_with_time, &base_name).await?,
-        "json" => save_json(cli, &transcript_with_time, &base_name).await?,
-        "all" => {
-            save_srt(cli, &transcript_with_time, &base_name).await?;
-            save_vtt(cli, &transcript_with_time, &base_name).await?;
-            save_txt(cli, &transcript_with_time, &base_name).await?;
-            save_json(cli, &transcript_with_time, &base_name).await?;
+    let mut shares = HashMap::new();
+    let mut speaker_totals: BTreeMap<String, (f32, usize)> = BTreeMap::new();
+    let mut duration_total = 0.0;
+    let mut weighted_total = 0.0;
+    for (segment, sentiment) in &scored {
+        *shares.entry(sentiment.label.clone()).or_insert(0.0) += 1.0 / scored.len() as f32;
+        
+        let duration = (segment.end - segment.start).max(0.0) as f32;
+        duration_total += duration;
+        weighted_total += sentiment.compound * duration;
+        
+        if let Some(speaker) = &segment.speaker {
+            let entry = speaker_totals.entry(speaker.clone()).or_insert((0.0, 0));
+            entry.0 += sentiment.compound;
+            entry.1 += 1;
         }
-        _ => return Err(anyhow::anyhow!("Unsupported format: {}", cli.format)),
     }
     
-    Ok(())
-}
-
-async fn save_srt(cli: &Cli, transcript: &TranscriptResult, base_name: &str) -> Result<()> {
-    let output_path = cli.output.join(format!("{}.srt", base_name));
-    let mut content = String::new();
+    let mean_compound = scored.iter().map(|(_, s)| s.compound).sum::<f32>() / scored.len() as f32;
     
-    for segment in &transcript.segments {
-        content.push_str(&format!(
-            "{}\n{} --> {}\n{}\n\n",
-            segment.id,
-            format_time_srt(segment.start),
-            format_time_srt(segment.end),
-            segment.text
-        ));
+    SentimentDistribution {
+        shares,
+        mean_compound,
+        duration_weighted_mean: if duration_total > 0.0 { weighted_total / duration_total } else { mean_compound },
+        speaker_means: speaker_totals
+            .into_iter()
+            .map(|(speaker, (total, count))| (speaker, total / count as f32))
+            .collect(),
     }
-    
-    fs::write(&output_path, content).await
-        .context("Failed to write SRT file")?;
-    
-    info!("Saved SRT: {:?}", output_path);
-    Ok(())
 }
 
-async fn save_vtt(cli: &Cli, transcript: &TranscriptResult, base_name: &str) -> Result<()> {
-    let output_path = cli.output.join(format!("{}.vtt", base_name));
-    let mut content = String::from("WEBVTT\n\n");
-    
-    for segment in &transcript.segments {
-        content.push_str(&format!(
-            "{} --> {}\n{}\n\n",
-            format_time_vtt(segment.start),
-            format_time_vtt(segment.end),
-            segment.text
-        ));
-    }
-    
-    fs::write(&output_path, content).await
-        .context("Failed to write VTT file")?;
-    
-    info!("Saved VTT: {:?}", output_path);
-    Ok(())
+/// Output files are named after the input: `<base_name>.<suffix>`
+fn output_base_name(transcript: &TranscriptResult) -> String {
+    std::path::Path::new(&transcript.filename)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "transcript".to_string())
 }
 
-async fn save_txt(cli: &Cli, transcript: &TranscriptResult, base_name: &str) -> Result<()> {
-    let output_path = cli.output.join(format!("{}.txt", base_name));
-    let content = transcript.segments
-        .iter()
-        .map(|s| s.text.clone())
-        .collect::<Vec<_>>()
-        .join(" ");
+async fn save_transcript(cli: &Cli, transcript: &TranscriptResult, processing_time: f64) -> Result<()> {
+    let base_name = output_base_name(transcript);
     
-    fs::write(&output_path, content).await
-        .context("Failed to write TXT file")?;
+    let mut transcript_with_time = transcript.clone();
+    transcript_with_time.processing_time = processing_time;
     
-    info!("Saved TXT: {:?}", output_path);
-    Ok(())
-}
-
-async fn save_json(cli: &Cli, transcript: &TranscriptResult, base_name: &str) -> Result<()> {
-    let output_path = cli.output.join(format!("{}.json", base_name));
-    let content = serde_json::to_string_pretty(transcript)
-        .context("Failed to serialize transcript")?;
+    let mut selected = writers::resolve_formats(&cli.format)?;
+    // Reports that come with their options, whatever the formats
+    for (wanted, format) in [(cli.broadcast_safe, "compliance"), (cli.script.is_some(), "script")] {
+        if wanted && !selected.iter().any(|w| w.format() == format) {
+            selected.extend(writers::resolve_formats(format)?);
+        }
+    }
     
-    fs::write(&output_path, content).await
-        .context("Failed to write JSON file")?;
+    for writer in selected {
+        for artifact in writer.render(cli, &transcript_with_time)? {
+            let output_path = cli.output.join(format!("{}.{}", base_name, artifact.suffix));
+            
+            fs::write(&output_path, artifact.contents).await
+                .with_context(|| format!("Failed to write {} file", writer.format().to_uppercase()))?;
+            
+            info!("Saved {}: {:?}", writer.format().to_uppercase(), output_path);
+        }
+    }
+    
+    // The log goes out whatever the formats, even when nothing was found
+    if cli.redact {
+        let output_path = cli.output.join(format!("{}.redactions.json", base_name));
+        fs::write(&output_path, serde_json::to_string_pretty(&transcript.redactions)?).await
+            .context("Failed to write redaction log")?;
+        info!("Saved redaction log: {:?}", output_path);
+    }
     
-    info!("Saved JSON: {:?}", output_path);
     Ok(())
 }
 
-fn format_time_srt(seconds: f64) -> String {
-    let hours = (seconds / 3600.0) as u32;
-    let minutes = ((seconds % 3600.0) / 60.0) as u32;
-    let secs = (seconds % 60.0) as u32;
-    let millis = ((seconds % 1.0) * 1000.0) as u32;
-    
-    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millis)
+/// `<base_name>.redacted.<ext>` in the output directory
+fn redacted_media_path(cli: &Cli, media: &Path, transcript: &TranscriptResult) -> PathBuf {
+    let extension = media.extension().map(|e| e.to_string_lossy().to_string()).unwrap_or_else(|| "wav".to_string());
+    cli.output.join(format!("{}.redacted.{}", output_base_name(transcript), extension))
 }
 
-fn format_time_vtt(seconds: f64) -> String {
-    let hours = (seconds / 3600.0) as u32;
-    let minutes = ((seconds % 3600.0) / 60.0) as u32;
-    let secs = (seconds % 60.0) as u32;
-    let millis = ((seconds % 1.0) * 1000.0) as u32;
+/// The bleeped or silenced copy of the audio asked for with `--redact-audio`
+async fn save_redacted_audio(cli: &Cli, transcript: &TranscriptResult) -> Result<()> {
+    let Some(mode) = cli.redact_audio else {
+        return Ok(());
+    };
+    let Some(media) = cli.media() else {
+        warn!("No redacted audio for {}: pass the recording with --audio", transcript.filename);
+        return Ok(());
+    };
     
-    format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+    let output_path = redacted_media_path(cli, media, transcript);
+    redaction::write_audio(media, &transcript.redactions, mode, &output_path).await?;
+    info!("Saved redacted audio: {:?}", output_path);
+    Ok(())
 }
 
 fn print_summary(transcript: &TranscriptResult, processing_time: f64) {
@@ -690,20 +1320,133 @@ fn print_summary(transcript: &TranscriptResult, processing_time: f64) {
     println!("🎬 Segments: {}", transcript.statistics.total_segments);
     println!("💬 Words: {}", transcript.statistics.total_words);
     println!("✅ Avg Confidence: {:.1}%", transcript.statistics.average_confidence * 100.0);
+    let speakers = &transcript.statistics.speakers;
+    if speakers.len() > 1 || speakers.iter().any(|s| s.speaker.is_some()) {
+        println!("🎙️  Speakers:");
+        for stats in speakers {
+            let longest = stats.longest_monologue.as_ref().map(|m| m.end - m.start).unwrap_or(0.0);
+            println!(
+                "   {}: {:.0}% of talk time ({}), {:.0} wpm, {} turns, longest {}, avg pause {:.1}s",
+                stats.speaker.as_deref().unwrap_or("(unlabeled)"),
+                stats.share * 100.0,
+                writers::format_clock(stats.talk_time),
+                stats.words_per_minute,
+                stats.turns,
+                writers::format_clock(longest),
+                stats.average_pause
+            );
+            if stats.interruptions > 0 || stats.interrupted > 0 {
+                println!(
+                    "      interrupted others {}×, was interrupted {}× ({:.1}s overlapping)",
+                    stats.interruptions,
+                    stats.interrupted,
+                    stats.overlap_seconds
+                );
+            }
+        }
+    } else if let Some(stats) = speakers.first() {
+        println!("🗣️  Speaking Rate: {:.0} wpm", stats.words_per_minute);
+    }
+    for rate in transcript.statistics.disfluency.iter().filter(|r| r.fillers > 0) {
+        println!(
+            "💭 Fillers{}: {:.1} per 100 words ({} repeats, {} restarts)",
+            rate.speaker.as_ref().map(|s| format!(" ({})", s)).unwrap_or_default(),
+            rate.filler_rate,
+            rate.repeats,
+            rate.restarts
+        );
+    }
+    
+    if !transcript.entities.is_empty() {
+        let top: Vec<&str> = transcript.entities.iter().take(5).map(|e| e.name.as_str()).collect();
+        println!("🏷️  Entities: {} ({})", transcript.entities.len(), top.join(", "));
+    }
+    
+    if !transcript.claims.is_empty() {
+        println!("🔎 Checkable Claims: {}", transcript.claims.len());
+    }
+    
+    if let Some(script) = &transcript.script {
+        let count = |delivery: Delivery| script.passages.iter().filter(|p| p.delivery == delivery).count();
+        println!(
+            "📜 Script: {:.0}% delivered ({} verbatim, {} paraphrased, {} skipped), {} ad-libs ({} words)",
+            script.coverage * 100.0,
+            count(Delivery::Verbatim),
+            count(Delivery::Paraphrased),
+            count(Delivery::Skipped),
+            count(Delivery::AdLibbed),
+            script.adlib_words
+        );
+    }
+    
+    let flags: Vec<&Profanity> = transcript.segments.iter().flat_map(|s| &s.profanity).collect();
+    if !flags.is_empty() {
+        let count = |severity: Severity| flags.iter().filter(|f| f.severity == severity).count();
+        println!(
+            "🚫 Profanity: {} ({} severe, {} strong, {} mild)",
+            flags.len(),
+            count(Severity::Severe),
+            count(Severity::Strong),
+            count(Severity::Mild)
+        );
+    }
+    
+    if !transcript.redactions.is_empty() {
+        let mut kinds: Vec<(&str, usize)> = Vec::new();
+        for redaction in &transcript.redactions {
+            match kinds.iter_mut().find(|(kind, _)| *kind == redaction.kind.as_str()) {
+                Some((_, count)) => *count += 1,
+                None => kinds.push((redaction.kind.as_str(), 1)),
+            }
+        }
+        let kinds: Vec<String> = kinds.iter().map(|(kind, count)| format!("{} {}", count, kind)).collect();
+        println!("🔒 Redacted: {} ({})", transcript.redactions.len(), kinds.join(", "));
+    }
+    
+    if !transcript.corrections.is_empty() {
+        let count = |rule: CorrectionRule| transcript.corrections.iter().filter(|c| c.rule == rule).count();
+        println!(
+            "✏️  Corrections: {} ({} name, {} literal, {} regex)",
+            transcript.corrections.len(),
+            count(CorrectionRule::Name),
+            count(CorrectionRule::Literal),
+            count(CorrectionRule::Regex)
+        );
+    }
     
     if let Some(analysis) = &transcript.political_analysis {
         println!("\n🏛️  Political Analysis:");
         println!("📊 Key Themes: {}", analysis.key_themes.join(", "));
-        println!("💡 Talking Points: {}", analysis.talking_points.len());
+        println!(
+            "💡 Talking Points: {} across {} issues",
+            analysis.talking_points.iter().map(|g| g.points.len()).sum::<usize>(),
+            analysis.talking_points.len()
+        );
         println!("🔥 Quotable Moments: {}", analysis.quotable_moments.len());
         
-        if !analysis.sentiment_distribution.is_empty() {
-            println!("😊 Sentiment:");
-            for (sentiment, ratio) in &analysis.sentiment_distribution {
+        let count = |stance| analysis.policy_mentions.iter().filter(|m| m.stance == stance).count();
+        println!(
+            "🗳️  Policy Mentions: {} ({} support, {} oppose)",
+            analysis.policy_mentions.len(),
+            count(Stance::Support),
+            count(Stance::Oppose)
+        );
+        
+        let distribution = &analysis.sentiment_distribution;
+        if !distribution.shares.is_empty() {
+            println!("😊 Sentiment (mean {:+.2}):", distribution.mean_compound);
+            for (sentiment, ratio) in &distribution.shares {
                 println!("   {}: {:.1}%", sentiment, ratio * 100.0);
             }
+            for (speaker, mean) in &distribution.speaker_means {
+                println!("   {}: {:+.2}", speaker, mean);
+            }
         }
     }
     
+    if !transcript.audience_reactions.is_empty() {
+        println!("👏 Audience Reactions: {}", transcript.audience_reactions.len());
+    }
+    
     println!("\n✨ Ready for political communications! 🚀");
 } 
\ No newline at end of file
@@ -0,0 +1,234 @@
+//! Profanity and slurs, flagged per segment for broadcast review.
+//!
+//! The lexicon lives in `data/profanity.toml`, one table per language with
+//! three severity levels; see that file for the override format. The table
+//! is picked by the transcript's language. Flags are kept on the segments
+//! with the JSON verbatim; `--broadcast-safe` masks them in the captions
+//! ("f***") and adds the compliance report.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::text::TextSpan;
+
+const BUILTIN_LEXICON: &str = include_str!("../data/profanity.toml");
+
+/// Table used when the language is unknown or has none of its own
+const DEFAULT_LANGUAGE: &str = "en";
+/// Language names some Whisper front ends report instead of codes
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("english", "en"),
+    ("spanish", "es"),
+    ("french", "fr"),
+    ("german", "de"),
+    ("portuguese", "pt"),
+];
+
+#[derive(Serialize, Deserialize, JsonSchema, clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Mild,
+    Strong,
+    /// Slurs
+    Severe,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Mild => "mild",
+            Severity::Strong => "strong",
+            Severity::Severe => "severe",
+        }
+    }
+}
+
+/// A flagged word in a segment
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Profanity {
+    /// As spoken
+    pub text: String,
+    pub severity: Severity,
+    /// Where it occurs in the segment text
+    pub span: TextSpan,
+}
+
+#[derive(Deserialize, Default)]
+struct LanguageSpec {
+    #[serde(default)]
+    mild: Vec<String>,
+    #[serde(default)]
+    strong: Vec<String>,
+    #[serde(default)]
+    severe: Vec<String>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+/// Every language's table
+pub struct ProfanityLexicon {
+    languages: HashMap<String, LanguageLexicon>,
+}
+
+/// One language's words
+#[derive(Default)]
+pub struct LanguageLexicon {
+    words: HashMap<String, Severity>,
+    /// `fuck*`-style entries, matched as prefixes
+    stems: Vec<(String, Severity)>,
+}
+
+impl ProfanityLexicon {
+    pub async fn load(overrides: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut files: Vec<BTreeMap<String, LanguageSpec>> =
+            vec![toml::from_str(BUILTIN_LEXICON).context("Built-in profanity lexicon is invalid")?];
+
+        for path in overrides {
+            let path = path.as_ref();
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read profanity lexicon {:?}", path))?;
+            files.push(toml::from_str(&content)
+                .with_context(|| format!("Failed to parse profanity lexicon {:?}", path))?);
+        }
+
+        let mut languages: HashMap<String, LanguageLexicon> = HashMap::new();
+        for file in files {
+            for (language, spec) in file {
+                let lexicon = languages.entry(language.to_lowercase()).or_default();
+                for word in &spec.remove {
+                    let word = word.trim().to_lowercase();
+                    lexicon.words.remove(&word);
+                    lexicon.stems.retain(|(stem, _)| format!("{}*", stem) != word);
+                }
+                let entries = spec
+                    .mild
+                    .iter()
+                    .map(|w| (w, Severity::Mild))
+                    .chain(spec.strong.iter().map(|w| (w, Severity::Strong)))
+                    .chain(spec.severe.iter().map(|w| (w, Severity::Severe)));
+                for (word, severity) in entries {
+                    lexicon.insert(word, severity);
+                }
+            }
+        }
+
+        Ok(Self { languages })
+    }
+
+    /// The table for a language code ("en", "es-MX") or name ("Spanish")
+    pub fn for_language(&self, language: &str) -> &LanguageLexicon {
+        let language = language.trim().to_lowercase();
+        let code = LANGUAGE_NAMES
+            .iter()
+            .find(|(name, _)| *name == language)
+            .map_or_else(|| language.split(['-', '_']).next().unwrap_or_default(), |(_, code)| code);
+
+        self.languages
+            .get(code)
+            .or_else(|| self.languages.get(DEFAULT_LANGUAGE))
+            .unwrap_or_else(|| self.languages.values().next().expect("built-in lexicon has languages"))
+    }
+}
+
+impl LanguageLexicon {
+    fn insert(&mut self, word: &str, severity: Severity) {
+        let word = word.trim().to_lowercase();
+        match word.strip_suffix('*') {
+            Some(stem) if !stem.is_empty() => {
+                self.stems.retain(|(s, _)| s != stem);
+                self.stems.push((stem.to_string(), severity));
+                // Longest stem first, so "motherfuck*" outranks "fuck*"
+                self.stems.sort_by_key(|(stem, _)| std::cmp::Reverse(stem.chars().count()));
+            }
+            Some(_) => {}
+            None if !word.is_empty() => {
+                self.words.insert(word, severity);
+            }
+            None => {}
+        }
+    }
+
+    fn severity(&self, word: &str) -> Option<Severity> {
+        self.words.get(word).copied().or_else(|| {
+            self.stems
+                .iter()
+                .find(|(stem, _)| word.starts_with(stem.as_str()))
+                .map(|(_, severity)| *severity)
+        })
+    }
+
+    /// Flagged words in `text`, in order
+    pub fn find(&self, text: &str) -> Vec<Profanity> {
+        words(text)
+            .into_iter()
+            .filter_map(|(word, span)| {
+                let lower = word.to_lowercase();
+                let bare = lower.strip_suffix("'s").or_else(|| lower.strip_suffix("’s")).unwrap_or(&lower);
+                let severity = self.severity(&lower).or_else(|| self.severity(bare))?;
+                Some(Profanity {
+                    text: word.to_string(),
+                    severity,
+                    span,
+                })
+            })
+            .collect()
+    }
+}
+
+/// `text` with every flag at or above `threshold` masked to its first letter
+/// ("f***"); lengths are kept, so other spans into the text stay valid
+pub fn mask(text: &str, flags: &[Profanity], threshold: Severity) -> String {
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let masked = flags
+                .iter()
+                .any(|f| f.severity >= threshold && f.span.start < i && i < f.span.end && c.is_alphanumeric());
+            if masked { '*' } else { c }
+        })
+        .collect()
+}
+
+/// A single word masked to its first letter: "f***"
+pub fn mask_word(word: &str) -> String {
+    word.chars()
+        .enumerate()
+        .map(|(i, c)| if i > 0 && c.is_alphanumeric() { '*' } else { c })
+        .collect()
+}
+
+/// Words of `text` with their char spans; apostrophes stay inside words
+fn words(text: &str) -> Vec<(&str, TextSpan)> {
+    let mut found = Vec::new();
+    let mut current: Option<(usize, usize)> = None; // (byte start, char start)
+
+    for (char_index, (byte, c)) in text.char_indices().enumerate() {
+        let inside = c.is_alphanumeric() || ((c == '\'' || c == '’') && current.is_some());
+        match (inside, current) {
+            (true, None) => current = Some((byte, char_index)),
+            (false, Some((byte_start, char_start))) => {
+                found.push(word(text, byte_start, byte, char_start));
+                current = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((byte_start, char_start)) = current {
+        found.push(word(text, byte_start, text.len(), char_start));
+    }
+    found
+}
+
+/// A word with trailing apostrophes ("dogs'") trimmed off
+fn word(text: &str, byte_start: usize, byte_end: usize, char_start: usize) -> (&str, TextSpan) {
+    let raw = text[byte_start..byte_end].trim_end_matches(['\'', '’']);
+    let span = TextSpan {
+        start: char_start,
+        end: char_start + raw.chars().count(),
+    };
+    (raw, span)
+}
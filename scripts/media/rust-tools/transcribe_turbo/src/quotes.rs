@@ -0,0 +1,306 @@
+//! Quotable-moment extraction.
+//!
+//! Segments are re-cut into sentences (see `sentences`). Runs of one to three
+//! consecutive sentences lasting 5-30 s become candidates; a single sentence
+//! longer than 30 s is cut back to its last clause boundary that fits. Each
+//! candidate is scored 0-1 on five signals:
+//!
+//! - keywords: taxonomy weight of the issue terms it contains
+//! - emphasis: duration-weighted `emphasis_level` of the segments under it
+//! - reaction: applause, cheering or laughter right after it
+//! - rhetoric: tricolons, "not X but Y" contrasts, repetition
+//! - readability: whether it stands on its own without the sentence before
+//!
+//! The best candidates are kept in rank order, skipping any that overlap in
+//! time or mostly repeat the words of one already chosen.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::reactions::{AudienceReaction, ReactionKind};
+use crate::sentences::Sentence;
+use crate::taxonomy::KeywordHit;
+use crate::text::content_terms;
+use crate::QuotableMoment;
+
+const MIN_QUOTE_SECONDS: f64 = 5.0;
+const MAX_QUOTE_SECONDS: f64 = 30.0;
+const MAX_SENTENCES_PER_QUOTE: usize = 3;
+
+const MAX_QUOTES: usize = 10;
+const MIN_QUOTE_SCORE: f32 = 0.3;
+/// Word-set overlap above which two quotes count as the same line
+const DUPLICATE_OVERLAP: f32 = 0.6;
+
+/// Seconds after a line within which an audience reaction counts as a response to it
+const REACTION_WINDOW_SECONDS: f64 = 3.0;
+
+const WEIGHT_KEYWORDS: f32 = 0.25;
+const WEIGHT_EMPHASIS: f32 = 0.2;
+const WEIGHT_REACTION: f32 = 0.2;
+const WEIGHT_RHETORIC: f32 = 0.2;
+const WEIGHT_READABILITY: f32 = 0.15;
+
+static TRICOLON: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b\w+(?:\s+\w+){0,3},\s+\w+(?:\s+\w+){0,3},?\s+(?:and|or)\s+\w+").unwrap()
+});
+static STRONG_CONTRAST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:not|never|no longer)\b[^.;!?]{1,80}\bbut\b|\brather than\b|\binstead of\b").unwrap()
+});
+static WEAK_CONTRAST: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\b(?:but|yet|however|while)\b").unwrap());
+static CLAUSE_BREAK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[,;:—–]\s").unwrap());
+
+/// Sub-scores behind `viral_potential`, each 0-1
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+pub struct QuoteScores {
+    pub keywords: f32,
+    pub emphasis: f32,
+    pub reaction: f32,
+    pub rhetoric: f32,
+    pub readability: f32,
+}
+
+/// The sentences either side of a quote
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+pub struct QuoteContext {
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Ranked, deduplicated quotable moments
+pub fn extract(sentences: &[Sentence], audience_reactions: &[AudienceReaction]) -> Vec<QuotableMoment> {
+    let mut candidates: Vec<QuotableMoment> = Vec::new();
+    for first in 0..sentences.len() {
+        for count in 1..=MAX_SENTENCES_PER_QUOTE {
+            let Some(run) = sentences.get(first..first + count) else { break };
+            let contiguous = run.windows(2).all(|pair| pair[0].continues_into(&pair[1]));
+            if !contiguous {
+                break;
+            }
+
+            let duration = run.last().unwrap().end - run[0].start;
+            if duration > MAX_QUOTE_SECONDS {
+                if count == 1 {
+                    if let Some(trimmed) = trim_to_clause(&run[0]) {
+                        candidates.push(score(&[&trimmed], sentences, first, count, audience_reactions));
+                    }
+                }
+                break;
+            }
+            if duration >= MIN_QUOTE_SECONDS {
+                let run: Vec<&Sentence> = run.iter().collect();
+                candidates.push(score(&run, sentences, first, count, audience_reactions));
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| b.viral_potential.total_cmp(&a.viral_potential));
+
+    let mut chosen: Vec<QuotableMoment> = Vec::new();
+    for candidate in candidates {
+        if chosen.len() >= MAX_QUOTES || candidate.viral_potential < MIN_QUOTE_SCORE {
+            break;
+        }
+        let words = word_set(&candidate.text);
+        let duplicate = chosen.iter().any(|q| {
+            (candidate.start < q.end && q.start < candidate.end) || overlap(&words, &word_set(&q.text)) > DUPLICATE_OVERLAP
+        });
+        if !duplicate {
+            chosen.push(candidate);
+        }
+    }
+
+    chosen
+}
+
+/// Cut an over-long sentence back to the last clause break that keeps it within 5-30 s
+fn trim_to_clause<'a>(sentence: &Sentence<'a>) -> Option<Sentence<'a>> {
+    let duration = sentence.duration();
+    let char_count = sentence.text.chars().count();
+    let time_at = |byte: usize| {
+        let chars = sentence.text[..byte].chars().count();
+        sentence.start + duration * chars as f64 / char_count.max(1) as f64
+    };
+
+    let cut = CLAUSE_BREAK
+        .find_iter(&sentence.text)
+        .map(|m| m.start())
+        .filter(|&byte| {
+            let end = time_at(byte);
+            end - sentence.start >= MIN_QUOTE_SECONDS && end - sentence.start <= MAX_QUOTE_SECONDS
+        })
+        .last()?;
+
+    let end = time_at(cut);
+    let share = ((end - sentence.start) / duration.max(0.001)) as f32;
+    let cut_char = sentence.text[..cut].chars().count();
+    let keywords = sentence
+        .keywords
+        .iter()
+        .filter_map(|hit| {
+            let spans: Vec<_> = hit.spans.iter().filter(|s| s.end <= cut_char).copied().collect();
+            (!spans.is_empty()).then(|| KeywordHit { spans, ..hit.clone() })
+        })
+        .collect();
+    Some(Sentence {
+        text: sentence.text[..cut].to_string(),
+        start: sentence.start,
+        end,
+        speaker: sentence.speaker,
+        keywords,
+        emphasis_seconds: sentence.emphasis_seconds * share,
+        complete: false,
+    })
+}
+
+fn score(
+    run: &[&Sentence],
+    sentences: &[Sentence],
+    first: usize,
+    count: usize,
+    audience_reactions: &[AudienceReaction],
+) -> QuotableMoment {
+    let start = run[0].start;
+    let end = run.last().unwrap().end;
+    let text = run.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+
+    let keyword_weight: f32 = run.iter().map(|s| s.keyword_weight()).sum();
+    let seconds: f32 = run.iter().map(|s| s.duration() as f32).sum();
+    let emphasis = if seconds > 0.0 {
+        run.iter().map(|s| s.emphasis_seconds).sum::<f32>() / seconds
+    } else {
+        0.0
+    };
+    let (rhetoric, devices) = rhetoric(&text);
+
+    let scores = QuoteScores {
+        keywords: 1.0 - (-keyword_weight / 2.0).exp(),
+        emphasis: emphasis.clamp(0.0, 1.0),
+        reaction: reaction_score(end, audience_reactions),
+        rhetoric,
+        readability: readability(run),
+    };
+    let viral_potential = WEIGHT_KEYWORDS * scores.keywords
+        + WEIGHT_EMPHASIS * scores.emphasis
+        + WEIGHT_REACTION * scores.reaction
+        + WEIGHT_RHETORIC * scores.rhetoric
+        + WEIGHT_READABILITY * scores.readability;
+
+    QuotableMoment {
+        start,
+        end,
+        text,
+        speaker: run[0].speaker.map(str::to_string),
+        viral_potential: viral_potential.clamp(0.0, 1.0),
+        scores,
+        devices,
+        context: QuoteContext {
+            before: first.checked_sub(1).map(|i| sentences[i].text.clone()),
+            after: sentences.get(first + count).map(|s| s.text.clone()),
+        },
+    }
+}
+
+/// Strongest audience response starting just after `end`
+fn reaction_score(end: f64, audience_reactions: &[AudienceReaction]) -> f32 {
+    audience_reactions
+        .iter()
+        .filter(|r| r.start >= end - 0.5 && r.start <= end + REACTION_WINDOW_SECONDS)
+        .map(|r| {
+            let weight = match r.kind {
+                ReactionKind::Applause | ReactionKind::Cheering | ReactionKind::Laughter => 1.0,
+                ReactionKind::Booing => 0.5,
+                ReactionKind::Music => 0.0,
+            };
+            weight * r.confidence
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Rhetoric score and the names of the devices found
+fn rhetoric(text: &str) -> (f32, Vec<String>) {
+    let mut score: f32 = 0.0;
+    let mut devices = Vec::new();
+
+    let clauses: Vec<String> = text
+        .split([',', ';', '.', '!', '?', ':'])
+        .map(|clause| clause.trim().to_lowercase())
+        .filter(|clause| !clause.is_empty())
+        .collect();
+
+    // Anaphora: three clauses opening with the same word also make a tricolon
+    let mut openers: HashMap<&str, usize> = HashMap::new();
+    for clause in &clauses {
+        if let Some(word) = clause.split_whitespace().next() {
+            *openers.entry(word).or_default() += 1;
+        }
+    }
+    let anaphora = openers.iter().any(|(word, n)| *n >= 3 && !matches!(*word, "a" | "the"));
+
+    if TRICOLON.is_match(text) || anaphora {
+        score += 0.5;
+        devices.push("tricolon".to_string());
+    }
+
+    if STRONG_CONTRAST.is_match(text) {
+        score += 0.4;
+        devices.push("contrast".to_string());
+    } else if WEAK_CONTRAST.is_match(text) {
+        score += 0.2;
+        devices.push("contrast".to_string());
+    }
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for word in content_terms(text) {
+        if word.len() >= 4 {
+            *counts.entry(word).or_default() += 1;
+        }
+    }
+    if counts.values().any(|&n| n >= 3) || openers.values().any(|&n| n == 2) {
+        score += 0.35;
+        devices.push("repetition".to_string());
+    }
+
+    (score.min(1.0), devices)
+}
+
+/// How well the run reads when lifted out of the speech
+fn readability(run: &[&Sentence]) -> f32 {
+    let mut score: f32 = 1.0;
+    let text = run.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+    let words: Vec<&str> = text.split_whitespace().collect();
+
+    if run[0].leans_on_previous() {
+        score -= 0.4;
+    }
+    if !run.last().unwrap().complete {
+        score -= 0.2;
+    }
+    let words_per_sentence = words.len() as f32 / run.len() as f32;
+    if words_per_sentence > 25.0 {
+        score -= 0.2;
+    }
+    let long_words = words.iter().filter(|w| w.chars().filter(|c| c.is_alphanumeric()).count() >= 9).count();
+    if !words.is_empty() && long_words as f32 / words.len() as f32 > 0.2 {
+        score -= 0.1;
+    }
+
+    score.clamp(0.0, 1.0)
+}
+
+fn word_set(text: &str) -> HashSet<String> {
+    content_terms(text).into_iter().collect()
+}
+
+/// Share of the smaller set found in the larger one
+fn overlap(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let smaller = a.len().min(b.len());
+    if smaller == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / smaller as f32
+}
@@ -0,0 +1,380 @@
+//! Audience reaction detection: applause, cheering, laughter, booing and
+//! music in the audio track.
+//!
+//! The signal is cut into one-second windows described by short-time
+//! spectral features: spectral flatness (noise-like vs tonal), how much
+//! energy sits above 2 kHz or below 500 Hz, spectral centroid, onset density
+//! (claps), envelope steadiness and 3-6 Hz envelope modulation (the "ha-ha-ha"
+//! rhythm of laughter). Each reaction kind gets a soft score from those
+//! features; neighbouring windows with the same winning kind are merged into
+//! one event, and where two kinds overlap the more confident one keeps the
+//! shared seconds. Speech is rejected mostly by its unsteady, pause-ridden
+//! envelope.
+
+use std::f32::consts::PI;
+
+use rayon::prelude::*;
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::audio::AudioSignal;
+
+const FRAME_SECONDS: f64 = 0.064;
+const HOP_SECONDS: f64 = 0.032;
+const WINDOW_SECONDS: f64 = 1.0;
+const WINDOW_HOP_SECONDS: f64 = 0.5;
+
+/// Analysis band; crowd noise above 8 kHz adds little and is often codec-cut
+const BAND_LOW_HZ: f32 = 100.0;
+const BAND_HIGH_HZ: f32 = 8_000.0;
+
+/// Windows quieter than this are never a reaction, dBFS
+const MIN_EVENT_DB: f32 = -35.0;
+/// A window needs at least this score for its best kind to count
+const MIN_SCORE: f32 = 0.5;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReactionKind {
+    Applause,
+    Cheering,
+    Laughter,
+    Booing,
+    Music,
+}
+
+impl ReactionKind {
+    /// Caption cue text, e.g. `[APPLAUSE]`
+    pub fn cue(&self) -> &'static str {
+        match self {
+            ReactionKind::Applause => "[APPLAUSE]",
+            ReactionKind::Cheering => "[CHEERING]",
+            ReactionKind::Laughter => "[LAUGHTER]",
+            ReactionKind::Booing => "[BOOING]",
+            ReactionKind::Music => "[MUSIC]",
+        }
+    }
+
+    /// Shortest run that is reported as an event, in seconds
+    fn min_duration(&self) -> f64 {
+        match self {
+            ReactionKind::Applause => 1.5,
+            ReactionKind::Cheering => 1.5,
+            ReactionKind::Laughter => 1.0,
+            ReactionKind::Booing => 1.5,
+            ReactionKind::Music => 4.0,
+        }
+    }
+}
+
+/// A non-speech audience or music event
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct AudienceReaction {
+    pub kind: ReactionKind,
+    pub start: f64,
+    pub end: f64,
+    /// Mean classifier score over the event, 0-1
+    pub confidence: f32,
+    /// Mean loudness over the event, dBFS
+    pub loudness_db: f32,
+}
+
+struct FrameFeatures {
+    rms: f32,
+    flatness: f32,
+    centroid_hz: f32,
+    high_ratio: f32,
+    low_ratio: f32,
+    flux: f32,
+}
+
+struct WindowFeatures {
+    loudness_db: f32,
+    flatness: f32,
+    centroid_hz: f32,
+    high_ratio: f32,
+    low_ratio: f32,
+    /// Spectral-flux peaks per second
+    onset_density: f32,
+    /// Coefficient of variation of the frame RMS envelope
+    envelope_cv: f32,
+    /// Envelope autocorrelation peak at 3-6 Hz
+    modulation: f32,
+}
+
+/// Reactions found in the whole signal, in time order
+pub fn detect(audio: &AudioSignal) -> Vec<AudienceReaction> {
+    let frames = frame_features(audio);
+    if frames.is_empty() {
+        return Vec::new();
+    }
+
+    let frames_per_window = (WINDOW_SECONDS / HOP_SECONDS).round() as usize;
+    let frames_per_window_hop = (WINDOW_HOP_SECONDS / HOP_SECONDS).round() as usize;
+
+    // Onsets are flux peaks well above the recording's typical flux
+    let mean_flux = frames.iter().map(|f| f.flux).sum::<f32>() / frames.len() as f32;
+    let flux_spread = (frames.iter().map(|f| (f.flux - mean_flux).powi(2)).sum::<f32>() / frames.len() as f32).sqrt();
+    let onset_threshold = mean_flux + flux_spread;
+
+    let mut windows: Vec<(f64, f64, ReactionKind, f32, f32)> = Vec::new();
+    let mut first = 0;
+    while first + frames_per_window <= frames.len() {
+        let window = window_features(&frames[first..first + frames_per_window], onset_threshold);
+        if let Some((kind, score)) = classify(&window) {
+            let start = first as f64 * HOP_SECONDS;
+            windows.push((start, start + WINDOW_SECONDS, kind, score, window.loudness_db));
+        }
+        first += frames_per_window_hop;
+    }
+
+    merge_windows(windows)
+}
+
+fn frame_features(audio: &AudioSignal) -> Vec<FrameFeatures> {
+    let rate = audio.sample_rate as f32;
+    let frame_len = (FRAME_SECONDS * audio.sample_rate as f64) as usize;
+    let hop = (HOP_SECONDS * audio.sample_rate as f64) as usize;
+    if hop == 0 || audio.samples.len() < frame_len {
+        return Vec::new();
+    }
+
+    let fft_len = frame_len.next_power_of_two();
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(fft_len);
+    let hann: Vec<f32> = (0..frame_len)
+        .map(|n| 0.5 - 0.5 * (2.0 * PI * n as f32 / (frame_len - 1) as f32).cos())
+        .collect();
+
+    let bin_hz = rate / fft_len as f32;
+    let low_bin = (BAND_LOW_HZ / bin_hz).ceil() as usize;
+    let high_bin = ((BAND_HIGH_HZ.min(rate / 2.0)) / bin_hz) as usize;
+    if high_bin <= low_bin {
+        return Vec::new();
+    }
+
+    let frame_count = (audio.samples.len() - frame_len) / hop + 1;
+    let spectra: Vec<(f32, Vec<f32>)> = (0..frame_count)
+        .into_par_iter()
+        .map(|i| {
+            let samples = &audio.samples[i * hop..i * hop + frame_len];
+            let rms = (samples.iter().map(|s| s * s).sum::<f32>() / frame_len as f32).sqrt();
+
+            let mut buffer: Vec<Complex<f32>> = samples
+                .iter()
+                .zip(&hann)
+                .map(|(s, w)| Complex::new(s * w, 0.0))
+                .chain(std::iter::repeat(Complex::new(0.0, 0.0)))
+                .take(fft_len)
+                .collect();
+            fft.process(&mut buffer);
+
+            let power = buffer[low_bin..high_bin].iter().map(|c| c.norm_sqr()).collect();
+            (rms, power)
+        })
+        .collect();
+
+    let mut frames = Vec::with_capacity(spectra.len());
+    let mut previous_magnitude: Option<Vec<f32>> = None;
+    for (rms, power) in spectra {
+        let total: f32 = power.iter().sum::<f32>() + 1e-12;
+        let mean_power = total / power.len() as f32;
+        // The floor keeps near-silent frames from reading as perfectly flat noise
+        let floor = mean_power * 1e-6 + 1e-12;
+        let log_mean = power.iter().map(|p| (p + floor).ln()).sum::<f32>() / power.len() as f32;
+        let flatness = (log_mean.exp() / mean_power).min(1.0);
+
+        let hz = |bin: usize| (low_bin + bin) as f32 * bin_hz;
+        let centroid_hz = power.iter().enumerate().map(|(b, p)| hz(b) * p).sum::<f32>() / total;
+        let high_ratio = power.iter().enumerate().filter(|(b, _)| hz(*b) >= 2_000.0).map(|(_, p)| p).sum::<f32>() / total;
+        let low_ratio = power.iter().enumerate().filter(|(b, _)| hz(*b) < 500.0).map(|(_, p)| p).sum::<f32>() / total;
+
+        // Half-wave rectified change in magnitude, relative to this frame's level
+        let magnitude: Vec<f32> = power.iter().map(|p| p.sqrt()).collect();
+        let flux = match &previous_magnitude {
+            Some(previous) => {
+                let rise: f32 = magnitude.iter().zip(previous).map(|(m, p)| (m - p).max(0.0)).sum();
+                rise / (magnitude.iter().sum::<f32>() + 1e-6)
+            }
+            None => 0.0,
+        };
+        previous_magnitude = Some(magnitude);
+
+        frames.push(FrameFeatures {
+            rms,
+            flatness,
+            centroid_hz,
+            high_ratio,
+            low_ratio,
+            flux,
+        });
+    }
+
+    frames
+}
+
+fn window_features(frames: &[FrameFeatures], onset_threshold: f32) -> WindowFeatures {
+    let count = frames.len() as f32;
+    let mean_rms = frames.iter().map(|f| f.rms).sum::<f32>() / count;
+    let rms_spread = (frames.iter().map(|f| (f.rms - mean_rms).powi(2)).sum::<f32>() / count).sqrt();
+
+    // Spectral shape is energy-weighted so pauses don't dilute it
+    let energy: f32 = frames.iter().map(|f| f.rms * f.rms).sum::<f32>() + 1e-12;
+    let weighted = |f: fn(&FrameFeatures) -> f32| frames.iter().map(|frame| f(frame) * frame.rms * frame.rms).sum::<f32>() / energy;
+
+    let onsets = (1..frames.len().saturating_sub(1))
+        .filter(|&i| {
+            frames[i].flux > onset_threshold
+                && frames[i].flux >= frames[i - 1].flux
+                && frames[i].flux > frames[i + 1].flux
+        })
+        .count();
+
+    WindowFeatures {
+        loudness_db: 20.0 * mean_rms.max(1e-6).log10(),
+        flatness: weighted(|f| f.flatness),
+        centroid_hz: weighted(|f| f.centroid_hz),
+        high_ratio: weighted(|f| f.high_ratio),
+        low_ratio: weighted(|f| f.low_ratio),
+        onset_density: onsets as f32 / WINDOW_SECONDS as f32,
+        envelope_cv: if mean_rms > 0.0 { rms_spread / mean_rms } else { 0.0 },
+        modulation: envelope_modulation(frames, mean_rms),
+    }
+}
+
+/// Strongest envelope periodicity between 3 and 6 Hz
+fn envelope_modulation(frames: &[FrameFeatures], mean_rms: f32) -> f32 {
+    let envelope: Vec<f32> = frames.iter().map(|f| f.rms - mean_rms).collect();
+    let energy: f32 = envelope.iter().map(|e| e * e).sum();
+    if energy <= 0.0 {
+        return 0.0;
+    }
+
+    let min_lag = (1.0 / (6.0 * HOP_SECONDS)).floor() as usize;
+    let max_lag = (1.0 / (3.0 * HOP_SECONDS)).ceil() as usize;
+    (min_lag..=max_lag.min(envelope.len() - 1))
+        .map(|lag| {
+            let cross: f32 = envelope.iter().zip(&envelope[lag..]).map(|(a, b)| a * b).sum();
+            cross / energy * envelope.len() as f32 / (envelope.len() - lag) as f32
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Best-scoring reaction kind for a window, if any clears `MIN_SCORE`
+fn classify(w: &WindowFeatures) -> Option<(ReactionKind, f32)> {
+    if w.loudness_db < MIN_EVENT_DB {
+        return None;
+    }
+
+    let steady = 1.0 - ramp(w.envelope_cv, 0.3, 0.6);
+    let tonal = 1.0 - ramp(w.flatness, 0.05, 0.15);
+    // Periodicity of an almost flat envelope is just noise
+    let modulation = w.modulation * ramp(w.envelope_cv, 0.15, 0.35);
+
+    let scores = [
+        (
+            ReactionKind::Applause,
+            ramp(w.flatness, 0.25, 0.4) * ramp(w.high_ratio, 0.3, 0.5) * steady * (0.7 + 0.3 * ramp(w.onset_density, 2.0, 6.0)),
+        ),
+        (
+            ReactionKind::Cheering,
+            ramp(w.flatness, 0.05, 0.15) * (1.0 - ramp(w.flatness, 0.3, 0.45)) * ramp(w.high_ratio, 0.15, 0.3) * steady,
+        ),
+        (
+            ReactionKind::Laughter,
+            ramp(modulation, 0.4, 0.7) * ramp(w.flatness, 0.03, 0.1) * (1.0 - ramp(w.low_ratio, 0.75, 0.9)),
+        ),
+        (
+            ReactionKind::Booing,
+            ramp(w.low_ratio, 0.4, 0.6) * (1.0 - ramp(w.centroid_hz, 500.0, 900.0)) * steady * tonal,
+        ),
+        (
+            ReactionKind::Music,
+            tonal * steady * ramp(w.high_ratio, 0.03, 0.1) * (1.0 - ramp(modulation, 0.3, 0.6)),
+        ),
+    ];
+
+    scores
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .filter(|(_, score)| *score >= MIN_SCORE)
+}
+
+/// Join overlapping windows of the same kind, drop runs that are too short,
+/// and give time claimed by two kinds to one of them
+fn merge_windows(windows: Vec<(f64, f64, ReactionKind, f32, f32)>) -> Vec<AudienceReaction> {
+    let mut events: Vec<(AudienceReaction, usize)> = Vec::new();
+
+    for (start, end, kind, score, loudness_db) in windows {
+        match events.last_mut() {
+            Some((event, count)) if event.kind == kind && start <= event.end => {
+                event.end = end;
+                event.confidence += score;
+                event.loudness_db += loudness_db;
+                *count += 1;
+            }
+            _ => events.push((
+                AudienceReaction {
+                    kind,
+                    start,
+                    end,
+                    confidence: score,
+                    loudness_db,
+                },
+                1,
+            )),
+        }
+    }
+
+    let events = events
+        .into_iter()
+        .map(|(mut event, count)| {
+            event.confidence /= count as f32;
+            event.loudness_db /= count as f32;
+            event
+        })
+        .filter(|event| event.end - event.start >= event.kind.min_duration())
+        .collect();
+    resolve_overlaps(events)
+}
+
+/// Windows overlap by half, so applause running into cheering leaves two
+/// events over the same seconds; the more confident one keeps the shared
+/// stretch, and one swallowed whole disappears
+fn resolve_overlaps(events: Vec<AudienceReaction>) -> Vec<AudienceReaction> {
+    let mut resolved: Vec<AudienceReaction> = Vec::with_capacity(events.len());
+    'events: for mut event in events {
+        while let Some(last) = resolved.last_mut() {
+            if event.start >= last.end {
+                break;
+            }
+            if last.kind == event.kind {
+                // Only meet once whatever lay between them is gone
+                let (a, b) = ((last.end - last.start) as f32, (event.end - event.start) as f32);
+                last.confidence = (last.confidence * a + event.confidence * b) / (a + b);
+                last.loudness_db = (last.loudness_db * a + event.loudness_db * b) / (a + b);
+                last.end = last.end.max(event.end);
+                continue 'events;
+            }
+            if last.confidence >= event.confidence {
+                event.start = last.end;
+                break;
+            }
+            last.end = event.start;
+            if last.end > last.start {
+                break;
+            }
+            resolved.pop();
+        }
+        if event.end > event.start {
+            resolved.push(event);
+        }
+    }
+    resolved
+}
+
+/// 0 below `low`, 1 above `high`, linear in between
+fn ramp(value: f32, low: f32, high: f32) -> f32 {
+    ((value - low) / (high - low)).clamp(0.0, 1.0)
+}
@@ -0,0 +1,406 @@
+//! Personal information redaction for constituent recordings.
+//!
+//! Emails, phone numbers, street addresses and SSN-like numbers are found by
+//! pattern, written or spoken ("jane at gmail dot com", "five five five, one
+//! two three four"); names come from the lists passed with `--redact-names`,
+//! one per line. Each hit is replaced by `[REDACTED]` in the segment text and
+//! its word timings before any analysis runs, so every stage and writer only
+//! ever sees the redacted text. The log records what kind of information was
+//! removed and when it was spoken, never the text itself.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::LazyLock;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::text::{char_span, is_stopword, normalize, token_spans, NormalizedText, TextSpan};
+use crate::{BasicSegment, WordTiming};
+
+/// What replaces redacted text
+pub const MARKER: &str = "[REDACTED]";
+
+/// Bleep tone frequency, Hz
+const BLEEP_FREQUENCY: f32 = 1000.0;
+/// Bleep amplitude, well below full scale
+const BLEEP_LEVEL: f32 = 0.3;
+/// Seconds of audio covered either side of a redacted span, since Whisper's
+/// word edges are only roughly right
+const AUDIO_PAD_SECONDS: f64 = 0.15;
+
+const DIGIT_WORDS: &str = "zero|oh|one|two|three|four|five|six|seven|eight|nine";
+
+/// "jane.doe@example.org"
+static EMAIL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}\b").unwrap());
+/// "jane doe at gmail dot com" as Whisper writes a spoken address
+static SPOKEN_EMAIL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[a-z0-9._-]+\s+at\s+[a-z0-9-]+(?:\s+dot\s+[a-z0-9-]+)*\s+dot\s+(?:com|org|net|gov|edu|us|io|info)\b")
+        .unwrap()
+});
+/// "(555) 123-4567", "555.123.4567", "+1 555 123 4567", "1-800-555-1234", "555-1234"
+static PHONE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+?\b1[\s.-]?)?(?:\(\d{3}\)\s?|\b\d{3}[\s.-])\d{3}[\s.-]\d{4}\b|\b\d{3}[.-]\d{4}\b").unwrap()
+});
+/// "123-45-6789", "123 45 6789"
+static SSN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b\d{3}([- ])\d{2}([- ])\d{4}\b").unwrap());
+/// Seven or more digits read out one by one
+static SPOKEN_DIGITS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r"(?i)\b(?:{0})(?:[\s,.-]+(?:{0})){{6,}}\b", DIGIT_WORDS)).unwrap()
+});
+/// "1234 North Elm Street", "55 W. 42nd St, Apt 4B"; street names must be
+/// capitalized so "20 years on this road" stays
+static STREET_ADDRESS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"\b\d{1,6}\s+(?:(?:[NSEW]\.?|North|South|East|West)\s+)?(?:(?:[A-Z][a-z'’]+|\d{1,3}(?:st|nd|rd|th))\s+){1,3}(?i:street|st|avenue|ave|road|rd|boulevard|blvd|drive|dr|lane|ln|court|ct|way|place|pl|terrace|circle|cir|parkway|pkwy|highway|hwy|trail)\b(?:,?\s+(?:(?i:apartment|apt|unit|suite)\.?\s*|#\s*)[A-Za-z0-9-]+\b)?",
+    )
+    .unwrap()
+});
+/// "P.O. Box 123"
+static PO_BOX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)\bP\.?\s?O\.?\s+Box\s+\d+\b").unwrap());
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RedactionKind {
+    Email,
+    Phone,
+    Address,
+    Ssn,
+    Name,
+}
+
+impl RedactionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RedactionKind::Email => "email",
+            RedactionKind::Phone => "phone",
+            RedactionKind::Address => "address",
+            RedactionKind::Ssn => "ssn",
+            RedactionKind::Name => "name",
+        }
+    }
+}
+
+/// One redacted span; the removed text is deliberately not kept
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct Redaction {
+    /// Id of the segment it was in
+    pub segment: usize,
+    pub kind: RedactionKind,
+    /// When the redacted words were spoken
+    pub start: f64,
+    pub end: f64,
+    /// True when the segment had no word timings lining up with its text
+    /// and the times are interpolated
+    #[serde(default)]
+    pub estimated: bool,
+    /// Where the `[REDACTED]` marker sits in the redacted segment text
+    pub span: TextSpan,
+}
+
+/// What `--redact-audio` puts over the redacted words
+#[derive(clap::ValueEnum, Debug, Clone, Copy)]
+pub enum AudioRedaction {
+    /// A 1 kHz tone
+    Bleep,
+    Silence,
+}
+
+pub struct Redactor {
+    /// Listed names, over normalized text
+    names: AhoCorasick,
+}
+
+impl Redactor {
+    /// Read the name lists: one name per line, `#` comments
+    pub async fn load(name_lists: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut names: Vec<String> = Vec::new();
+        for path in name_lists {
+            let path = path.as_ref();
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read name list {:?}", path))?;
+            names.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(normalize)
+                    .filter(|name| !name.is_empty()),
+            );
+        }
+        names.sort();
+        names.dedup();
+
+        let names = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .build(&names)
+            .context("Failed to compile redaction name list")?;
+        Ok(Self { names })
+    }
+
+    /// Replace personal information in the segment's text and words,
+    /// returning what was redacted
+    pub fn redact(&self, segment: &mut BasicSegment) -> Vec<Redaction> {
+        let hits = self.find(&segment.text);
+        if hits.is_empty() {
+            return Vec::new();
+        }
+
+        let tokens = token_spans(&segment.text);
+        let aligned = !segment.words.is_empty() && segment.words.len() == tokens.len();
+        let char_count = segment.text.chars().count().max(1);
+        let at = |c: usize| segment.start + (segment.end - segment.start) * c as f64 / char_count as f64;
+
+        let chars: Vec<char> = segment.text.chars().collect();
+        let mut text = String::with_capacity(segment.text.len());
+        let mut text_chars = 0;
+        let mut cursor = 0;
+        let mut redactions = Vec::new();
+        // Token ranges covered by each hit, merged where hits share a token
+        let mut groups: Vec<(usize, usize)> = Vec::new();
+
+        for (kind, span) in &hits {
+            let first = tokens.iter().position(|t| t.end > span.start).unwrap_or_default();
+            let last = tokens.iter().rposition(|t| t.start < span.end).unwrap_or(first).max(first);
+            let (start, end) = if aligned {
+                (segment.words[first].start, segment.words[last].end)
+            } else {
+                (at(span.start), at(span.end))
+            };
+            match groups.last_mut() {
+                Some(group) if group.1 >= first => group.1 = group.1.max(last),
+                _ => groups.push((first, last)),
+            }
+
+            text.extend(&chars[cursor..span.start]);
+            text_chars += span.start - cursor;
+            text.push_str(MARKER);
+            redactions.push(Redaction {
+                segment: segment.id,
+                kind: *kind,
+                start,
+                end,
+                estimated: !aligned,
+                span: TextSpan {
+                    start: text_chars,
+                    end: text_chars + MARKER.len(),
+                },
+            });
+            text_chars += MARKER.len();
+            cursor = span.end;
+        }
+        text.extend(&chars[cursor..]);
+
+        if aligned {
+            segment.words = merge_redacted_words(&segment.words, &groups, &text);
+        } else if !segment.words.is_empty() {
+            // No way to tell which word is which: drop every word that
+            // spells part of what was redacted
+            let removed: Vec<String> = hits
+                .iter()
+                .map(|(_, span)| normalize(&chars[span.start..span.end].iter().collect::<String>()))
+                .flat_map(|words| words.split(' ').map(str::to_string).collect::<Vec<_>>())
+                .filter(|piece| !piece.is_empty() && !is_stopword(piece))
+                .collect();
+            for word in &mut segment.words {
+                if normalize(&word.text).split(' ').any(|piece| removed.iter().any(|r| r == piece)) {
+                    word.text = MARKER.to_string();
+                }
+            }
+        }
+        segment.text = text;
+        redactions
+    }
+
+    /// Personal information in `text`, in order, none overlapping
+    fn find(&self, text: &str) -> Vec<(RedactionKind, TextSpan)> {
+        let mut candidates: Vec<(RedactionKind, TextSpan)> = Vec::new();
+        let patterns: [(&Regex, RedactionKind); 6] = [
+            (&EMAIL, RedactionKind::Email),
+            (&SPOKEN_EMAIL, RedactionKind::Email),
+            (&SSN, RedactionKind::Ssn),
+            (&PHONE, RedactionKind::Phone),
+            (&STREET_ADDRESS, RedactionKind::Address),
+            (&PO_BOX, RedactionKind::Address),
+        ];
+        for (pattern, kind) in patterns {
+            for m in pattern.find_iter(text) {
+                // "123 45 6789" needs matching separators to be an SSN
+                if kind == RedactionKind::Ssn && m.as_str().contains(' ') && m.as_str().contains('-') {
+                    continue;
+                }
+                candidates.push((kind, char_span(text, m.start(), m.end())));
+            }
+        }
+        for m in SPOKEN_DIGITS.find_iter(text) {
+            let digits = m.as_str().split(|c: char| !c.is_alphabetic()).filter(|w| !w.is_empty()).count();
+            let kind = if digits == 9 { RedactionKind::Ssn } else { RedactionKind::Phone };
+            candidates.push((kind, char_span(text, m.start(), m.end())));
+        }
+
+        let normalized = NormalizedText::new(text);
+        for m in self.names.find_overlapping_iter(&normalized.text) {
+            if normalized.is_word(m.start(), m.end()) {
+                candidates.push((RedactionKind::Name, normalized.span(m.start(), m.end())));
+            }
+        }
+
+        // Longest first, so a name inside a street address goes with the address
+        candidates.sort_by(|(_, a), (_, b)| (b.end - b.start).cmp(&(a.end - a.start)).then(a.start.cmp(&b.start)));
+        let mut chosen: Vec<(RedactionKind, TextSpan)> = Vec::new();
+        for (kind, span) in candidates {
+            if chosen.iter().all(|(_, c)| c.end <= span.start || span.end <= c.start) {
+                chosen.push((kind, span));
+            }
+        }
+        chosen.sort_by_key(|(_, span)| span.start);
+        chosen
+    }
+}
+
+/// Copy of the original media with the redacted spans bleeped or silenced.
+/// Only the audio is re-encoded, with the source's codec, bitrate, sample
+/// rate and channels; video and subtitle streams are copied untouched.
+pub async fn write_audio(source: &Path, redactions: &[Redaction], mode: AudioRedaction, path: &Path) -> Result<()> {
+    let mut spans: Vec<(f64, f64)> = redactions
+        .iter()
+        .map(|r| ((r.start - AUDIO_PAD_SECONDS).max(0.0), r.end + AUDIO_PAD_SECONDS))
+        .collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut merged: Vec<(f64, f64)> = Vec::with_capacity(spans.len());
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let inside = if merged.is_empty() {
+        "0".to_string()
+    } else {
+        merged
+            .iter()
+            .map(|(start, end)| format!("between(t,{:.3},{:.3})", start, end))
+            .collect::<Vec<_>>()
+            .join("+")
+    };
+    let cover = match mode {
+        AudioRedaction::Bleep => format!("{}*sin(2*PI*{}*t)", BLEEP_LEVEL, BLEEP_FREQUENCY),
+        AudioRedaction::Silence => "0".to_string(),
+    };
+    // Every channel keeps its own samples outside the spans
+    let filter = format!("aeval=exprs='if(gt({},0),{},val(ch))':channel_layout=same", inside, cover);
+
+    let (codec, bit_rate) = probe_audio(source).await?;
+    let mut command = Command::new("ffmpeg");
+    command
+        .arg("-v").arg("error")
+        .arg("-y")
+        .arg("-i").arg(source)
+        .arg("-map").arg("0:v?")
+        .arg("-map").arg("0:a")
+        .arg("-map").arg("0:s?")
+        .arg("-c").arg("copy")
+        .arg("-filter:a").arg(&filter)
+        .arg("-c:a").arg(encoder(&codec));
+    if let Some(bit_rate) = bit_rate {
+        command.arg("-b:a").arg(bit_rate);
+    }
+    let output = command
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("Failed to run ffmpeg (is it installed?)")?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffmpeg could not write {:?}: {}",
+            path,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Codec name and bitrate of the first audio stream
+async fn probe_audio(source: &Path) -> Result<(String, Option<String>)> {
+    let output = Command::new("ffprobe")
+        .arg("-v").arg("error")
+        .arg("-select_streams").arg("a:0")
+        .arg("-show_entries").arg("stream=codec_name,bit_rate")
+        .arg("-of").arg("default=noprint_wrappers=1")
+        .arg(source)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("Failed to run ffprobe (is it installed?)")?;
+
+    let report = String::from_utf8_lossy(&output.stdout);
+    let field = |name: &str| {
+        report
+            .lines()
+            .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && *value != "N/A")
+            .map(str::to_string)
+    };
+    match field("codec_name") {
+        Some(codec) if output.status.success() => Ok((codec, field("bit_rate"))),
+        _ => Err(anyhow!(
+            "ffprobe found no audio stream in {:?}: {}",
+            source,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+/// ffmpeg encoder for a decoder's codec name, where the two differ
+fn encoder(codec: &str) -> &str {
+    match codec {
+        "mp3" => "libmp3lame",
+        "opus" => "libopus",
+        "vorbis" => "libvorbis",
+        other => other,
+    }
+}
+
+/// One word per token of the redacted text: words inside a redacted group
+/// collapse into one carrying the group's time span
+fn merge_redacted_words(words: &[WordTiming], groups: &[(usize, usize)], text: &str) -> Vec<WordTiming> {
+    let mut merged = Vec::with_capacity(words.len());
+    let mut redacted = Vec::new();
+    let mut i = 0;
+    while i < words.len() {
+        match groups.iter().find(|(first, _)| *first == i) {
+            Some(&(first, last)) => {
+                redacted.push(merged.len());
+                merged.push(WordTiming {
+                    text: MARKER.to_string(),
+                    start: words[first].start,
+                    end: words[last].end,
+                    confidence: words[first..=last].iter().map(|w| w.confidence).fold(f32::INFINITY, f32::min),
+                });
+                i = last + 1;
+            }
+            None => {
+                merged.push(words[i].clone());
+                i += 1;
+            }
+        }
+    }
+
+    // Keep the punctuation around the marker: "[REDACTED]."
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if tokens.len() == merged.len() {
+        for index in redacted {
+            merged[index].text = tokens[index].to_string();
+        }
+    }
+    merged
+}
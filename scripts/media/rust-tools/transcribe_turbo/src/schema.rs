@@ -0,0 +1,220 @@
+//! Versioned JSON contract for `TranscriptResult`.
+//!
+//! Rules for changing the JSON output:
+//! - Adding a field is non-breaking: give it `#[serde(default)]` so older
+//!   files still load, and leave `SCHEMA_VERSION` alone.
+//! - Renaming, removing or retyping a field is breaking: bump
+//!   `SCHEMA_VERSION` and append a step to `MIGRATIONS` that rewrites the
+//!   previous shape into the new one.
+//! - Readers ignore unknown fields, so newer minor additions never break
+//!   older consumers.
+//!
+//! The published copy of the schema lives in `schema/transcript.schema.json`;
+//! regenerate it with `transcribe-turbo schema > schema/transcript.schema.json`
+//! whenever the types change.
+
+use anyhow::{anyhow, Context, Result};
+use jsonschema::JSONSchema;
+use serde_json::Value;
+
+use crate::TranscriptResult;
+
+pub const SCHEMA_VERSION: u32 = 6;
+
+/// `MIGRATIONS[n]` upgrades a version `n` document to version `n + 1`
+const MIGRATIONS: &[fn(&mut Value) -> Result<()>] = &[migrate_v0_to_v1, migrate_v1_to_v2, migrate_v2_to_v3, migrate_v3_to_v4, migrate_v4_to_v5, migrate_v5_to_v6];
+
+pub fn json_schema() -> Value {
+    let schema = schemars::schema_for!(TranscriptResult);
+    serde_json::to_value(schema).expect("JSON Schema serializes")
+}
+
+pub fn document_version(document: &Value) -> u32 {
+    document
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0)
+}
+
+/// Upgrade a transcript document of any known version to `SCHEMA_VERSION`
+pub fn migrate(mut document: Value) -> Result<Value> {
+    let version = document_version(&document);
+    if version > SCHEMA_VERSION {
+        return Err(anyhow!(
+            "Transcript uses schema version {}, but this build only understands up to {}",
+            version,
+            SCHEMA_VERSION
+        ));
+    }
+
+    for (from, step) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        step(&mut document).with_context(|| format!("Failed to migrate schema v{} to v{}", from, from + 1))?;
+        document["schema_version"] = Value::from(from as u32 + 1);
+    }
+
+    Ok(document)
+}
+
+/// Check a document against the current schema. Returns one message per violation.
+pub fn validate(document: &Value) -> Result<Vec<String>> {
+    let schema = json_schema();
+    let compiled = JSONSchema::compile(&schema)
+        .map_err(|e| anyhow!("Generated schema is invalid: {}", e))?;
+
+    let mut problems = Vec::new();
+    let version = document_version(document);
+    if version != SCHEMA_VERSION {
+        problems.push(format!(
+            "schema_version is {}, expected {} (run `transcribe-turbo schema --migrate` to upgrade)",
+            version, SCHEMA_VERSION
+        ));
+    }
+
+    if let Err(errors) = compiled.validate(document) {
+        for error in errors {
+            let path = error.instance_path.to_string();
+            let path = if path.is_empty() { "/".to_string() } else { path };
+            problems.push(format!("{}: {}", path, error));
+        }
+    }
+
+    Ok(problems)
+}
+
+/// Parse transcript JSON written by any version of this tool
+pub fn load_transcript(content: &str) -> Result<TranscriptResult> {
+    let document: Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let document = migrate(document)?;
+    serde_json::from_value(document).context("JSON does not match the transcript schema")
+}
+
+fn migrate_v0_to_v1(_document: &mut Value) -> Result<()> {
+    // v1 only introduced `schema_version` itself, which `migrate` stamps
+    Ok(())
+}
+
+fn migrate_v1_to_v2(document: &mut Value) -> Result<()> {
+    // v2 turned `political_keywords` from bare terms into `{ term, category, weight }`
+    let Some(segments) = document.get_mut("segments").and_then(Value::as_array_mut) else {
+        return Ok(());
+    };
+
+    for segment in segments {
+        let Some(keywords) = segment.get_mut("political_keywords").and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for keyword in keywords.iter_mut() {
+            if let Value::String(term) = keyword {
+                *keyword = serde_json::json!({
+                    "term": term,
+                    "category": "uncategorized",
+                    "weight": 1.0,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_v2_to_v3(document: &mut Value) -> Result<()> {
+    // v3 turned the sentiment label into `{ label, compound }` and the
+    // distribution into label shares plus mean scores. v2 never computed a
+    // score, so migrated segments get 0.0.
+    if let Some(segments) = document.get_mut("segments").and_then(Value::as_array_mut) {
+        for segment in segments {
+            if let Some(Value::String(label)) = segment.get("sentiment") {
+                segment["sentiment"] = serde_json::json!({ "label": label, "compound": 0.0 });
+            }
+        }
+    }
+
+    if let Some(analysis) = document.get_mut("political_analysis").filter(|a| a.is_object()) {
+        let shares = analysis.get("sentiment_distribution").cloned().unwrap_or_else(|| serde_json::json!({}));
+        if shares.get("shares").is_none() {
+            analysis["sentiment_distribution"] = serde_json::json!({
+                "shares": shares,
+                "mean_compound": 0.0,
+                "duration_weighted_mean": 0.0,
+                "speaker_means": {},
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_v3_to_v4(document: &mut Value) -> Result<()> {
+    // v4 replaced the templated `context` string of quotable moments with the
+    // surrounding sentences and capped `viral_potential` at 1.0
+    let quotes = document
+        .get_mut("political_analysis")
+        .and_then(|a| a.get_mut("quotable_moments"))
+        .and_then(Value::as_array_mut);
+    let Some(quotes) = quotes else {
+        return Ok(());
+    };
+
+    for quote in quotes {
+        if quote.get("context").is_some_and(Value::is_string) {
+            quote["context"] = serde_json::json!({ "before": null, "after": null });
+        }
+        if let Some(potential) = quote.get("viral_potential").and_then(Value::as_f64) {
+            quote["viral_potential"] = Value::from(potential.clamp(0.0, 1.0));
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_v4_to_v5(document: &mut Value) -> Result<()> {
+    // v5 made `stance` a lowercase enum (support / oppose / neutral); the new
+    // evidence fields all have defaults
+    let mentions = document
+        .get_mut("political_analysis")
+        .and_then(|a| a.get_mut("policy_mentions"))
+        .and_then(Value::as_array_mut);
+    let Some(mentions) = mentions else {
+        return Ok(());
+    };
+
+    for mention in mentions {
+        if let Some(stance) = mention.get("stance").and_then(Value::as_str) {
+            let stance = match stance.to_lowercase().as_str() {
+                "support" => "support",
+                "oppose" => "oppose",
+                _ => "neutral",
+            };
+            mention["stance"] = Value::from(stance);
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_v5_to_v6(document: &mut Value) -> Result<()> {
+    // v6 grouped talking points by issue area with timestamps. The old ones
+    // were bare segment texts with no time, so they land in one group at 0.
+    let Some(analysis) = document.get_mut("political_analysis").filter(|a| a.is_object()) else {
+        return Ok(());
+    };
+    let Some(points) = analysis.get("talking_points").and_then(Value::as_array) else {
+        return Ok(());
+    };
+    if !points.iter().all(Value::is_string) {
+        return Ok(());
+    }
+
+    let points: Vec<Value> = points
+        .iter()
+        .map(|text| serde_json::json!({ "text": text, "start": 0.0, "end": 0.0, "speaker": null, "score": 0.0 }))
+        .collect();
+    analysis["talking_points"] = if points.is_empty() {
+        serde_json::json!([])
+    } else {
+        serde_json::json!([{ "category": "general", "label": "General", "points": points }])
+    };
+
+    Ok(())
+}
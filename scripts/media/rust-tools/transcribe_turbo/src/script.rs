@@ -0,0 +1,583 @@
+//! Prepared remarks against what was actually said.
+//!
+//! The script given with `--script` (plain text or Markdown; headings and
+//! `[STAGE DIRECTIONS]` are dropped) is aligned to the transcript word by
+//! word with a global sequence alignment. Words that differ only by a typical
+//! recognition error ("recognise" / "recognize") still count as delivered.
+//! Each prepared sentence is then marked verbatim, paraphrased or skipped,
+//! and runs of unscripted words become ad-libs.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::LazyLock;
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::sentences::{sentence_pieces, span_times};
+use crate::text::{edit_distance, normalize, TextSpan};
+use crate::TranscriptSegment;
+
+/// Share of a sentence's words delivered for it to count as verbatim
+const VERBATIM_RATIO: f32 = 0.9;
+/// Below this share of its words a sentence counts as skipped
+const SKIPPED_RATIO: f32 = 0.25;
+/// Unscripted runs shorter than this are folded into the nearest sentence
+const MIN_ADLIB_WORDS: usize = 3;
+/// Alignment grid size limit (script words × spoken words), about 400 MB
+const MAX_ALIGNMENT_CELLS: usize = 400_000_000;
+
+/// Alignment scores; two different words never pair up, they are one
+/// missing and one added
+const SAME_SCORE: i32 = 4;
+const SIMILAR_SCORE: i32 = 2;
+const GAP_SCORE: i32 = -2;
+
+/// `[APPLAUSE]`, `(PAUSE)`: directions to the speaker, not remarks
+static STAGE_DIRECTION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[^\]]*\]|\([A-Z][A-Z\s.,'-]*\)").unwrap());
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Delivery {
+    Verbatim,
+    Paraphrased,
+    Skipped,
+    AdLibbed,
+}
+
+impl Delivery {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Delivery::Verbatim => "verbatim",
+            Delivery::Paraphrased => "paraphrased",
+            Delivery::Skipped => "skipped",
+            Delivery::AdLibbed => "ad-libbed",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Edit {
+    /// Said as written
+    Same,
+    /// In the script, not said
+    Missing,
+    /// Said, not in the script
+    Added,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct DiffWord {
+    pub text: String,
+    pub edit: Edit,
+}
+
+/// A prepared sentence as delivered, or an ad-lib
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct ScriptPassage {
+    pub delivery: Delivery,
+    /// Start and end of what was said; a sentence nothing of was said sits,
+    /// zero-length, where it was due
+    pub start: f64,
+    pub end: f64,
+    #[serde(default)]
+    pub speaker: Option<String>,
+    /// The prepared sentence; empty for ad-libs
+    pub script: String,
+    /// What was said
+    pub spoken: String,
+    /// Share of the prepared words delivered, 0-1
+    pub match_ratio: f32,
+    /// Word-by-word difference, in speaking order
+    #[serde(default)]
+    pub diff: Vec<DiffWord>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct ScriptComparison {
+    /// Words in the prepared text
+    pub script_words: usize,
+    /// Prepared words that were said
+    pub delivered_words: usize,
+    /// Share of the prepared words delivered, 0-1
+    pub coverage: f32,
+    /// Spoken words in ad-libbed passages
+    pub adlib_words: usize,
+    /// Prepared sentences and ad-libs, in speaking order
+    pub passages: Vec<ScriptPassage>,
+}
+
+/// Normalized word and the display tokens it spans: "health-care" gives two
+/// words from one token, and "health care" one word from two when the other
+/// side writes "healthcare"
+struct Word {
+    id: u32,
+    first_token: usize,
+    last_token: usize,
+}
+
+impl Word {
+    fn tokens(&self) -> std::ops::RangeInclusive<usize> {
+        self.first_token..=self.last_token
+    }
+}
+
+pub struct PreparedScript {
+    sentences: Vec<String>,
+    /// Display tokens and the sentence each is in
+    tokens: Vec<(String, usize)>,
+    words: Vec<String>,
+    word_tokens: Vec<usize>,
+}
+
+impl PreparedScript {
+    pub async fn load(path: &Path) -> Result<Self> {
+        let content = tokio::fs::read_to_string(path).await
+            .with_context(|| format!("Failed to read script {:?}", path))?;
+        let script = Self::parse(&content);
+        if script.words.is_empty() {
+            return Err(anyhow!("Script {:?} has no words", path));
+        }
+        Ok(script)
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut script = Self {
+            sentences: Vec::new(),
+            tokens: Vec::new(),
+            words: Vec::new(),
+            word_tokens: Vec::new(),
+        };
+
+        let mut paragraph = String::new();
+        let lines = content.lines().chain(std::iter::once(""));
+        for line in lines {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                let line = line.trim_start_matches(['>', '-', '*']).replace(['*', '_', '`'], "");
+                paragraph.push_str(&STAGE_DIRECTION.replace_all(&line, " "));
+                paragraph.push(' ');
+                continue;
+            }
+
+            // A blank line or heading ends the paragraph
+            let chars: Vec<char> = paragraph.chars().collect();
+            for (start, end, _) in sentence_pieces(&paragraph) {
+                let sentence: String = chars[start..end].iter().collect::<String>().trim().to_string();
+                if normalize(&sentence).is_empty() {
+                    continue;
+                }
+                let index = script.sentences.len();
+                for token in sentence.split_whitespace() {
+                    for word in normalize(token).split(' ').filter(|w| !w.is_empty()) {
+                        script.words.push(word.to_string());
+                        script.word_tokens.push(script.tokens.len());
+                    }
+                    script.tokens.push((token.to_string(), index));
+                }
+                script.sentences.push(sentence);
+            }
+            paragraph.clear();
+        }
+
+        script
+    }
+}
+
+/// A spoken token with its timing
+struct SpokenToken<'a> {
+    text: &'a str,
+    start: f64,
+    end: f64,
+    speaker: Option<&'a str>,
+}
+
+impl SpokenToken<'_> {
+    fn ends_sentence(&self) -> bool {
+        self.text.ends_with(['.', '!', '?'])
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Step {
+    /// Script word and spoken word, said as written (or close enough)
+    Same(usize, usize),
+    Missing(usize),
+    Added(usize),
+}
+
+impl Step {
+    fn script_word(&self) -> Option<usize> {
+        match *self {
+            Step::Same(s, _) | Step::Missing(s) => Some(s),
+            Step::Added(_) => None,
+        }
+    }
+
+    fn spoken_word(&self) -> Option<usize> {
+        match *self {
+            Step::Same(_, t) | Step::Added(t) => Some(t),
+            Step::Missing(_) => None,
+        }
+    }
+}
+
+/// Where a step goes
+#[derive(Clone, Copy, PartialEq)]
+enum Owner {
+    Sentence(usize),
+    AdLib,
+}
+
+pub fn compare(script: &PreparedScript, segments: &[TranscriptSegment]) -> Result<ScriptComparison> {
+    let mut spoken: Vec<SpokenToken> = Vec::new();
+    let mut spoken_words: Vec<(String, usize, usize)> = Vec::new();
+    for segment in segments {
+        let mut char_index = 0;
+        for raw in segment.text.split_inclusive(char::is_whitespace) {
+            let token = raw.trim_end();
+            let span = TextSpan {
+                start: char_index,
+                end: char_index + token.chars().count(),
+            };
+            char_index += raw.chars().count();
+            let normalized = normalize(token);
+            if normalized.is_empty() {
+                continue;
+            }
+            let (start, end) = span_times(segment, span);
+            for word in normalized.split(' ') {
+                spoken_words.push((word.to_string(), spoken.len(), spoken.len()));
+            }
+            spoken.push(SpokenToken {
+                text: token,
+                start,
+                end,
+                speaker: segment.speaker.as_deref(),
+            });
+        }
+    }
+    let script_words: Vec<(String, usize, usize)> = script
+        .words
+        .iter()
+        .zip(&script.word_tokens)
+        .map(|(word, &token)| (word.clone(), token, token))
+        .collect();
+
+    let script_vocabulary: HashSet<&str> = script.words.iter().map(String::as_str).collect();
+    let spoken_words = join_compounds(spoken_words, &script_vocabulary);
+    let spoken_vocabulary: HashSet<String> = spoken_words.iter().map(|(w, _, _)| w.clone()).collect();
+    let script_words = join_compounds(script_words, &spoken_vocabulary.iter().map(String::as_str).collect());
+
+    let mut ids: HashMap<String, u32> = HashMap::new();
+    let mut intern = |words: Vec<(String, usize, usize)>| -> Vec<Word> {
+        words
+            .into_iter()
+            .map(|(word, first_token, last_token)| {
+                let next = ids.len() as u32;
+                Word {
+                    id: *ids.entry(word).or_insert(next),
+                    first_token,
+                    last_token,
+                }
+            })
+            .collect()
+    };
+    let script_words = intern(script_words);
+    let spoken_words = intern(spoken_words);
+
+    let mut vocabulary: Vec<&str> = vec![""; ids.len()];
+    for (word, &id) in &ids {
+        vocabulary[id as usize] = word;
+    }
+    let steps = align(&script_words, &spoken_words, &vocabulary)?;
+    Ok(summarize(script, &script_words, &spoken, &spoken_words, &steps))
+}
+
+/// Join two neighbouring words into one where the other side has them as
+/// a single word: "health care" against a script that says "healthcare"
+fn join_compounds(words: Vec<(String, usize, usize)>, other: &HashSet<&str>) -> Vec<(String, usize, usize)> {
+    let mut joined: Vec<(String, usize, usize)> = Vec::with_capacity(words.len());
+    let mut words = words.into_iter().peekable();
+    while let Some((word, first, last)) = words.next() {
+        let compound = words.peek().map(|(next, _, _)| format!("{}{}", word, next));
+        match compound {
+            Some(compound) if !other.contains(word.as_str()) && other.contains(compound.as_str()) => {
+                let (_, _, next_last) = words.next().unwrap_or_default();
+                joined.push((compound, first, next_last.max(last)));
+            }
+            _ => joined.push((word, first, last)),
+        }
+    }
+    joined
+}
+
+/// Global alignment of the two word sequences (Needleman-Wunsch)
+fn align(script: &[Word], spoken: &[Word], vocabulary: &[&str]) -> Result<Vec<Step>> {
+    let (n, m) = (script.len(), spoken.len());
+    if (n + 1).saturating_mul(m + 1) > MAX_ALIGNMENT_CELLS {
+        return Err(anyhow!(
+            "Script ({} words) and transcript ({} words) are too long to align; split the recording",
+            n,
+            m
+        ));
+    }
+
+    let mut similar_cache: HashMap<(u32, u32), bool> = HashMap::new();
+    let mut pair_score = |a: u32, b: u32| -> Option<i32> {
+        if a == b {
+            return Some(SAME_SCORE);
+        }
+        let similar = *similar_cache
+            .entry((a, b))
+            .or_insert_with(|| similar(vocabulary[a as usize], vocabulary[b as usize]));
+        similar.then_some(SIMILAR_SCORE)
+    };
+
+    // 0 = same word, 1 = script word missing, 2 = spoken word added
+    let mut trace = vec![0u8; (n + 1) * (m + 1)];
+    let mut previous: Vec<i32> = (0..=m as i32).map(|j| j * GAP_SCORE).collect();
+    let mut current = vec![0; m + 1];
+    for step in trace.iter_mut().take(m + 1).skip(1) {
+        *step = 2;
+    }
+    for i in 1..=n {
+        current[0] = i as i32 * GAP_SCORE;
+        trace[i * (m + 1)] = 1;
+        for j in 1..=m {
+            let diagonal = pair_score(script[i - 1].id, spoken[j - 1].id).map(|score| previous[j - 1] + score);
+            let missing = previous[j] + GAP_SCORE;
+            let added = current[j - 1] + GAP_SCORE;
+            let (score, step) = match diagonal {
+                Some(diagonal) if diagonal >= missing && diagonal >= added => (diagonal, 0),
+                _ if missing >= added => (missing, 1),
+                _ => (added, 2),
+            };
+            current[j] = score;
+            trace[i * (m + 1) + j] = step;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    let mut steps = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (n, m);
+    while i > 0 || j > 0 {
+        match trace[i * (m + 1) + j] {
+            0 => {
+                i -= 1;
+                j -= 1;
+                steps.push(Step::Same(i, j));
+            }
+            1 => {
+                i -= 1;
+                steps.push(Step::Missing(i));
+            }
+            _ => {
+                j -= 1;
+                steps.push(Step::Added(j));
+            }
+        }
+    }
+    steps.reverse();
+    Ok(steps)
+}
+
+/// Close enough to be a recognition error: same first letter and at most
+/// one edit per four characters
+fn similar(a: &str, b: &str) -> bool {
+    let longest = a.chars().count().max(b.chars().count());
+    longest >= 4 && a.chars().next() == b.chars().next() && edit_distance(a, b) * 4 <= longest
+}
+
+fn summarize(
+    script: &PreparedScript,
+    script_words: &[Word],
+    spoken: &[SpokenToken],
+    spoken_words: &[Word],
+    steps: &[Step],
+) -> ScriptComparison {
+    let sentence_of = |step: &Step| step.script_word().map(|s| script.tokens[script_words[s].first_token].1);
+    let tokens_of = |run: &[Step]| -> Vec<usize> {
+        let mut tokens: Vec<usize> = run.iter().filter_map(Step::spoken_word).flat_map(|t| spoken_words[t].tokens()).collect();
+        tokens.dedup();
+        tokens
+    };
+
+    // Unscripted words finishing a spoken sentence go with the prepared
+    // sentence before them; other runs are ad-libs if long enough and
+    // otherwise go with the sentence before (after, at the very start)
+    let mut owners: Vec<Owner> = Vec::with_capacity(steps.len());
+    let mut i = 0;
+    while i < steps.len() {
+        if let Some(sentence) = sentence_of(&steps[i]) {
+            owners.push(Owner::Sentence(sentence));
+            i += 1;
+            continue;
+        }
+        let run_end = (i..steps.len()).find(|&k| sentence_of(&steps[k]).is_some()).unwrap_or(steps.len());
+        let before = steps[..i].iter().rev().find_map(sentence_of);
+        let after = steps[run_end..].iter().find_map(sentence_of);
+
+        let mid_sentence = steps[..i]
+            .iter()
+            .rev()
+            .find_map(Step::spoken_word)
+            .is_some_and(|t| !spoken[spoken_words[t].last_token].ends_sentence());
+        let mut tail_end = i;
+        if mid_sentence && before.is_some() {
+            while tail_end < run_end {
+                let word = steps[tail_end].spoken_word().map(|t| &spoken_words[t]);
+                tail_end += 1;
+                if word.is_some_and(|w| spoken[w.last_token].ends_sentence()) {
+                    break;
+                }
+            }
+        }
+        owners.extend(std::iter::repeat_n(Owner::Sentence(before.unwrap_or_default()), tail_end - i));
+
+        let owner = match (before, after) {
+            _ if tokens_of(&steps[tail_end..run_end]).len() >= MIN_ADLIB_WORDS => Owner::AdLib,
+            (Some(b), _) => Owner::Sentence(b),
+            (None, Some(a)) => Owner::Sentence(a),
+            (None, None) => Owner::AdLib,
+        };
+        owners.extend(std::iter::repeat_n(owner, run_end - tail_end));
+        i = run_end;
+    }
+
+    // Steps of each sentence (an ad-lib in the middle doesn't split it) and
+    // of each ad-lib, with when the group was due
+    let mut groups: Vec<(Owner, Vec<Step>, f64)> = Vec::new();
+    let mut last_time = spoken.first().map(|t| t.start).unwrap_or_default();
+    for (k, step) in steps.iter().enumerate() {
+        let owner = owners[k];
+        let existing = match owner {
+            Owner::Sentence(_) => groups.iter().rposition(|(o, _, _)| *o == owner),
+            Owner::AdLib if k > 0 && owners[k - 1] == Owner::AdLib => Some(groups.len() - 1),
+            Owner::AdLib => None,
+        };
+        match existing {
+            Some(group) => groups[group].1.push(*step),
+            None => groups.push((owner, vec![*step], last_time)),
+        }
+        if let Some(t) = step.spoken_word() {
+            last_time = spoken[spoken_words[t].last_token].end;
+        }
+    }
+
+    let mut passages = Vec::new();
+    for (owner, run, due) in groups {
+        let tokens = tokens_of(&run);
+        let spoken_text = tokens.iter().map(|&t| spoken[t].text).collect::<Vec<_>>().join(" ");
+        let (start, end) = match (tokens.first(), tokens.last()) {
+            (Some(&first), Some(&last)) => (spoken[first].start, spoken[last].end),
+            _ => (due, due),
+        };
+        let speaker = tokens.first().and_then(|&t| spoken[t].speaker).map(str::to_string);
+        let diff = diff_words(&run, script, script_words, spoken, spoken_words);
+
+        passages.push(match owner {
+            Owner::AdLib => ScriptPassage {
+                delivery: Delivery::AdLibbed,
+                start,
+                end,
+                speaker,
+                script: String::new(),
+                spoken: spoken_text,
+                match_ratio: 0.0,
+                diff,
+            },
+            Owner::Sentence(sentence) => {
+                let prepared = run.iter().filter(|s| s.script_word().is_some()).count();
+                let same = run.iter().filter(|s| matches!(s, Step::Same(..))).count();
+                let added = run.iter().filter(|s| matches!(s, Step::Added(_))).count();
+                let ratio = if prepared > 0 { same as f32 / prepared as f32 } else { 0.0 };
+                let delivery = if ratio >= VERBATIM_RATIO && added <= (prepared / 10).max(1) {
+                    Delivery::Verbatim
+                } else if ratio < SKIPPED_RATIO {
+                    Delivery::Skipped
+                } else {
+                    Delivery::Paraphrased
+                };
+                ScriptPassage {
+                    delivery,
+                    start,
+                    end,
+                    speaker,
+                    script: script.sentences[sentence].clone(),
+                    spoken: spoken_text,
+                    match_ratio: ratio,
+                    diff,
+                }
+            }
+        });
+    }
+
+    let delivered_words = steps.iter().filter(|s| matches!(s, Step::Same(..))).count();
+    ScriptComparison {
+        script_words: script_words.len(),
+        delivered_words,
+        coverage: delivered_words as f32 / script_words.len().max(1) as f32,
+        adlib_words: passages
+            .iter()
+            .filter(|p| p.delivery == Delivery::AdLibbed)
+            .map(|p| p.spoken.split_whitespace().count())
+            .sum(),
+        passages,
+    }
+}
+
+/// Word-level difference of a run of steps, one entry per display token;
+/// between two words said as written, what was dropped comes before what
+/// replaced it
+fn diff_words(
+    run: &[Step],
+    script: &PreparedScript,
+    script_words: &[Word],
+    spoken: &[SpokenToken],
+    spoken_words: &[Word],
+) -> Vec<DiffWord> {
+    let mut diff: Vec<DiffWord> = Vec::new();
+    let mut missing: Vec<usize> = Vec::new();
+    let mut added: Vec<usize> = Vec::new();
+    let mut same: Vec<usize> = Vec::new();
+
+    let flush = |diff: &mut Vec<DiffWord>, missing: &mut Vec<usize>, added: &mut Vec<usize>| {
+        missing.dedup();
+        added.dedup();
+        diff.extend(missing.drain(..).map(|t| DiffWord {
+            text: script.tokens[t].0.clone(),
+            edit: Edit::Missing,
+        }));
+        diff.extend(added.drain(..).map(|t| DiffWord {
+            text: spoken[t].text.to_string(),
+            edit: Edit::Added,
+        }));
+    };
+
+    for step in run {
+        match *step {
+            Step::Same(_, t) => {
+                flush(&mut diff, &mut missing, &mut added);
+                for token in spoken_words[t].tokens() {
+                    // "health-care" is two words but one token
+                    if same.last() != Some(&token) {
+                        same.push(token);
+                        diff.push(DiffWord {
+                            text: spoken[token].text.to_string(),
+                            edit: Edit::Same,
+                        });
+                    }
+                }
+            }
+            Step::Missing(s) => missing.extend(script_words[s].tokens()),
+            Step::Added(t) => added.extend(spoken_words[t].tokens()),
+        }
+    }
+    flush(&mut diff, &mut missing, &mut added);
+    diff
+}
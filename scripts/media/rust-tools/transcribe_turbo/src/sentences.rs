@@ -0,0 +1,186 @@
+//! Sentence segmentation across transcript segments.
+//!
+//! Whisper and caption files cut text wherever a line fills up, so a segment
+//! can hold half a sentence or several. Here segments are re-cut on terminal
+//! punctuation; a sentence may run across segment boundaries as long as the
+//! speaker stays the same and the pause between them is short. Keyword hits
+//! travel with the text, re-based onto the sentence.
+
+use crate::taxonomy::KeywordHit;
+use crate::text::{char_slice, TextSpan};
+use crate::TranscriptSegment;
+
+/// A pause longer than this ends a sentence even without punctuation
+pub const MAX_SENTENCE_GAP_SECONDS: f64 = 2.0;
+
+/// Openers that lean on the previous sentence ("And that's why...")
+const DANGLING_OPENERS: &[&str] = &[
+    "and", "but", "so", "because", "which", "this", "that", "these", "those", "it", "they", "he",
+    "she", "also", "then", "or",
+];
+
+pub struct Sentence<'a> {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<&'a str>,
+    /// Taxonomy hits inside the sentence, spans as char offsets into `text`
+    pub keywords: Vec<KeywordHit>,
+    /// Sum of emphasis × seconds, for a duration-weighted mean
+    pub emphasis_seconds: f32,
+    /// Ends in terminal punctuation rather than at a pause or speaker change
+    pub complete: bool,
+}
+
+impl Sentence<'_> {
+    pub fn duration(&self) -> f64 {
+        (self.end - self.start).max(0.0)
+    }
+
+    /// Sum of taxonomy weight over every keyword occurrence
+    pub fn keyword_weight(&self) -> f32 {
+        self.keywords.iter().map(|hit| hit.weight * hit.spans.len() as f32).sum()
+    }
+
+    /// Whether the sentence only makes sense after the one before it
+    pub fn leans_on_previous(&self) -> bool {
+        let first = self.text.split(|c: char| !c.is_alphanumeric() && c != '\'').find(|w| !w.is_empty());
+        first.is_some_and(|w| DANGLING_OPENERS.contains(&w.to_lowercase().as_str()))
+    }
+
+    /// Whether two sentences belong to one uninterrupted stretch of speech
+    pub fn continues_into(&self, next: &Sentence) -> bool {
+        self.speaker == next.speaker && next.start - self.end <= MAX_SENTENCE_GAP_SECONDS
+    }
+}
+
+pub fn split(segments: &[TranscriptSegment]) -> Vec<Sentence<'_>> {
+    let mut sentences: Vec<Sentence> = Vec::new();
+    let mut open: Option<Sentence> = None;
+
+    for segment in segments {
+        let speaker = segment.speaker.as_deref();
+        if let Some(current) = open.take() {
+            let continues = current.speaker == speaker && segment.start - current.end <= MAX_SENTENCE_GAP_SECONDS;
+            if continues {
+                open = Some(current);
+            } else {
+                sentences.push(current);
+            }
+        }
+
+        for (start_char, end_char, complete) in sentence_pieces(&segment.text) {
+            let raw = slice_chars(&segment.text, start_char, end_char);
+            let text = raw.trim();
+            if text.is_empty() {
+                continue;
+            }
+            // Char offset of the trimmed text within the segment
+            let text_start = start_char + raw.chars().take_while(|c| c.is_whitespace()).count();
+            let text_end = text_start + text.chars().count();
+
+            let (start, end) = span_times(segment, TextSpan { start: start_char, end: end_char });
+            let emphasis_seconds = segment.emphasis_level.unwrap_or(0.0) * (end - start) as f32;
+
+            let sentence = open.get_or_insert_with(|| Sentence {
+                text: String::new(),
+                start,
+                end,
+                speaker,
+                keywords: Vec::new(),
+                emphasis_seconds: 0.0,
+                complete: false,
+            });
+            if !sentence.text.is_empty() {
+                sentence.text.push(' ');
+            }
+            let offset = sentence.text.chars().count();
+            sentence.text.push_str(text);
+            sentence.end = end;
+            sentence.emphasis_seconds += emphasis_seconds;
+            sentence.complete = complete;
+
+            for hit in &segment.political_keywords {
+                let spans: Vec<TextSpan> = hit
+                    .spans
+                    .iter()
+                    .filter(|s| s.start >= text_start && s.end <= text_end)
+                    .map(|s| TextSpan {
+                        start: s.start - text_start + offset,
+                        end: s.end - text_start + offset,
+                    })
+                    .collect();
+                if spans.is_empty() {
+                    continue;
+                }
+                match sentence.keywords.iter_mut().find(|k| k.term == hit.term && k.category == hit.category) {
+                    Some(existing) => existing.spans.extend(spans),
+                    None => sentence.keywords.push(KeywordHit { spans, ..hit.clone() }),
+                }
+            }
+
+            if complete {
+                sentences.extend(open.take());
+            }
+        }
+    }
+
+    sentences.extend(open);
+    sentences
+}
+
+/// Character ranges of the sentences in `text`, and whether each ends in
+/// terminal punctuation
+pub fn sentence_pieces(text: &str) -> Vec<(usize, usize, bool)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    for i in 0..chars.len() {
+        if !matches!(chars[i], '.' | '!' | '?') {
+            continue;
+        }
+        let at_break = chars.get(i + 1).is_none_or(|c| c.is_whitespace() || *c == '"' || *c == '”');
+        if !at_break || is_abbreviation(&chars[start..i]) {
+            continue;
+        }
+        pieces.push((start, i + 1, true));
+        start = i + 1;
+    }
+    if chars[start..].iter().any(|c| c.is_alphanumeric()) {
+        pieces.push((start, chars.len(), false));
+    }
+
+    pieces
+}
+
+/// "Mr." / "U.S." / "St." style periods that don't end a sentence
+fn is_abbreviation(before: &[char]) -> bool {
+    let word: String = before.iter().rev().take_while(|c| !c.is_whitespace()).collect::<Vec<_>>().into_iter().rev().collect();
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    matches!(
+        word.to_ascii_lowercase().as_str(),
+        "mr" | "mrs" | "ms" | "dr" | "st" | "sen" | "rep" | "gov" | "gen" | "jr" | "sr" | "vs" | "etc"
+    ) || (word.len() == 1 && word != "I" && word.chars().all(|c| c.is_ascii_uppercase()))
+        || word.contains('.')
+}
+
+/// Start and end time of a character range of a segment, from word timings
+/// when they line up with the text and by interpolation otherwise
+pub fn span_times(segment: &TranscriptSegment, span: TextSpan) -> (f64, f64) {
+    let tokens_before = |char_index: usize| slice_chars(&segment.text, 0, char_index).split_whitespace().count();
+    if !segment.words.is_empty() && segment.words.len() == segment.text.split_whitespace().count() {
+        let first = tokens_before(span.start).min(segment.words.len() - 1);
+        let last = tokens_before(span.end).clamp(first + 1, segment.words.len());
+        return (segment.words[first].start, segment.words[last - 1].end);
+    }
+
+    let char_count = segment.text.chars().count();
+    let duration = segment.end - segment.start;
+    let at = |c: usize| segment.start + duration * c as f64 / char_count.max(1) as f64;
+    (at(span.start), at(span.end))
+}
+
+fn slice_chars(text: &str, start: usize, end: usize) -> &str {
+    char_slice(text, TextSpan { start, end })
+}
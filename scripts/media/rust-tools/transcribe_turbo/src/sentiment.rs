@@ -0,0 +1,250 @@
+//! Lexicon-based sentiment scoring in the style of VADER (Hutto & Gilbert, 2014).
+//!
+//! Each lexicon word contributes its valence, adjusted by up to three
+//! preceding words: intensifiers ("very", "barely") scale it, negations
+//! ("not", "never", "isn't") flip and dampen it. Words after "but" outweigh
+//! the words before it, ALL-CAPS words in otherwise mixed-case text are
+//! boosted, and "!" / "??" push the total further from zero. The sum is
+//! squashed into a compound score in [-1, 1].
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const BUILTIN_LEXICON: &str = include_str!("../data/sentiment_lexicon.tsv");
+
+/// Compound scores at or beyond these count as positive / negative
+const POSITIVE_THRESHOLD: f32 = 0.05;
+const NEGATIVE_THRESHOLD: f32 = -0.05;
+
+const BOOST_INCREMENT: f32 = 0.293;
+const BOOST_DECREMENT: f32 = -0.293;
+const CAPS_INCREMENT: f32 = 0.733;
+const NEGATION_SCALAR: f32 = -0.74;
+/// Normalizes the raw sum: compound = sum / sqrt(sum² + ALPHA)
+const ALPHA: f32 = 15.0;
+
+const NEGATIONS: &[&str] = &[
+    "not", "no", "never", "nor", "none", "nobody", "nothing", "neither", "nowhere", "cannot",
+    "without", "aint", "hardly", "rarely", "seldom",
+];
+
+const INTENSIFIERS: &[&str] = &[
+    "absolutely", "amazingly", "completely", "deeply", "enormously", "entirely", "especially",
+    "exceptionally", "extremely", "fully", "greatly", "highly", "hugely", "incredibly",
+    "intensely", "most", "more", "particularly", "purely", "quite", "really", "remarkably",
+    "so", "substantially", "thoroughly", "totally", "tremendously", "truly", "unbelievably",
+    "utterly", "very",
+];
+
+const DAMPENERS: &[&str] = &[
+    "almost", "barely", "less", "little", "marginally", "occasionally", "partly", "scarcely",
+    "slightly", "somewhat", "sort",
+];
+
+/// Sentiment of one piece of text
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct Sentiment {
+    /// positive / negative / neutral
+    pub label: String,
+    /// Normalized score from -1 (most negative) to 1 (most positive)
+    pub compound: f32,
+}
+
+impl Sentiment {
+    pub fn from_compound(compound: f32) -> Self {
+        let label = if compound >= POSITIVE_THRESHOLD {
+            "positive"
+        } else if compound <= NEGATIVE_THRESHOLD {
+            "negative"
+        } else {
+            "neutral"
+        };
+        Sentiment {
+            label: label.to_string(),
+            compound,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SentimentAnalyzer {
+    lexicon: HashMap<String, f32>,
+}
+
+impl Default for SentimentAnalyzer {
+    fn default() -> Self {
+        let mut analyzer = SentimentAnalyzer {
+            lexicon: HashMap::new(),
+        };
+        analyzer.apply(BUILTIN_LEXICON).expect("built-in sentiment lexicon is valid");
+        analyzer
+    }
+}
+
+impl SentimentAnalyzer {
+    /// Built-in lexicon with each override file layered on in order
+    pub async fn load(overrides: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut analyzer = SentimentAnalyzer::default();
+
+        for path in overrides {
+            let path = path.as_ref();
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read sentiment lexicon {:?}", path))?;
+            analyzer.apply(&content)
+                .with_context(|| format!("Failed to parse sentiment lexicon {:?}", path))?;
+        }
+
+        Ok(analyzer)
+    }
+
+    fn apply(&mut self, content: &str) -> Result<()> {
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (word, valence) = line
+                .split_once('\t')
+                .ok_or_else(|| anyhow!("line {}: expected `word<TAB>valence`", number + 1))?;
+            let valence: f32 = valence
+                .trim()
+                .parse()
+                .map_err(|_| anyhow!("line {}: invalid valence {:?}", number + 1, valence.trim()))?;
+            self.lexicon.insert(word.trim().to_lowercase(), valence);
+        }
+        Ok(())
+    }
+
+    pub fn analyze(&self, text: &str) -> Sentiment {
+        let tokens = tokenize(text);
+        if tokens.is_empty() {
+            return Sentiment::from_compound(0.0);
+        }
+        let lower: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+
+        // Caps only signal emphasis when the rest of the text isn't shouting too
+        let shouted = tokens.iter().filter(|t| is_shouted(t)).count();
+        let caps_differential = shouted > 0 && shouted < tokens.len();
+
+        let mut valences: Vec<f32> = Vec::with_capacity(tokens.len());
+        for (i, word) in lower.iter().enumerate() {
+            let followed_by_of = lower.get(i + 1).is_some_and(|next| next == "of");
+            let Some(&base) = self.lexicon.get(word.as_str()) else {
+                valences.push(0.0);
+                continue;
+            };
+            // "kind of" is a hedge, not kindness
+            if is_booster(word) || (word == "kind" && followed_by_of) {
+                valences.push(0.0);
+                continue;
+            }
+
+            let mut valence = base;
+            if caps_differential && is_shouted(tokens[i]) {
+                valence += CAPS_INCREMENT * valence.signum();
+            }
+
+            for distance in 1..=3 {
+                let Some(j) = i.checked_sub(distance) else { break };
+                if self.lexicon.contains_key(lower[j].as_str()) {
+                    continue;
+                }
+
+                let scalar = booster_scalar(tokens[j], &lower[j], valence, caps_differential);
+                valence += match distance {
+                    1 => scalar,
+                    2 => scalar * 0.95,
+                    _ => scalar * 0.9,
+                };
+
+                // "never so good" intensifies rather than negates
+                let never_so = distance > 1
+                    && lower[j] == "never"
+                    && matches!(lower[i - 1].as_str(), "so" | "this");
+                if never_so {
+                    valence *= 1.25;
+                } else if is_negation(&lower[j]) {
+                    valence *= NEGATION_SCALAR;
+                }
+            }
+
+            valences.push(valence);
+        }
+
+        // The clause after "but" carries the speaker's point
+        if let Some(but) = lower.iter().position(|w| w == "but") {
+            for (i, valence) in valences.iter_mut().enumerate() {
+                if i < but {
+                    *valence *= 0.5;
+                } else if i > but {
+                    *valence *= 1.5;
+                }
+            }
+        }
+
+        let mut sum: f32 = valences.iter().sum();
+        if sum != 0.0 {
+            sum += punctuation_emphasis(text) * sum.signum();
+        }
+
+        let compound = (sum / (sum * sum + ALPHA).sqrt()).clamp(-1.0, 1.0);
+        Sentiment::from_compound(compound)
+    }
+}
+
+/// Words with surrounding punctuation stripped; inner apostrophes and hyphens stay
+fn tokenize(text: &str) -> Vec<&str> {
+    text.split_whitespace()
+        .map(|t| t.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn is_shouted(token: &str) -> bool {
+    token.chars().count() > 1
+        && token.chars().any(char::is_alphabetic)
+        && token.chars().filter(|c| c.is_alphabetic()).all(char::is_uppercase)
+}
+
+fn is_negation(word: &str) -> bool {
+    NEGATIONS.contains(&word) || word.ends_with("n't") || word.ends_with("n’t")
+}
+
+fn is_booster(word: &str) -> bool {
+    INTENSIFIERS.contains(&word) || DAMPENERS.contains(&word)
+}
+
+/// How much a preceding intensifier or dampener shifts `valence`
+fn booster_scalar(token: &str, word: &str, valence: f32, caps_differential: bool) -> f32 {
+    let mut scalar = if INTENSIFIERS.contains(&word) {
+        BOOST_INCREMENT
+    } else if DAMPENERS.contains(&word) {
+        BOOST_DECREMENT
+    } else {
+        return 0.0;
+    };
+
+    if valence < 0.0 {
+        scalar = -scalar;
+    }
+    if caps_differential && is_shouted(token) {
+        scalar += CAPS_INCREMENT * valence.signum();
+    }
+    scalar
+}
+
+/// Extra magnitude from exclamation marks (up to four) and repeated question marks
+fn punctuation_emphasis(text: &str) -> f32 {
+    let exclamations = text.matches('!').count().min(4) as f32 * 0.292;
+    let questions = match text.matches('?').count() {
+        0 | 1 => 0.0,
+        n @ 2..=3 => n as f32 * 0.18,
+        _ => 0.96,
+    };
+    exclamations + questions
+}
+
@@ -0,0 +1,365 @@
+//! Stance toward the policy issues a speaker mentions.
+//!
+//! Every sentence that mentions a taxonomy issue becomes a policy mention,
+//! one per issue area. The stance (support / oppose / neutral) comes from a
+//! `StanceClassifier`; the built-in one is rule-based and reads its cue
+//! phrases from `data/stance_lexicon.toml`, so it runs offline. Another
+//! backend, such as a local model, only has to implement the trait.
+//!
+//! The lexicon classifier finds cue phrases in the sentence ("we must
+//! protect", "repeal", "I will fight against"), flips any that follow a
+//! negation ("won't cut") or an oppose cue of their own ("fight against the
+//! repeal of"), and discounts each by its distance in words from the issue
+//! term. The side with the larger total wins; confidence grows with
+//! the margin between the sides and the amount of evidence.
+
+use std::path::Path;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::keyword_matcher::inflections;
+use crate::sentences::Sentence;
+use crate::taxonomy::{KeywordHit, Taxonomy};
+use crate::text::{char_slice, normalize, NormalizedText};
+use crate::PolicyMention;
+
+const BUILTIN_LEXICON: &str = include_str!("../data/stance_lexicon.toml");
+
+const DEFAULT_CUE_WEIGHT: f32 = 1.0;
+/// How many words before a cue a negation, or an oppose cue, still flips it
+const NEGATION_WINDOW: usize = 3;
+/// "won't cut" is weaker evidence of support than "protect"
+const NEGATED_CUE_SCALE: f32 = 0.8;
+/// A cue this many words away from the issue term counts half
+const HALF_WEIGHT_DISTANCE: f32 = 6.0;
+/// Less cue weight than this reads as neutral
+const MIN_EVIDENCE: f32 = 0.3;
+/// Support and oppose totals closer than this share of the evidence read as
+/// neutral (mixed)
+const MIN_MARGIN: f32 = 0.2;
+const NEUTRAL_CONFIDENCE: f32 = 0.5;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Stance {
+    Support,
+    Oppose,
+    Neutral,
+}
+
+impl Stance {
+    fn opposite(self) -> Self {
+        match self {
+            Stance::Support => Stance::Oppose,
+            Stance::Oppose => Stance::Support,
+            Stance::Neutral => Stance::Neutral,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Stance::Support => "support",
+            Stance::Oppose => "oppose",
+            Stance::Neutral => "neutral",
+        }
+    }
+}
+
+pub struct StanceVerdict {
+    pub stance: Stance,
+    pub confidence: f32,
+    /// The phrases the stance was read from, as spoken
+    pub cues: Vec<String>,
+}
+
+/// Decides the speaker's stance toward one issue in one sentence
+pub trait StanceClassifier: Send + Sync {
+    /// `hits` are the sentence's mentions of a single taxonomy category
+    fn classify(&self, sentence: &Sentence, hits: &[&KeywordHit]) -> StanceVerdict;
+}
+
+/// One policy mention per issue area per sentence, in speech order
+pub fn detect(sentences: &[Sentence], taxonomy: &Taxonomy, classifier: &dyn StanceClassifier) -> Vec<PolicyMention> {
+    let mut mentions = Vec::new();
+
+    for sentence in sentences {
+        let mut hits: Vec<&KeywordHit> = sentence.keywords.iter().collect();
+        hits.sort_by_key(|hit| hit.spans.iter().map(|s| s.start).min());
+
+        let mut categories: Vec<&str> = Vec::new();
+        for hit in &hits {
+            if !categories.contains(&hit.category.as_str()) {
+                categories.push(&hit.category);
+            }
+        }
+
+        for category in categories {
+            let issue: Vec<&KeywordHit> = hits.iter().copied().filter(|h| h.category == category).collect();
+            let verdict = classifier.classify(sentence, &issue);
+
+            mentions.push(PolicyMention {
+                policy: taxonomy
+                    .category(category)
+                    .map(|c| c.label.clone())
+                    .unwrap_or_else(|| category.to_string()),
+                category: category.to_string(),
+                terms: issue.iter().map(|h| h.term.clone()).collect(),
+                stance: verdict.stance,
+                confidence: verdict.confidence,
+                timestamp: sentence.start,
+                end: sentence.end,
+                speaker: sentence.speaker.map(str::to_string),
+                evidence: sentence.text.clone(),
+                cues: verdict.cues,
+            });
+        }
+    }
+
+    mentions
+}
+
+#[derive(Deserialize, Default)]
+struct LexiconFile {
+    #[serde(default)]
+    negations: Vec<String>,
+    #[serde(default)]
+    support: Vec<CueSpec>,
+    #[serde(default)]
+    oppose: Vec<CueSpec>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CueSpec {
+    Plain(String),
+    Detailed { phrase: String, weight: Option<f32> },
+}
+
+struct Cue {
+    phrase: String,
+    stance: Stance,
+    weight: f32,
+}
+
+/// Rule-based classifier over support / oppose cue phrases
+pub struct LexiconStanceClassifier {
+    cues: Vec<Cue>,
+    negations: Vec<String>,
+    automaton: AhoCorasick,
+    /// Cue index for every automaton pattern
+    targets: Vec<usize>,
+}
+
+impl LexiconStanceClassifier {
+    /// Built-in lexicon with each override file layered on in order
+    pub async fn load(overrides: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut files = vec![toml::from_str(BUILTIN_LEXICON).context("Built-in stance lexicon is invalid")?];
+
+        for path in overrides {
+            let path = path.as_ref();
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read stance lexicon {:?}", path))?;
+            files.push(toml::from_str(&content)
+                .with_context(|| format!("Failed to parse stance lexicon {:?}", path))?);
+        }
+
+        Self::from_files(files)
+    }
+
+    fn from_files(files: Vec<LexiconFile>) -> Result<Self> {
+        let mut cues: Vec<Cue> = Vec::new();
+        let mut negations: Vec<String> = Vec::new();
+
+        for file in files {
+            let removed: Vec<String> = file.remove.iter().map(|p| normalize(p)).collect();
+            cues.retain(|c| !removed.contains(&c.phrase));
+            negations.retain(|n| !removed.contains(n));
+
+            for negation in file.negations {
+                let negation = normalize(&negation);
+                if !negation.is_empty() && !negations.contains(&negation) {
+                    negations.push(negation);
+                }
+            }
+
+            let specs = file
+                .support
+                .into_iter()
+                .map(|spec| (spec, Stance::Support))
+                .chain(file.oppose.into_iter().map(|spec| (spec, Stance::Oppose)));
+            for (spec, stance) in specs {
+                let (phrase, weight) = match spec {
+                    CueSpec::Plain(phrase) => (phrase, None),
+                    CueSpec::Detailed { phrase, weight } => (phrase, weight),
+                };
+                let phrase = normalize(&phrase);
+                if phrase.is_empty() {
+                    continue;
+                }
+                // A later file can move a phrase to the other side
+                cues.retain(|c| c.phrase != phrase);
+                cues.push(Cue {
+                    phrase,
+                    stance,
+                    weight: weight.unwrap_or(DEFAULT_CUE_WEIGHT),
+                });
+            }
+        }
+
+        let mut patterns: Vec<String> = Vec::new();
+        let mut targets: Vec<usize> = Vec::new();
+        for (index, cue) in cues.iter().enumerate() {
+            for form in inflections(&cue.phrase) {
+                if !patterns.contains(&form) {
+                    patterns.push(form);
+                    targets.push(index);
+                }
+            }
+        }
+
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .build(&patterns)
+            .context("Failed to compile stance cues")?;
+
+        Ok(Self {
+            cues,
+            negations,
+            automaton,
+            targets,
+        })
+    }
+}
+
+impl StanceClassifier for LexiconStanceClassifier {
+    fn classify(&self, sentence: &Sentence, hits: &[&KeywordHit]) -> StanceVerdict {
+        let normalized = NormalizedText::new(&sentence.text);
+        let words: Vec<(usize, usize)> = word_ranges(&normalized.text);
+        let word_at = |byte: usize| words.partition_point(|&(_, end)| end <= byte);
+
+        // Words of the sentence that belong to the issue terms themselves
+        let issue_words: Vec<usize> = words
+            .iter()
+            .enumerate()
+            .filter(|(_, &(start, end))| {
+                let span = normalized.span(start, end);
+                hits.iter().flat_map(|h| &h.spans).any(|s| s.start < span.end && span.start < s.end)
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        let mut matches: Vec<(usize, usize, usize)> = self
+            .automaton
+            .find_overlapping_iter(&normalized.text)
+            .filter(|m| normalized.is_word(m.start(), m.end()))
+            .map(|m| (m.start(), m.end(), self.targets[m.pattern().as_usize()]))
+            .collect();
+        matches.sort_by(|a, b| a.0.cmp(&b.0).then((b.1 - b.0).cmp(&(a.1 - a.0))));
+
+        // Cue matches that don't overlap each other or the issue terms
+        let mut placed: Vec<(usize, usize, usize, usize, &Cue)> = Vec::new();
+        let mut covered_until = 0;
+        for (start, end, cue) in matches {
+            if start < covered_until {
+                continue;
+            }
+            covered_until = end;
+
+            let (first, last) = (word_at(start), word_at(end - 1));
+            // "border security" is the issue, not a "secure" cue
+            if issue_words.iter().any(|w| (first..=last).contains(w)) {
+                continue;
+            }
+            placed.push((start, end, first, last, &self.cues[cue]));
+        }
+
+        let chars: Vec<char> = sentence.text.chars().collect();
+        let mut support = 0.0;
+        let mut oppose = 0.0;
+        let mut found: Vec<(Stance, String)> = Vec::new();
+        // Start of an oppose cue that turns the next one around
+        let mut opposed_from: Option<usize> = None;
+        for (index, &(start, end, first, last, cue)) in placed.iter().enumerate() {
+            // "fight against the repeal of", "stop the cuts to": opposing an
+            // attack on the issue supports it, like a negation
+            let against_attack = placed.get(index + 1).is_some_and(|&(_, _, next_first, next_last, next)| {
+                cue.stance == Stance::Oppose
+                    && next.stance == Stance::Oppose
+                    && next_first - last <= NEGATION_WINDOW
+                    && issue_words.iter().any(|&w| w > next_last)
+            });
+            if against_attack {
+                opposed_from = Some(start);
+                continue;
+            }
+
+            let distance = issue_words
+                .iter()
+                .map(|&w| if w < first { first - w } else { w.saturating_sub(last) })
+                .min()
+                .unwrap_or(0);
+            let mut weight = cue.weight / (1.0 + distance as f32 / HALF_WEIGHT_DISTANCE);
+            let mut stance = cue.stance;
+            let mut phrase_start = start;
+
+            let negation = (first.saturating_sub(NEGATION_WINDOW)..first)
+                .rev()
+                .find(|&w| self.negations.iter().any(|n| *n == normalized.text[words[w].0..words[w].1]))
+                .map(|w| words[w].0);
+            if let Some(negation_start) = opposed_from.take().or(negation) {
+                weight *= NEGATED_CUE_SCALE;
+                stance = stance.opposite();
+                phrase_start = negation_start;
+            }
+
+            match stance {
+                Stance::Support => support += weight,
+                _ => oppose += weight,
+            }
+            // Widen to whole words of the original, so "won't cut" keeps its "won'"
+            let mut span = normalized.span(phrase_start, end);
+            while span.start > 0 && !chars[span.start - 1].is_whitespace() {
+                span.start -= 1;
+            }
+            let phrase = char_slice(&sentence.text, span).to_string();
+            if !found.iter().any(|(s, p)| *s == stance && normalize(p) == normalize(&phrase)) {
+                found.push((stance, phrase));
+            }
+        }
+
+        let total = support + oppose;
+        let margin = if total > 0.0 { (support - oppose).abs() / total } else { 0.0 };
+        if total < MIN_EVIDENCE || margin < MIN_MARGIN {
+            return StanceVerdict {
+                stance: Stance::Neutral,
+                confidence: NEUTRAL_CONFIDENCE,
+                cues: found.into_iter().map(|(_, phrase)| phrase).collect(),
+            };
+        }
+
+        let stance = if support > oppose { Stance::Support } else { Stance::Oppose };
+        StanceVerdict {
+            stance,
+            confidence: 0.5 + 0.45 * margin * (1.0 - (-total).exp()),
+            cues: found.into_iter().filter(|(s, _)| *s == stance).map(|(_, phrase)| phrase).collect(),
+        }
+    }
+}
+
+/// Byte ranges of the space-separated words of normalized text
+fn word_ranges(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for word in text.split(' ') {
+        if !word.is_empty() {
+            ranges.push((start, start + word.len()));
+        }
+        start += word.len() + 1;
+    }
+    ranges
+}
@@ -0,0 +1,308 @@
+//! Extractive summary and talking points.
+//!
+//! Sentences are ranked with LexRank (Erkan & Radev, 2004): each sentence is
+//! a TF-IDF vector, edges carry the cosine similarity between sentences, and
+//! the stationary distribution of a random walk over that graph says which
+//! sentences the rest of the speech keeps coming back to. The walk teleports
+//! towards sentences with more taxonomy weight, so policy content outranks
+//! equally central small talk.
+//!
+//! The summary is the top sentences by rank, skipping near-duplicates, put
+//! back in speech order; talking points are the top-ranked sentences for
+//! each issue area.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rayon::prelude::*;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::sentences::Sentence;
+use crate::taxonomy::Taxonomy;
+use crate::text::content_terms;
+
+/// Longest summary offered; shorter ones are its top-ranked bullets
+pub const MAX_BULLETS: usize = 10;
+const ABSTRACT_MAX_WORDS: usize = 80;
+const TALKING_POINTS_PER_ISSUE: usize = 3;
+
+/// Sentences shorter than this ("Thank you.") never make the summary
+const MIN_SENTENCE_WORDS: usize = 6;
+/// Edges weaker than this are noise from shared common words
+const MIN_SIMILARITY: f32 = 0.1;
+/// Rank kept by sentences that open mid-thought ("She never complained...")
+const DANGLING_PENALTY: f32 = 0.7;
+/// Candidates this similar to a chosen sentence repeat it
+const MAX_REDUNDANCY: f32 = 0.5;
+const DAMPING: f32 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f32 = 1e-6;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+pub struct Summary {
+    /// Up to ten bullets in speech order; the 3- and 5-bullet summaries are
+    /// the bullets with `rank` <= 3 and <= 5
+    pub bullets: Vec<SummaryBullet>,
+    /// One paragraph built from the highest-ranked sentences
+    #[serde(rename = "abstract")]
+    pub abstract_text: String,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct SummaryBullet {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<String>,
+    /// 1 for the most central sentence
+    pub rank: usize,
+    /// LexRank score relative to the best sentence, 0-1
+    pub score: f32,
+}
+
+/// Talking points for one issue area, best first
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct TalkingPointGroup {
+    pub category: String,
+    pub label: String,
+    pub points: Vec<TalkingPoint>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct TalkingPoint {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    pub speaker: Option<String>,
+    pub score: f32,
+}
+
+impl Summary {
+    /// The `count` highest-ranked bullets, in speech order
+    pub fn top(&self, count: usize) -> impl Iterator<Item = &SummaryBullet> {
+        self.bullets.iter().filter(move |b| b.rank <= count)
+    }
+}
+
+/// LexRank centrality of every sentence, normalized so the best is 1.0
+pub fn lexrank(sentences: &[Sentence]) -> Vec<f32> {
+    let n = sentences.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let vectors = tf_idf(sentences);
+    let similarity: Vec<Vec<(usize, f32)>> = (0..n)
+        .into_par_iter()
+        .map(|i| {
+            (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (j, cosine(&vectors[i], &vectors[j])))
+                .filter(|&(_, s)| s >= MIN_SIMILARITY)
+                .collect()
+        })
+        .collect();
+    let row_sums: Vec<f32> = similarity.iter().map(|row| row.iter().map(|(_, s)| s).sum()).collect();
+
+    // Teleport towards sentences that carry issue content
+    let bias: Vec<f32> = sentences.iter().map(|s| 1.0 + s.keyword_weight()).collect();
+    let bias_total: f32 = bias.iter().sum();
+    let teleport: Vec<f32> = bias.iter().map(|b| b / bias_total).collect();
+
+    let mut rank = teleport.clone();
+    for _ in 0..MAX_ITERATIONS {
+        // Sentences with no edges hand their mass back to the teleport vector
+        let dangling: f32 = (0..n).filter(|&i| row_sums[i] == 0.0).map(|i| rank[i]).sum();
+        let mut next: Vec<f32> = teleport.iter().map(|t| (1.0 - DAMPING + DAMPING * dangling) * t).collect();
+        for (i, row) in similarity.iter().enumerate() {
+            if row_sums[i] == 0.0 {
+                continue;
+            }
+            for &(j, s) in row {
+                next[j] += DAMPING * rank[i] * s / row_sums[i];
+            }
+        }
+
+        let change: f32 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    let best = rank.iter().cloned().fold(0.0, f32::max);
+    if best > 0.0 {
+        for r in &mut rank {
+            *r /= best;
+        }
+    }
+    rank
+}
+
+pub fn summarize(sentences: &[Sentence], ranks: &[f32]) -> Summary {
+    let vectors = tf_idf(sentences);
+
+    let mut order: Vec<usize> = (0..sentences.len())
+        .filter(|&i| word_count(&sentences[i].text) >= MIN_SENTENCE_WORDS)
+        .collect();
+    let standalone = |i: usize| if sentences[i].leans_on_previous() { ranks[i] * DANGLING_PENALTY } else { ranks[i] };
+    order.sort_by(|&a, &b| standalone(b).total_cmp(&standalone(a)));
+
+    let mut chosen: Vec<usize> = Vec::new();
+    for i in order {
+        if chosen.len() >= MAX_BULLETS {
+            break;
+        }
+        if chosen.iter().all(|&c| cosine(&vectors[i], &vectors[c]) <= MAX_REDUNDANCY) {
+            chosen.push(i);
+        }
+    }
+
+    // The abstract takes the best sentences that fit, read in speech order
+    let mut abstract_indices: Vec<usize> = Vec::new();
+    let mut words = 0;
+    for &i in &chosen {
+        let count = word_count(&sentences[i].text);
+        if words + count > ABSTRACT_MAX_WORDS && !abstract_indices.is_empty() {
+            break;
+        }
+        words += count;
+        abstract_indices.push(i);
+    }
+    abstract_indices.sort_unstable();
+    let abstract_text = abstract_indices
+        .iter()
+        .map(|&i| sentences[i].text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut bullets: Vec<SummaryBullet> = chosen
+        .iter()
+        .enumerate()
+        .map(|(position, &i)| bullet(&sentences[i], position + 1, ranks[i]))
+        .collect();
+    bullets.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    Summary { bullets, abstract_text }
+}
+
+/// Best sentences per issue area, areas ordered by their strongest point
+pub fn talking_points(sentences: &[Sentence], ranks: &[f32], taxonomy: &Taxonomy) -> Vec<TalkingPointGroup> {
+    let mut groups: Vec<TalkingPointGroup> = Vec::new();
+
+    for (i, sentence) in sentences.iter().enumerate() {
+        if word_count(&sentence.text) < MIN_SENTENCE_WORDS {
+            continue;
+        }
+        let mut weights: BTreeMap<&str, f32> = BTreeMap::new();
+        for hit in &sentence.keywords {
+            *weights.entry(&hit.category).or_default() += hit.weight * hit.spans.len() as f32;
+        }
+
+        for (category, weight) in weights {
+            let index = match groups.iter().position(|g| g.category == category) {
+                Some(index) => index,
+                None => {
+                    groups.push(TalkingPointGroup {
+                        category: category.to_string(),
+                        label: taxonomy
+                            .category(category)
+                            .map(|c| c.label.clone())
+                            .unwrap_or_else(|| category.to_string()),
+                        points: Vec::new(),
+                    });
+                    groups.len() - 1
+                }
+            };
+            groups[index].points.push(TalkingPoint {
+                text: sentence.text.clone(),
+                start: sentence.start,
+                end: sentence.end,
+                speaker: sentence.speaker.map(str::to_string),
+                // Centrality, nudged towards sentences that are squarely about the issue
+                score: ranks[i] * (1.0 - (-weight).exp()),
+            });
+        }
+    }
+
+    for group in &mut groups {
+        group.points.sort_by(|a, b| b.score.total_cmp(&a.score));
+        group.points.truncate(TALKING_POINTS_PER_ISSUE);
+    }
+    groups.sort_by(|a, b| b.points[0].score.total_cmp(&a.points[0].score));
+    groups
+}
+
+fn bullet(sentence: &Sentence, rank: usize, score: f32) -> SummaryBullet {
+    SummaryBullet {
+        text: sentence.text.clone(),
+        start: sentence.start,
+        end: sentence.end,
+        speaker: sentence.speaker.map(str::to_string),
+        rank,
+        score,
+    }
+}
+
+/// Sparse, L2-normalized TF-IDF vector per sentence, sorted by term id
+fn tf_idf(sentences: &[Sentence]) -> Vec<Vec<(usize, f32)>> {
+    let mut vocabulary: HashMap<String, usize> = HashMap::new();
+    let counts: Vec<HashMap<usize, f32>> = sentences
+        .iter()
+        .map(|sentence| {
+            let mut counts = HashMap::new();
+            for term in content_terms(&sentence.text) {
+                let next_id = vocabulary.len();
+                let id = *vocabulary.entry(term).or_insert(next_id);
+                *counts.entry(id).or_insert(0.0) += 1.0;
+            }
+            counts
+        })
+        .collect();
+
+    let mut document_frequency = vec![0usize; vocabulary.len()];
+    for sentence in &counts {
+        for &id in sentence.keys() {
+            document_frequency[id] += 1;
+        }
+    }
+    let n = sentences.len() as f32;
+
+    counts
+        .into_iter()
+        .map(|sentence| {
+            let mut vector: Vec<(usize, f32)> = sentence
+                .into_iter()
+                .map(|(id, tf)| (id, tf * (((1.0 + n) / (1.0 + document_frequency[id] as f32)).ln() + 1.0)))
+                .collect();
+            let norm = vector.iter().map(|(_, w)| w * w).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                for (_, w) in &mut vector {
+                    *w /= norm;
+                }
+            }
+            vector.sort_unstable_by_key(|&(id, _)| id);
+            vector
+        })
+        .collect()
+}
+
+fn cosine(a: &[(usize, f32)], b: &[(usize, f32)]) -> f32 {
+    let (mut i, mut j, mut dot) = (0, 0, 0.0);
+    while i < a.len() && j < b.len() {
+        match a[i].0.cmp(&b[j].0) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                dot += a[i].1 * b[j].1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    dot
+}
+
+fn word_count(text: &str) -> usize {
+    text.split_whitespace().count()
+}
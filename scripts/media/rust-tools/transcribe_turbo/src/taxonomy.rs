@@ -0,0 +1,297 @@
+//! Political keyword taxonomy: issue categories, weighted terms, aliases and
+//! exclusion phrases.
+//!
+//! The built-in taxonomy lives in `data/taxonomy.toml`; see that file for the
+//! override format used by campaign files.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::text::TextSpan;
+
+const BUILTIN_TAXONOMY: &str = include_str!("../data/taxonomy.toml");
+
+/// Category that plain line-based keyword files feed into
+const LEGACY_CATEGORY: &str = "general";
+
+#[derive(Debug, Clone)]
+pub struct Taxonomy {
+    pub categories: Vec<Category>,
+    pub exclusions: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Category {
+    pub name: String,
+    pub label: String,
+    pub terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Term {
+    pub term: String,
+    pub weight: f32,
+    pub aliases: Vec<String>,
+    /// Also match plural and -ed/-ing forms of the last word
+    pub inflect: bool,
+    /// The term or aliases as spelled in capitals in the taxonomy ("ICE",
+    /// "ACA"), normalized; these only match where the speaker's text is in
+    /// capitals too, so "thin ice" is not immigration
+    pub acronyms: Vec<String>,
+}
+
+/// A taxonomy term found in a segment
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct KeywordHit {
+    /// Canonical term, even when an alias was what matched
+    pub term: String,
+    pub category: String,
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+    /// Where the term (or an alias/inflection of it) occurs in the segment text
+    #[serde(default)]
+    pub spans: Vec<TextSpan>,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Default)]
+struct TaxonomyFile {
+    #[serde(default)]
+    categories: BTreeMap<String, CategorySpec>,
+    #[serde(default)]
+    exclusions: Vec<String>,
+    /// Global synonyms, `"alias" = "canonical term"`
+    #[serde(default)]
+    aliases: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct CategorySpec {
+    label: Option<String>,
+    /// Default weight for plain-string terms in this category
+    weight: Option<f32>,
+    #[serde(default)]
+    terms: Vec<TermSpec>,
+    #[serde(default)]
+    remove: Vec<String>,
+    #[serde(default)]
+    replace: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TermSpec {
+    Plain(String),
+    Detailed {
+        term: String,
+        weight: Option<f32>,
+        #[serde(default)]
+        aliases: Vec<String>,
+        inflect: Option<bool>,
+    },
+}
+
+impl Default for Taxonomy {
+    fn default() -> Self {
+        let file: TaxonomyFile = toml::from_str(BUILTIN_TAXONOMY).expect("built-in taxonomy is valid TOML");
+        let mut taxonomy = Taxonomy {
+            categories: Vec::new(),
+            exclusions: Vec::new(),
+        };
+        taxonomy.apply(file);
+        taxonomy
+    }
+}
+
+impl Taxonomy {
+    /// Built-in defaults with each override file layered on in order
+    pub async fn load(overrides: &[impl AsRef<Path>]) -> Result<Self> {
+        let mut taxonomy = Taxonomy::default();
+
+        for path in overrides {
+            let path = path.as_ref();
+            if !path.exists() {
+                tracing::warn!("Keywords file {:?} not found, skipping", path);
+                continue;
+            }
+            let content = tokio::fs::read_to_string(path).await
+                .with_context(|| format!("Failed to read keywords file {:?}", path))?;
+            let file = parse_file(path, &content)
+                .with_context(|| format!("Failed to parse keywords file {:?}", path))?;
+            taxonomy.apply(file);
+        }
+
+        Ok(taxonomy)
+    }
+
+    pub fn category(&self, name: &str) -> Option<&Category> {
+        self.categories.iter().find(|c| c.name == name)
+    }
+
+    fn apply(&mut self, file: TaxonomyFile) {
+        for (name, spec) in file.categories {
+            let name = name.to_lowercase();
+            let index = match self.categories.iter().position(|c| c.name == name) {
+                Some(index) => index,
+                None => {
+                    self.categories.push(Category {
+                        label: title_case(&name),
+                        name: name.clone(),
+                        terms: Vec::new(),
+                    });
+                    self.categories.len() - 1
+                }
+            };
+            let category = &mut self.categories[index];
+
+            if let Some(label) = spec.label {
+                category.label = label;
+            }
+            if spec.replace {
+                category.terms.clear();
+            }
+            let removed: Vec<String> = spec.remove.iter().map(|t| normalize_term(t)).collect();
+            category.terms.retain(|t| !removed.contains(&t.term));
+
+            let default_weight = spec.weight.unwrap_or(1.0);
+            for term_spec in spec.terms {
+                let term = match term_spec {
+                    TermSpec::Plain(term) => Term {
+                        term: normalize_term(&term),
+                        weight: default_weight,
+                        aliases: Vec::new(),
+                        inflect: true,
+                        acronyms: acronyms(std::iter::once(&term)),
+                    },
+                    TermSpec::Detailed { term, weight, aliases, inflect } => Term {
+                        term: normalize_term(&term),
+                        weight: weight.unwrap_or(default_weight),
+                        aliases: aliases.iter().map(|a| normalize_term(a)).collect(),
+                        inflect: inflect.unwrap_or(true),
+                        acronyms: acronyms(std::iter::once(&term).chain(&aliases)),
+                    },
+                };
+                if term.term.is_empty() {
+                    continue;
+                }
+
+                match category.terms.iter_mut().find(|t| t.term == term.term) {
+                    Some(existing) => {
+                        existing.weight = term.weight;
+                        existing.inflect = term.inflect;
+                        for alias in term.aliases {
+                            if !existing.aliases.contains(&alias) {
+                                existing.aliases.push(alias);
+                            }
+                        }
+                        for acronym in term.acronyms {
+                            if !existing.acronyms.contains(&acronym) {
+                                existing.acronyms.push(acronym);
+                            }
+                        }
+                    }
+                    None => category.terms.push(term),
+                }
+            }
+        }
+
+        for (spelled, canonical) in file.aliases {
+            let alias = normalize_term(&spelled);
+            let canonical = normalize_term(&canonical);
+            let target = self
+                .categories
+                .iter_mut()
+                .flat_map(|c| c.terms.iter_mut())
+                .find(|t| t.term == canonical);
+            match target {
+                Some(term) => {
+                    if !term.aliases.contains(&alias) {
+                        term.aliases.push(alias.clone());
+                    }
+                    if acronyms([&spelled]).contains(&alias) && !term.acronyms.contains(&alias) {
+                        term.acronyms.push(alias);
+                    }
+                }
+                None => tracing::warn!("Alias {:?} points at unknown term {:?}", alias, canonical),
+            }
+        }
+
+        for exclusion in file.exclusions {
+            let exclusion = normalize_term(&exclusion);
+            if !exclusion.is_empty() && !self.exclusions.contains(&exclusion) {
+                self.exclusions.push(exclusion);
+            }
+        }
+    }
+}
+
+fn parse_file(path: &Path, content: &str) -> Result<TaxonomyFile> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "toml" => toml::from_str(content).map_err(|e| anyhow!(e)),
+        "yaml" | "yml" => serde_yaml::from_str(content).map_err(|e| anyhow!(e)),
+        _ => Ok(parse_legacy(content)),
+    }
+}
+
+/// The original format: one term per line, `#` comments, all filed under "general"
+fn parse_legacy(content: &str) -> TaxonomyFile {
+    let terms = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| TermSpec::Plain(line.to_string()))
+        .collect();
+
+    let mut file = TaxonomyFile::default();
+    file.categories.insert(
+        LEGACY_CATEGORY.to_string(),
+        CategorySpec {
+            terms,
+            ..Default::default()
+        },
+    );
+    file
+}
+
+/// The normalized forms of those spelled in capitals, like "ICE" or "E.P.A."
+fn acronyms<'a>(spellings: impl IntoIterator<Item = &'a String>) -> Vec<String> {
+    spellings
+        .into_iter()
+        .filter(|s| {
+            let letters: Vec<char> = s.chars().filter(|c| c.is_alphabetic()).collect();
+            letters.len() >= 2 && letters.iter().all(|c| c.is_uppercase())
+        })
+        .map(|s| normalize_term(s))
+        .collect()
+}
+
+fn normalize_term(term: &str) -> String {
+    term.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn title_case(name: &str) -> String {
+    name.split(['_', '-', ' '])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
@@ -0,0 +1,164 @@
+//! Text helpers shared by the analysis stages.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Function words that carry no topic
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "again", "all", "also", "am", "an", "and", "any", "are", "as", "at",
+    "be", "because", "been", "before", "being", "but", "by", "can", "could", "did", "do", "does",
+    "doing", "don", "for", "from", "get", "got", "had", "has", "have", "having", "he", "her",
+    "here", "him", "his", "how", "i", "if", "in", "into", "is", "it", "its", "just", "know", "let",
+    "like", "ll", "me", "more", "most", "my", "no", "not", "now", "of", "on", "one", "only", "or",
+    "our", "out", "over", "re", "s", "said", "say", "so", "some", "t", "than", "that", "the",
+    "their", "them", "then", "there", "these", "they", "this", "those", "to", "too", "up", "us",
+    "ve", "very", "was", "we", "well", "were", "what", "when", "where", "which", "while", "who",
+    "why", "will", "with", "would", "you", "your",
+];
+
+/// Character span into a segment's text, end exclusive
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Lowercased text with every run of non-alphanumeric characters folded into
+/// a single space, so "Health-care," and "health care" compare equal. Keeps a
+/// map back to character offsets in the original text.
+pub struct NormalizedText {
+    pub text: String,
+    /// Original char index for every byte of `text`
+    byte_to_char: Vec<usize>,
+}
+
+impl NormalizedText {
+    pub fn new(original: &str) -> Self {
+        let mut text = String::with_capacity(original.len());
+        let mut byte_to_char = Vec::with_capacity(original.len());
+
+        let chars: Vec<char> = original.chars().collect();
+        for (char_index, &c) in chars.iter().enumerate() {
+            // Dotted acronyms fold together: "E.P.A." -> "epa"
+            let dotted_acronym = c == '.'
+                && char_index > 0
+                && chars[char_index - 1].is_alphabetic()
+                && chars.get(char_index + 1).is_some_and(|next| next.is_alphabetic());
+
+            if dotted_acronym {
+                continue;
+            } else if c.is_alphanumeric() {
+                for lower in c.to_lowercase() {
+                    text.push(lower);
+                    byte_to_char.extend(std::iter::repeat_n(char_index, lower.len_utf8()));
+                }
+            } else if !text.is_empty() && !text.ends_with(' ') {
+                text.push(' ');
+                byte_to_char.push(char_index);
+            }
+        }
+
+        if text.ends_with(' ') {
+            text.pop();
+            byte_to_char.pop();
+        }
+
+        Self { text, byte_to_char }
+    }
+
+    /// Map a byte range of the normalized text back to the original characters
+    pub fn span(&self, start: usize, end: usize) -> TextSpan {
+        TextSpan {
+            start: self.byte_to_char[start],
+            end: self.byte_to_char[end - 1] + 1,
+        }
+    }
+
+    /// True when `start..end` begins and ends on word boundaries
+    pub fn is_word(&self, start: usize, end: usize) -> bool {
+        let bytes = self.text.as_bytes();
+        (start == 0 || bytes[start - 1] == b' ') && (end == bytes.len() || bytes[end] == b' ')
+    }
+}
+
+pub fn normalize(text: &str) -> String {
+    NormalizedText::new(text).text
+}
+
+/// Slice `text` by character offsets
+pub fn char_slice(text: &str, span: TextSpan) -> &str {
+    let start = byte_offset(text, span.start);
+    let end = byte_offset(text, span.end);
+    &text[start..end]
+}
+
+pub fn byte_offset(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map(|(byte, _)| byte)
+        .unwrap_or(text.len())
+}
+
+pub fn is_stopword(word: &str) -> bool {
+    STOPWORDS.contains(&word)
+}
+
+/// Lowercased words minus stopwords, with plural -s stripped, for
+/// bag-of-words comparisons
+pub fn content_terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| !w.is_empty() && !STOPWORDS.contains(&w.as_str()))
+        .map(|w| match w.strip_suffix('s') {
+            Some(stem) if stem.len() >= 4 && !stem.ends_with('s') => stem.to_string(),
+            _ => w,
+        })
+        .collect()
+}
+
+/// Levenshtein distance in characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Char spans of the whitespace-separated tokens of `text`
+pub fn token_spans(text: &str) -> Vec<TextSpan> {
+    let mut spans = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut count = 0;
+    for (i, c) in text.chars().enumerate() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                spans.push(TextSpan { start: s, end: i });
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+        count = i + 1;
+    }
+    if let Some(s) = start {
+        spans.push(TextSpan { start: s, end: count });
+    }
+    spans
+}
+
+/// Char span of a byte range of `text`
+pub fn char_span(text: &str, start: usize, end: usize) -> TextSpan {
+    TextSpan {
+        start: text[..start].chars().count(),
+        end: text[..end].chars().count(),
+    }
+}
@@ -0,0 +1,248 @@
+//! Talk time, speaking rate and turn-taking per speaker.
+//!
+//! A turn is a run of segments from one speaker, however long the pauses,
+//! until someone else speaks; the longest turn is the speaker's longest
+//! monologue. An interruption is a segment that starts while another
+//! speaker's is still running, which only happens when diarization reports
+//! overlapping speech; plain Whisper output never overlaps.
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::TranscriptSegment;
+
+/// Width of the rolling speaking-rate window
+const ROLLING_WINDOW_SECONDS: f64 = 60.0;
+const ROLLING_STEP_SECONDS: f64 = 30.0;
+/// Windows where the speaker talked less than this give no rate sample
+const MIN_WINDOW_TALK_SECONDS: f64 = 15.0;
+/// Overlaps shorter than this are diarization jitter, not interruptions
+const OVERLAP_TOLERANCE_SECONDS: f64 = 0.25;
+/// Gaps shorter than this are between words, not pauses
+const MIN_PAUSE_SECONDS: f64 = 0.3;
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct SpeakerStats {
+    /// None when the transcript has no speaker labels
+    pub speaker: Option<String>,
+    /// Seconds of speech
+    pub talk_time: f64,
+    /// Share of everyone's talk time, 0-1
+    pub share: f32,
+    pub words: usize,
+    pub words_per_minute: f32,
+    /// Speaking rate over a sliding one-minute window
+    #[serde(default)]
+    pub rolling_wpm: Vec<RateSample>,
+    pub turns: usize,
+    pub longest_monologue: Option<Monologue>,
+    /// Times this speaker started talking over someone
+    #[serde(default)]
+    pub interruptions: usize,
+    /// Times someone started talking over this speaker
+    #[serde(default)]
+    pub interrupted: usize,
+    /// Seconds this speaker spent talking over someone
+    #[serde(default)]
+    pub overlap_seconds: f64,
+    /// Mean pause inside this speaker's turns, in seconds
+    pub average_pause: f64,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct RateSample {
+    pub start: f64,
+    pub end: f64,
+    pub words_per_minute: f32,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct Monologue {
+    pub start: f64,
+    pub end: f64,
+    pub words: usize,
+}
+
+#[derive(Default)]
+struct Tally {
+    talk_time: f64,
+    words: usize,
+    turns: usize,
+    longest: Option<Monologue>,
+    interruptions: usize,
+    interrupted: usize,
+    overlap_seconds: f64,
+    pauses: Vec<f64>,
+}
+
+/// Per-speaker statistics, in order of first appearance
+pub fn speaker_stats(segments: &[TranscriptSegment]) -> Vec<SpeakerStats> {
+    let mut ordered: Vec<&TranscriptSegment> = segments.iter().collect();
+    ordered.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut speakers: Vec<Option<&str>> = Vec::new();
+    for segment in &ordered {
+        if !speakers.contains(&segment.speaker.as_deref()) {
+            speakers.push(segment.speaker.as_deref());
+        }
+    }
+    let mut tallies: Vec<Tally> = speakers.iter().map(|_| Tally::default()).collect();
+
+    // Each speaker's latest segment end, and who has already talked over
+    // that segment: one segment cut into twice by the same person is one
+    // interruption
+    let mut running: HashMap<usize, (f64, Vec<usize>)> = HashMap::new();
+    let mut turn: Option<(usize, Monologue)> = None;
+    let mut previous_end: Option<f64> = None;
+
+    for segment in ordered {
+        let index = speakers.iter().position(|s| *s == segment.speaker.as_deref()).unwrap_or_default();
+        let words = segment.text.split_whitespace().count();
+        tallies[index].talk_time += (segment.end - segment.start).max(0.0);
+        tallies[index].words += words;
+        tallies[index].pauses.extend(word_pauses(segment));
+
+        for (&other, (end, interrupted_by)) in running.iter_mut() {
+            let overlap = end.min(segment.end) - segment.start;
+            if other == index || *end - segment.start <= OVERLAP_TOLERANCE_SECONDS {
+                continue;
+            }
+            tallies[index].overlap_seconds += overlap;
+            if !interrupted_by.contains(&index) {
+                interrupted_by.push(index);
+                tallies[index].interruptions += 1;
+                tallies[other].interrupted += 1;
+            }
+        }
+        running.retain(|_, (end, _)| *end > segment.start);
+        running.insert(index, (segment.end, Vec::new()));
+
+        match &mut turn {
+            Some((speaker, monologue)) if *speaker == index => {
+                if let Some(end) = previous_end {
+                    let gap = segment.start - end;
+                    if gap >= MIN_PAUSE_SECONDS {
+                        tallies[index].pauses.push(gap);
+                    }
+                }
+                monologue.end = monologue.end.max(segment.end);
+                monologue.words += words;
+            }
+            _ => {
+                if let Some((speaker, monologue)) = turn.take() {
+                    close_turn(&mut tallies[speaker], monologue);
+                }
+                turn = Some((
+                    index,
+                    Monologue {
+                        start: segment.start,
+                        end: segment.end,
+                        words,
+                    },
+                ));
+            }
+        }
+        previous_end = Some(segment.end);
+    }
+    if let Some((speaker, monologue)) = turn {
+        close_turn(&mut tallies[speaker], monologue);
+    }
+
+    let total_talk: f64 = tallies.iter().map(|t| t.talk_time).sum();
+    speakers
+        .into_iter()
+        .zip(tallies)
+        .map(|(speaker, tally)| SpeakerStats {
+            speaker: speaker.map(str::to_string),
+            share: if total_talk > 0.0 { (tally.talk_time / total_talk) as f32 } else { 0.0 },
+            words_per_minute: rate(tally.words as f64, tally.talk_time),
+            rolling_wpm: rolling_rate(segments, speaker),
+            turns: tally.turns,
+            longest_monologue: tally.longest,
+            interruptions: tally.interruptions,
+            interrupted: tally.interrupted,
+            overlap_seconds: tally.overlap_seconds,
+            average_pause: if tally.pauses.is_empty() {
+                0.0
+            } else {
+                tally.pauses.iter().sum::<f64>() / tally.pauses.len() as f64
+            },
+            talk_time: tally.talk_time,
+            words: tally.words,
+        })
+        .collect()
+}
+
+fn close_turn(tally: &mut Tally, monologue: Monologue) {
+    tally.turns += 1;
+    let longer = tally
+        .longest
+        .as_ref()
+        .is_none_or(|longest| monologue.end - monologue.start > longest.end - longest.start);
+    if longer {
+        tally.longest = Some(monologue);
+    }
+}
+
+/// Pauses between the words of a segment, when word timings are known
+fn word_pauses(segment: &TranscriptSegment) -> Vec<f64> {
+    segment
+        .words
+        .windows(2)
+        .map(|pair| pair[1].start - pair[0].end)
+        .filter(|gap| *gap >= MIN_PAUSE_SECONDS)
+        .collect()
+}
+
+/// Words per minute over each window where the speaker talked enough
+fn rolling_rate(segments: &[TranscriptSegment], speaker: Option<&str>) -> Vec<RateSample> {
+    let mine: Vec<&TranscriptSegment> = segments.iter().filter(|s| s.speaker.as_deref() == speaker).collect();
+    let Some(last_end) = mine.iter().map(|s| s.end).reduce(f64::max) else {
+        return Vec::new();
+    };
+
+    let mut samples = Vec::new();
+    let mut start = 0.0;
+    while start < last_end {
+        let end = start + ROLLING_WINDOW_SECONDS;
+        let mut talk = 0.0;
+        let mut words = 0.0;
+        for segment in &mine {
+            let overlap = segment.end.min(end) - segment.start.max(start);
+            if overlap <= 0.0 {
+                continue;
+            }
+            talk += overlap;
+            words += if segment.words.is_empty() {
+                // Spread the words evenly over the segment
+                let duration = (segment.end - segment.start).max(f64::EPSILON);
+                segment.text.split_whitespace().count() as f64 * overlap / duration
+            } else {
+                segment
+                    .words
+                    .iter()
+                    .filter(|w| (start..end).contains(&((w.start + w.end) / 2.0)))
+                    .count() as f64
+            };
+        }
+        if talk >= MIN_WINDOW_TALK_SECONDS {
+            samples.push(RateSample {
+                start,
+                end: end.min(last_end),
+                words_per_minute: rate(words, talk),
+            });
+        }
+        start += ROLLING_STEP_SECONDS;
+    }
+    samples
+}
+
+fn rate(words: f64, seconds: f64) -> f32 {
+    if seconds > 0.0 {
+        (words * 60.0 / seconds) as f32
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,198 @@
+//! Timed caption and label formats.
+//!
+//! SRT, VTT, SBV and LRC follow `--clean-read` and `--broadcast-safe`;
+//! Audacity labels stay verbatim so editors can find the stumbles.
+
+use std::borrow::Cow;
+
+use anyhow::Result;
+
+use super::{caption_text, Artifact, TranscriptWriter};
+use crate::{Cli, TranscriptResult};
+
+/// One caption entry: a segment, or a reaction cue with `--reaction-cues`
+struct Cue<'a> {
+    start: f64,
+    end: f64,
+    text: Cow<'a, str>,
+}
+
+fn cues<'a>(cli: &Cli, transcript: &'a TranscriptResult) -> Vec<Cue<'a>> {
+    let mut cues: Vec<Cue> = transcript
+        .segments
+        .iter()
+        .map(|s| Cue {
+            start: s.start,
+            end: s.end,
+            text: caption_text(cli, s),
+        })
+        // A segment that was nothing but "Um." leaves no caption
+        .filter(|cue| !cue.text.trim().is_empty())
+        .collect();
+
+    if cli.reaction_cues {
+        cues.extend(transcript.audience_reactions.iter().map(|r| Cue {
+            start: r.start,
+            end: r.end,
+            text: Cow::Borrowed(r.kind.cue()),
+        }));
+        cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+    }
+
+    cues
+}
+
+pub struct SrtWriter;
+
+impl TranscriptWriter for SrtWriter {
+    fn format(&self) -> &'static str {
+        "srt"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut content = String::new();
+
+        for (index, cue) in cues(cli, transcript).iter().enumerate() {
+            content.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                index + 1,
+                format_time_srt(cue.start),
+                format_time_srt(cue.end),
+                cue.text
+            ));
+        }
+
+        Ok(vec![Artifact::text("srt", content)])
+    }
+}
+
+pub struct VttWriter;
+
+impl TranscriptWriter for VttWriter {
+    fn format(&self) -> &'static str {
+        "vtt"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut content = String::from("WEBVTT\n\n");
+
+        for cue in cues(cli, transcript) {
+            content.push_str(&format!(
+                "{} --> {}\n{}\n\n",
+                format_time_vtt(cue.start),
+                format_time_vtt(cue.end),
+                cue.text
+            ));
+        }
+
+        Ok(vec![Artifact::text("vtt", content)])
+    }
+}
+
+/// YouTube Studio's SubViewer-style captions
+pub struct SbvWriter;
+
+impl TranscriptWriter for SbvWriter {
+    fn format(&self) -> &'static str {
+        "sbv"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut content = String::new();
+
+        for cue in cues(cli, transcript) {
+            content.push_str(&format!(
+                "{},{}\n{}\n\n",
+                format_time_sbv(cue.start),
+                format_time_sbv(cue.end),
+                cue.text
+            ));
+        }
+
+        Ok(vec![Artifact::text("sbv", content)])
+    }
+}
+
+/// Line-synced lyrics format used by podcast and music players
+pub struct LrcWriter;
+
+impl TranscriptWriter for LrcWriter {
+    fn format(&self) -> &'static str {
+        "lrc"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut content = format!(
+            "[ti:{}]\n[length:{}]\n[re:transcribe-turbo]\n",
+            transcript.filename,
+            format_time_lrc(transcript.duration)
+        );
+
+        for segment in &transcript.segments {
+            // LRC lines are single-line by definition
+            let text = caption_text(cli, segment).split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.is_empty() {
+                continue;
+            }
+            content.push_str(&format!("[{}]{}\n", format_time_lrc(segment.start), text));
+        }
+
+        Ok(vec![Artifact::text("lrc", content)])
+    }
+}
+
+/// Audacity label track (File > Import > Labels)
+pub struct AudacityLabelsWriter;
+
+impl TranscriptWriter for AudacityLabelsWriter {
+    fn format(&self) -> &'static str {
+        "audacity"
+    }
+
+    fn render(&self, _cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut content = String::new();
+
+        for segment in &transcript.segments {
+            let label = segment.text.replace(['\t', '\n', '\r'], " ");
+            content.push_str(&format!("{:.6}\t{:.6}\t{}\n", segment.start, segment.end, label));
+        }
+
+        Ok(vec![Artifact::text("labels.txt", content)])
+    }
+}
+
+pub fn format_time_srt(seconds: f64) -> String {
+    let hours = (seconds / 3600.0) as u32;
+    let minutes = ((seconds % 3600.0) / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let millis = ((seconds % 1.0) * 1000.0) as u32;
+
+    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millis)
+}
+
+pub fn format_time_vtt(seconds: f64) -> String {
+    let hours = (seconds / 3600.0) as u32;
+    let minutes = ((seconds % 3600.0) / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let millis = ((seconds % 1.0) * 1000.0) as u32;
+
+    format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+}
+
+fn format_time_sbv(seconds: f64) -> String {
+    let hours = (seconds / 3600.0) as u32;
+    let minutes = ((seconds % 3600.0) / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let millis = ((seconds % 1.0) * 1000.0) as u32;
+
+    format!("{}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+}
+
+fn format_time_lrc(seconds: f64) -> String {
+    // Minutes are not wrapped into hours in LRC
+    let minutes = (seconds / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let centis = ((seconds % 1.0) * 100.0) as u32;
+
+    format!("{:02}:{:02}.{:02}", minutes, secs, centis)
+}
@@ -0,0 +1,94 @@
+//! Chapter exports for publishing full speeches.
+//!
+//! One `chapters` format writes three files:
+//! - `chapters.txt`: timestamps to paste into a YouTube description
+//! - `chapters.vtt`: a WebVTT track for `<track kind="chapters">`
+//! - `chapters.ffmeta`: ffmpeg metadata, applied to an MP4 with
+//!   `ffmpeg -i speech.mp4 -i speech.chapters.ffmeta -map_metadata 1 -map_chapters 1 -codec copy out.mp4`
+
+use anyhow::Result;
+
+use super::captions::format_time_vtt;
+use super::{Artifact, TranscriptWriter};
+use crate::chapters::Chapter;
+use crate::{Cli, TranscriptResult};
+
+pub struct ChaptersWriter;
+
+impl TranscriptWriter for ChaptersWriter {
+    fn format(&self) -> &'static str {
+        "chapters"
+    }
+
+    fn render(&self, _cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        if transcript.chapters.len() < 3 {
+            tracing::warn!(
+                "Only {} chapter(s) detected; YouTube needs at least three to show chapters",
+                transcript.chapters.len()
+            );
+        }
+
+        Ok(vec![
+            Artifact::text("chapters.txt", render_youtube(&transcript.chapters)),
+            Artifact::text("chapters.vtt", render_vtt(&transcript.chapters)),
+            Artifact::text("chapters.ffmeta", render_ffmetadata(&transcript.chapters)),
+        ])
+    }
+}
+
+/// `0:00 Opening` lines; hours only appear once the speech passes an hour
+pub fn render_youtube(chapters: &[Chapter]) -> String {
+    let long = chapters.last().is_some_and(|c| c.start >= 3600.0);
+    chapters
+        .iter()
+        .map(|chapter| {
+            let total = chapter.start.max(0.0) as u64;
+            let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+            let stamp = if long {
+                format!("{}:{:02}:{:02}", hours, minutes, seconds)
+            } else {
+                format!("{}:{:02}", minutes, seconds)
+            };
+            format!("{} {}\n", stamp, chapter.title)
+        })
+        .collect()
+}
+
+pub fn render_vtt(chapters: &[Chapter]) -> String {
+    let mut content = String::from("WEBVTT\n\n");
+    for (index, chapter) in chapters.iter().enumerate() {
+        content.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            format_time_vtt(chapter.start),
+            format_time_vtt(chapter.end),
+            chapter.title
+        ));
+    }
+    content
+}
+
+pub fn render_ffmetadata(chapters: &[Chapter]) -> String {
+    let mut content = String::from(";FFMETADATA1\n");
+    for chapter in chapters {
+        content.push_str(&format!(
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            (chapter.start * 1000.0).round() as u64,
+            (chapter.end * 1000.0).round() as u64,
+            escape_ffmetadata(&chapter.title)
+        ));
+    }
+    content
+}
+
+/// `=`, `;`, `#`, `\` and newlines are special in ffmetadata values
+fn escape_ffmetadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
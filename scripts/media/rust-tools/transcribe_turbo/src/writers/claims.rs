@@ -0,0 +1,61 @@
+//! Checkable claims for the rapid-response desk.
+//!
+//! One `claims` format writes two files:
+//! - `claims.json`: every claim with its normalized quantities
+//! - `claims.csv`: one row per claim, quantities and cues joined with `;`
+//!
+//! CSV columns: start, end, speaker, kind, claim, quantities, cues,
+//! context_before, context_after. As with the other tables, new columns are
+//! only ever appended.
+
+use anyhow::{Context, Result};
+
+use super::tabular::{render_table, Delimited, KEYWORD_SEPARATOR};
+use super::{Artifact, TranscriptWriter};
+use crate::claims::Claim;
+use crate::{Cli, TranscriptResult};
+
+const CLAIM_COLUMNS: [&str; 9] = [
+    "start", "end", "speaker", "kind", "claim", "quantities", "cues", "context_before", "context_after",
+];
+
+pub struct ClaimsWriter;
+
+impl TranscriptWriter for ClaimsWriter {
+    fn format(&self) -> &'static str {
+        "claims"
+    }
+
+    fn render(&self, _cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let json = serde_json::to_string_pretty(&transcript.claims)
+            .context("Failed to serialize claims")?;
+
+        Ok(vec![
+            Artifact::text("claims.json", json),
+            Artifact::text("claims.csv", render_csv(&transcript.claims)),
+        ])
+    }
+}
+
+pub fn render_csv(claims: &[Claim]) -> String {
+    let rows = claims.iter().map(|claim| {
+        vec![
+            format!("{:.3}", claim.start),
+            format!("{:.3}", claim.end),
+            claim.speaker.clone().unwrap_or_default(),
+            claim.kind.as_str().to_string(),
+            claim.text.clone(),
+            claim
+                .quantities
+                .iter()
+                .map(|q| q.display())
+                .collect::<Vec<_>>()
+                .join(KEYWORD_SEPARATOR),
+            claim.cues.join(KEYWORD_SEPARATOR),
+            claim.context_before.clone(),
+            claim.context_after.clone(),
+        ]
+    });
+
+    render_table(&CLAIM_COLUMNS, rows, Delimited::Csv)
+}
@@ -0,0 +1,93 @@
+//! Broadcast compliance report.
+//!
+//! `compliance.md` lists every flagged word with its timestamp, speaker and
+//! the caption as it will air, worst first in the tally. `--broadcast-safe`
+//! adds this format to whatever `--format` asked for.
+
+use anyhow::Result;
+
+use super::{caption_text, format_clock, Artifact, TranscriptWriter};
+use crate::profanity::{mask_word, Severity};
+use crate::sentences::span_times;
+use crate::{Cli, TranscriptResult};
+
+pub struct ComplianceWriter;
+
+impl TranscriptWriter for ComplianceWriter {
+    fn format(&self) -> &'static str {
+        "compliance"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        Ok(vec![Artifact::text("compliance.md", render_report(cli, transcript))])
+    }
+}
+
+pub fn render_report(cli: &Cli, transcript: &TranscriptResult) -> String {
+    let mut out = format!("# Broadcast Compliance: {}\n\n", transcript.filename);
+
+    let count = |severity: Severity| {
+        transcript
+            .segments
+            .iter()
+            .flat_map(|s| &s.profanity)
+            .filter(|p| p.severity == severity)
+            .count()
+    };
+    let (severe, strong, mild) = (count(Severity::Severe), count(Severity::Strong), count(Severity::Mild));
+    // What the captions really hide: only --broadcast-safe masks, and only
+    // down to --mask-severity
+    let flags = || transcript.segments.iter().flat_map(|s| &s.profanity);
+    let is_masked = |severity: Severity| cli.broadcast_safe && severity >= cli.mask_severity;
+    let unmasked_strong = flags().any(|p| p.severity >= Severity::Strong && !is_masked(p.severity));
+    let any_masked = flags().any(|p| is_masked(p.severity));
+    let status = if severe > 0 {
+        "Not cleared: slurs present, review before any use"
+    } else if unmasked_strong {
+        "Not cleared: strong language in unmasked captions"
+    } else if any_masked {
+        "Cleared with masked captions"
+    } else if mild > 0 {
+        "Cleared: mild language only, captions unmasked"
+    } else {
+        "Cleared: no flagged language"
+    };
+
+    out.push_str(&format!("- **Status:** {}\n", status));
+    out.push_str(&format!("- **Language:** {}\n", transcript.language));
+    out.push_str(&format!("- **Flags:** {} severe, {} strong, {} mild\n", severe, strong, mild));
+    out.push_str(&format!(
+        "- **Captions:** {}\n\n",
+        if cli.broadcast_safe {
+            format!("{} and worse masked", cli.mask_severity.as_str())
+        } else {
+            "not masked (run with --broadcast-safe)".to_string()
+        }
+    ));
+
+    if severe + strong + mild == 0 {
+        return out;
+    }
+
+    out.push_str("| Time | Speaker | Severity | Word | Caption |\n");
+    out.push_str("|------|---------|----------|------|---------|\n");
+    for segment in &transcript.segments {
+        if segment.profanity.is_empty() {
+            continue;
+        }
+        let caption = caption_text(cli, segment).replace('|', "\\|");
+        for flag in &segment.profanity {
+            let (start, _) = span_times(segment, flag.span);
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                format_clock(start),
+                segment.speaker.as_deref().unwrap_or("-"),
+                flag.severity.as_str(),
+                mask_word(&flag.text),
+                caption
+            ));
+        }
+    }
+
+    out
+}
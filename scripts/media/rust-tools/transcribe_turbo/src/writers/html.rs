@@ -0,0 +1,536 @@
+//! Self-contained interactive HTML report.
+//!
+//! Everything (styles, script, data) is inlined so the file can be opened
+//! straight from a shared drive without network access. Only the media file
+//! itself is referenced, via `media_src`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use super::{format_clock, Artifact, TranscriptWriter};
+use crate::stance::Stance;
+use crate::text::{byte_offset, TextSpan};
+use crate::{is_video_file, redacted_media_path, Cli, TranscriptResult};
+
+/// Transcript files, which `transcript.filename` may name but no player can play
+const TRANSCRIPT_EXTENSIONS: &[&str] = &["srt", "vtt", "json", "txt", "md"];
+
+const SPEAKER_COLORS: [&str; 8] = [
+    "#1f77b4", "#d62728", "#2ca02c", "#9467bd", "#ff7f0e", "#17becf", "#8c564b", "#e377c2",
+];
+/// Most-mentioned entities listed in the sidebar
+const MAX_ENTITIES: usize = 15;
+
+const STYLE: &str = r#"
+* { box-sizing: border-box; }
+body { margin: 0; font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #222; background: #f6f7f9; }
+header { padding: 16px 24px; background: #14213d; color: #fff; }
+header h1 { margin: 0 0 4px; font-size: 20px; }
+header .meta { font-size: 13px; opacity: 0.8; }
+.layout { display: flex; gap: 24px; padding: 24px; align-items: flex-start; }
+main { flex: 3; min-width: 0; }
+aside { flex: 1; min-width: 260px; position: sticky; top: 16px; max-height: calc(100vh - 32px); overflow-y: auto; }
+.player { position: sticky; top: 0; background: #f6f7f9; padding-bottom: 12px; z-index: 1; }
+.player video { width: 100%; max-height: 45vh; background: #000; }
+.player audio { width: 100%; }
+.segment { margin: 0 0 10px; padding: 8px 12px; background: #fff; border-left: 4px solid #ccc; border-radius: 4px; cursor: pointer; line-height: 1.5; }
+.segment:hover { background: #eef3fb; }
+.segment.active { background: #fff6d5; }
+.segment .head { font-size: 12px; color: #666; margin-bottom: 2px; }
+.segment .speaker { font-weight: 600; margin-right: 8px; }
+.segment mark { background: #ffe08a; padding: 0 2px; border-radius: 2px; }
+.badge { display: inline-block; font-size: 11px; padding: 1px 6px; border-radius: 8px; margin-left: 6px; }
+.badge.positive { background: #d4f4dd; color: #1b6b32; }
+.badge.negative { background: #fbd9d9; color: #8a1c1c; }
+.badge.neutral { background: #e6e6e6; color: #555; }
+.badge.support { background: #d4f4dd; color: #1b6b32; }
+.badge.oppose { background: #fbd9d9; color: #8a1c1c; }
+.panel { background: #fff; border-radius: 4px; padding: 12px 16px; margin-bottom: 16px; }
+.panel h2 { font-size: 15px; margin: 0 0 8px; }
+.panel h3 { font-size: 13px; margin: 10px 0 4px; }
+.panel ul { margin: 0; padding-left: 18px; }
+.panel li { margin-bottom: 6px; font-size: 14px; }
+.quote { border-bottom: 1px solid #eee; padding: 8px 0; font-size: 14px; }
+.quote:last-child { border-bottom: none; }
+.quote .time { color: #1f5fbf; cursor: pointer; text-decoration: underline; }
+.quote button { font-size: 12px; margin-left: 6px; cursor: pointer; }
+.muted { color: #777; font-size: 13px; }
+"#;
+
+const SCRIPT: &str = r#"
+(function () {
+  var media = document.getElementById('media');
+  var segments = Array.prototype.slice.call(document.querySelectorAll('.segment'));
+  function seek(t) {
+    if (!media) { return; }
+    media.currentTime = t;
+    media.play();
+  }
+  segments.forEach(function (el) {
+    el.addEventListener('click', function () { seek(parseFloat(el.dataset.start)); });
+  });
+  document.querySelectorAll('[data-seek]').forEach(function (el) {
+    el.addEventListener('click', function () { seek(parseFloat(el.dataset.seek)); });
+  });
+  document.querySelectorAll('button[data-copy]').forEach(function (btn) {
+    btn.addEventListener('click', function (ev) {
+      ev.stopPropagation();
+      var text = btn.dataset.copy;
+      var done = function () {
+        btn.textContent = 'Copied';
+        setTimeout(function () { btn.textContent = 'Copy'; }, 1500);
+      };
+      if (navigator.clipboard && window.isSecureContext) {
+        navigator.clipboard.writeText(text).then(done);
+      } else {
+        var area = document.createElement('textarea');
+        area.value = text;
+        document.body.appendChild(area);
+        area.select();
+        document.execCommand('copy');
+        document.body.removeChild(area);
+        done();
+      }
+    });
+  });
+  if (media) {
+    media.addEventListener('timeupdate', function () {
+      var t = media.currentTime;
+      segments.forEach(function (el) {
+        var active = t >= parseFloat(el.dataset.start) && t < parseFloat(el.dataset.end);
+        if (active && !el.classList.contains('active')) {
+          el.scrollIntoView({ block: 'nearest', behavior: 'smooth' });
+        }
+        el.classList.toggle('active', active);
+      });
+    });
+  }
+})();
+"#;
+
+pub struct HtmlWriter;
+
+impl TranscriptWriter for HtmlWriter {
+    fn format(&self) -> &'static str {
+        "html"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let media = match cli.media() {
+            // With --redact-audio the report plays the redacted copy, never the original
+            Some(media) if cli.redact_audio.is_some() => Some(redacted_media_path(cli, media, transcript)),
+            Some(media) => Some(media.to_path_buf()),
+            None if cli.redact_audio.is_some() => None,
+            // A transcript imported without --audio only has the name it was made from
+            None => {
+                let named = PathBuf::from(&transcript.filename);
+                let playable = named
+                    .extension()
+                    .is_some_and(|e| !TRANSCRIPT_EXTENSIONS.contains(&e.to_string_lossy().to_lowercase().as_str()));
+                (playable && named.is_file()).then_some(named)
+            }
+        };
+        let media_src = cli
+            .media_src
+            .clone()
+            .or_else(|| media.as_ref().map(|m| relative_media_path(m, &cli.output)));
+        let is_video = media.as_ref().is_some_and(is_video_file);
+        let content = render(transcript, media_src.as_deref(), is_video, cli.summary_bullets);
+
+        Ok(vec![Artifact::text("html", content)])
+    }
+}
+
+/// `media_src` of None leaves the player out
+pub fn render(transcript: &TranscriptResult, media_src: Option<&str>, is_video: bool, summary_bullets: usize) -> String {
+    let speaker_colors = assign_speaker_colors(transcript);
+    let mut html = String::new();
+
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{} - Transcript</title>\n", escape_html(&transcript.filename)));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+
+    html.push_str(&format!(
+        "<header><h1>{}</h1><div class=\"meta\">Duration {} &middot; Language {} &middot; Model {} &middot; {} segments &middot; {} words &middot; Generated {}</div></header>\n",
+        escape_html(&transcript.filename),
+        format_clock(transcript.duration),
+        escape_html(&transcript.language),
+        escape_html(&transcript.model_used),
+        transcript.statistics.total_segments,
+        transcript.statistics.total_words,
+        transcript.timestamp.format("%Y-%m-%d %H:%M UTC"),
+    ));
+
+    html.push_str("<div class=\"layout\">\n<main>\n");
+    if let Some(media_src) = media_src {
+        let tag = if is_video { "video" } else { "audio" };
+        html.push_str(&format!(
+            "<div class=\"player\">\n<{tag} id=\"media\" controls preload=\"metadata\" src=\"{}\"></{tag}>\n</div>\n",
+            escape_html(media_src),
+            tag = tag,
+        ));
+    }
+
+    for segment in &transcript.segments {
+        let color = segment
+            .speaker
+            .as_ref()
+            .and_then(|s| speaker_colors.get(s))
+            .copied()
+            .unwrap_or("#cccccc");
+
+        html.push_str(&format!(
+            "<p class=\"segment\" data-start=\"{:.3}\" data-end=\"{:.3}\" style=\"border-left-color: {}\">",
+            segment.start, segment.end, color
+        ));
+        html.push_str("<span class=\"head\">");
+        if let Some(speaker) = &segment.speaker {
+            html.push_str(&format!(
+                "<span class=\"speaker\" style=\"color: {}\">{}</span>",
+                color,
+                escape_html(speaker)
+            ));
+        }
+        html.push_str(&format!("<span class=\"time\">{}</span>", format_clock(segment.start)));
+        if let Some(sentiment) = &segment.sentiment {
+            html.push_str(&format!(
+                "<span class=\"badge {}\" title=\"compound {:+.2}\">{}</span>",
+                sentiment_class(&sentiment.label),
+                sentiment.compound,
+                escape_html(&sentiment.label)
+            ));
+        }
+        html.push_str("</span><br>");
+        let spans: Vec<TextSpan> = segment.political_keywords.iter().flat_map(|k| k.spans.iter().copied()).collect();
+        html.push_str(&highlight_spans(&segment.text, &spans));
+        html.push_str("</p>\n");
+    }
+
+    html.push_str("</main>\n<aside>\n");
+    render_sidebar(&mut html, transcript, &speaker_colors, summary_bullets);
+    html.push_str("</aside>\n</div>\n");
+
+    html.push_str(&format!("<script>{}</script>\n</body>\n</html>\n", SCRIPT));
+    html
+}
+
+fn render_sidebar(
+    html: &mut String,
+    transcript: &TranscriptResult,
+    speaker_colors: &HashMap<String, &str>,
+    summary_bullets: usize,
+) {
+    if !speaker_colors.is_empty() {
+        let mut speakers: Vec<_> = speaker_colors.iter().collect();
+        speakers.sort_by_key(|(_, color)| SPEAKER_COLORS.iter().position(|c| c == *color));
+
+        html.push_str("<section class=\"panel\"><h2>Speakers</h2><ul>");
+        for (speaker, color) in speakers {
+            let stats = transcript
+                .statistics
+                .speakers
+                .iter()
+                .find(|s| s.speaker.as_deref() == Some(speaker.as_str()))
+                .map(|s| format!(" <span class=\"muted\">{:.0}% of talk time, {:.0} wpm</span>", s.share * 100.0, s.words_per_minute))
+                .unwrap_or_default();
+            html.push_str(&format!(
+                "<li><span style=\"color: {}\">&#9632;</span> {}{}</li>",
+                color,
+                escape_html(speaker),
+                stats
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    if transcript.chapters.len() > 1 {
+        html.push_str("<section class=\"panel\"><h2>Chapters</h2><ul>");
+        for chapter in &transcript.chapters {
+            html.push_str(&format!(
+                "<li><span class=\"time\" data-seek=\"{:.3}\">{}</span> {}</li>",
+                chapter.start,
+                format_clock(chapter.start),
+                escape_html(&chapter.title)
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    if !transcript.audience_reactions.is_empty() {
+        html.push_str("<section class=\"panel\"><h2>Audience Reactions</h2><ul>");
+        for reaction in &transcript.audience_reactions {
+            html.push_str(&format!(
+                "<li><span class=\"time\" data-seek=\"{:.3}\">{}</span> {} <span class=\"muted\">{:.0}s, {:.0}%</span></li>",
+                reaction.start,
+                format_clock(reaction.start),
+                reaction.kind.cue(),
+                reaction.end - reaction.start,
+                reaction.confidence * 100.0
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    if !transcript.entities.is_empty() {
+        html.push_str("<section class=\"panel\"><h2>Mentioned</h2><ul>");
+        for entity in transcript.entities.iter().take(MAX_ENTITIES) {
+            html.push_str(&format!(
+                "<li><span class=\"time\" data-seek=\"{:.3}\">{}</span> {} <span class=\"muted\">{}, {}&times;</span></li>",
+                entity.first_mention,
+                format_clock(entity.first_mention),
+                escape_html(&entity.name),
+                entity.kind.as_str(),
+                entity.mentions
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    let analysis = match &transcript.political_analysis {
+        Some(analysis) => analysis,
+        None => {
+            html.push_str("<section class=\"panel\"><p class=\"muted\">Political analysis was not enabled for this transcript.</p></section>\n");
+            return;
+        }
+    };
+
+    if !analysis.summary.bullets.is_empty() {
+        html.push_str(&format!(
+            "<section class=\"panel\"><h2>Summary</h2><p>{}</p><ul>",
+            escape_html(&analysis.summary.abstract_text)
+        ));
+        for bullet in analysis.summary.top(summary_bullets) {
+            html.push_str(&format!(
+                "<li><span class=\"time\" data-seek=\"{:.3}\">{}</span> {}</li>",
+                bullet.start,
+                format_clock(bullet.start),
+                escape_html(&bullet.text),
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    if !analysis.issue_areas.is_empty() {
+        html.push_str("<section class=\"panel\"><h2>Issue Areas</h2><ul>");
+        for area in &analysis.issue_areas {
+            html.push_str(&format!(
+                "<li><strong>{}</strong> <span class=\"muted\">({} mentions)</span><br><span class=\"muted\">{}</span></li>",
+                escape_html(&area.label),
+                area.mentions,
+                escape_html(&area.terms.join(", ")),
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    // Neutral mentions are just the issue coming up; only show positions taken
+    let positions: Vec<_> = analysis.policy_mentions.iter().filter(|m| m.stance != Stance::Neutral).collect();
+    if !positions.is_empty() {
+        html.push_str("<section class=\"panel\"><h2>Policy Positions</h2><ul>");
+        for mention in positions {
+            html.push_str(&format!(
+                "<li><span class=\"time\" data-seek=\"{:.3}\">{}</span> <span class=\"badge {}\">{}</span> <strong>{}</strong> <span class=\"muted\">{:.0}%</span><br><span class=\"muted\" title=\"{}\">&ldquo;{}&rdquo;</span></li>",
+                mention.timestamp,
+                format_clock(mention.timestamp),
+                mention.stance.as_str(),
+                mention.stance.as_str(),
+                escape_html(&mention.policy),
+                mention.confidence * 100.0,
+                escape_html(&mention.cues.join(", ")),
+                escape_html(&mention.evidence),
+            ));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    if !analysis.talking_points.is_empty() {
+        html.push_str("<section class=\"panel\"><h2>Talking Points</h2>");
+        for group in &analysis.talking_points {
+            html.push_str(&format!("<h3>{}</h3><ul>", escape_html(&group.label)));
+            for point in &group.points {
+                html.push_str(&format!(
+                    "<li><span class=\"time\" data-seek=\"{:.3}\">{}</span> {}</li>",
+                    point.start,
+                    format_clock(point.start),
+                    escape_html(&point.text),
+                ));
+            }
+            html.push_str("</ul>");
+        }
+        html.push_str("</section>\n");
+    }
+
+    // Themes with how often they came up
+    html.push_str("<section class=\"panel\"><h2>Key Themes</h2>");
+    if analysis.key_themes.is_empty() {
+        html.push_str("<p class=\"muted\">No recurring themes detected.</p>");
+    } else {
+        html.push_str("<ul>");
+        for theme in &analysis.key_themes {
+            let mentions = transcript
+                .segments
+                .iter()
+                .filter(|s| s.political_keywords.iter().any(|k| &k.term == theme))
+                .count();
+            html.push_str(&format!(
+                "<li>{} <span class=\"muted\">({} segments)</span></li>",
+                escape_html(theme),
+                mentions
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str("</section>\n");
+
+    let distribution = &analysis.sentiment_distribution;
+    if !distribution.shares.is_empty() {
+        let mut shares: Vec<_> = distribution.shares.iter().collect();
+        shares.sort_by(|a, b| a.0.cmp(b.0));
+
+        html.push_str(&format!(
+            "<section class=\"panel\"><h2>Sentiment</h2><p class=\"muted\">Mean {:+.2}, duration-weighted {:+.2}</p><ul>",
+            distribution.mean_compound, distribution.duration_weighted_mean
+        ));
+        for (sentiment, ratio) in shares {
+            html.push_str(&format!(
+                "<li><span class=\"badge {}\">{}</span> {:.1}%</li>",
+                sentiment_class(sentiment),
+                escape_html(sentiment),
+                ratio * 100.0
+            ));
+        }
+        for (speaker, mean) in &distribution.speaker_means {
+            html.push_str(&format!("<li>{} {:+.2}</li>", escape_html(speaker), mean));
+        }
+        html.push_str("</ul></section>\n");
+    }
+
+    html.push_str("<section class=\"panel\"><h2>Quotable Moments</h2>");
+    if analysis.quotable_moments.is_empty() {
+        html.push_str("<p class=\"muted\">No quotable moments detected.</p>");
+    }
+    for quote in &analysis.quotable_moments {
+        let clipboard = format!("\"{}\" ({})", quote.text, format_clock(quote.start));
+        let devices = if quote.devices.is_empty() { String::new() } else { format!(", {}", quote.devices.join(", ")) };
+        html.push_str(&format!(
+            "<div class=\"quote\"><span class=\"time\" data-seek=\"{:.3}\">{}</span> <span class=\"muted\">viral {:.2}{}</span><button data-copy=\"{}\">Copy</button><br>",
+            quote.start,
+            format_clock(quote.start),
+            quote.viral_potential,
+            escape_html(&devices),
+            escape_html(&clipboard),
+        ));
+        if let Some(before) = &quote.context.before {
+            html.push_str(&format!("<span class=\"muted\">{}</span> ", escape_html(before)));
+        }
+        html.push_str(&format!("<strong>{}</strong>", escape_html(&quote.text)));
+        if let Some(after) = &quote.context.after {
+            html.push_str(&format!(" <span class=\"muted\">{}</span>", escape_html(after)));
+        }
+        html.push_str("</div>");
+    }
+    html.push_str("</section>\n");
+}
+
+fn assign_speaker_colors(transcript: &TranscriptResult) -> HashMap<String, &'static str> {
+    let mut colors = HashMap::new();
+    for speaker in transcript.segments.iter().filter_map(|s| s.speaker.as_ref()) {
+        if !colors.contains_key(speaker) {
+            let color = SPEAKER_COLORS[colors.len() % SPEAKER_COLORS.len()];
+            colors.insert(speaker.clone(), color);
+        }
+    }
+    colors
+}
+
+fn sentiment_class(sentiment: &str) -> &'static str {
+    match sentiment {
+        "positive" => "positive",
+        "negative" => "negative",
+        _ => "neutral",
+    }
+}
+
+fn highlight_spans(text: &str, spans: &[TextSpan]) -> String {
+    let mut ranges: Vec<(usize, usize)> = spans
+        .iter()
+        .map(|span| (byte_offset(text, span.start), byte_offset(text, span.end)))
+        .filter(|(start, end)| start < end)
+        .collect();
+    ranges.sort();
+
+    let mut html = String::new();
+    let mut cursor = 0;
+    for (start, end) in ranges {
+        if start < cursor {
+            continue;
+        }
+        html.push_str(&escape_html(&text[cursor..start]));
+        html.push_str("<mark>");
+        html.push_str(&escape_html(&text[start..end]));
+        html.push_str("</mark>");
+        cursor = end;
+    }
+    html.push_str(&escape_html(&text[cursor..]));
+    html
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// A URL for `input` relative to the output directory the report lives in
+fn relative_media_path(input: &Path, output_dir: &Path) -> String {
+    let (Ok(input), Ok(output_dir)) = (input.canonicalize(), output_dir.canonicalize()) else {
+        return percent_encode(&input.to_string_lossy());
+    };
+
+    let input_parts: Vec<_> = input.components().collect();
+    let output_parts: Vec<_> = output_dir.components().collect();
+    let common = input_parts
+        .iter()
+        .zip(&output_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..output_parts.len() {
+        relative.push("..");
+    }
+    for part in &input_parts[common..] {
+        relative.push(part);
+    }
+
+    relative
+        .components()
+        .map(|c| percent_encode(&c.as_os_str().to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Percent-encode everything but unreserved URL characters and `/`, so
+/// "Town Hall #2?.mp4" still loads
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,53 @@
+//! Machine-readable JSON outputs.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use super::{Artifact, TranscriptWriter};
+use crate::{Cli, TranscriptResult, TranscriptSegment};
+
+pub struct JsonWriter;
+
+impl TranscriptWriter for JsonWriter {
+    fn format(&self) -> &'static str {
+        "json"
+    }
+
+    fn render(&self, _cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let content = serde_json::to_string_pretty(transcript)
+            .context("Failed to serialize transcript")?;
+
+        Ok(vec![Artifact::text("json", content)])
+    }
+}
+
+/// One JSON object per segment, for log pipelines
+pub struct JsonLinesWriter;
+
+#[derive(Serialize)]
+struct JsonLine<'a> {
+    filename: &'a str,
+    #[serde(flatten)]
+    segment: &'a TranscriptSegment,
+}
+
+impl TranscriptWriter for JsonLinesWriter {
+    fn format(&self) -> &'static str {
+        "jsonl"
+    }
+
+    fn render(&self, _cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut content = String::new();
+
+        for segment in &transcript.segments {
+            let line = JsonLine {
+                filename: &transcript.filename,
+                segment,
+            };
+            content.push_str(&serde_json::to_string(&line).context("Failed to serialize segment")?);
+            content.push('\n');
+        }
+
+        Ok(vec![Artifact::text("jsonl", content)])
+    }
+}
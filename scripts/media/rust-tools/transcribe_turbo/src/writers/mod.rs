@@ -0,0 +1,124 @@
+//! Output writers.
+//!
+//! Every output format implements [`TranscriptWriter`] and is listed in
+//! [`WRITERS`]; `--format` takes a comma-separated list of their names.
+
+use std::borrow::Cow;
+
+use anyhow::{anyhow, Result};
+
+use crate::{disfluency, profanity, Cli, TranscriptResult, TranscriptSegment};
+
+pub mod captions;
+pub mod chapters;
+pub mod claims;
+pub mod compliance;
+pub mod html;
+pub mod json;
+pub mod press;
+pub mod script;
+pub mod tabular;
+
+/// One file produced by a writer, saved as `<base_name>.<suffix>`
+pub struct Artifact {
+    pub suffix: String,
+    pub contents: Vec<u8>,
+}
+
+impl Artifact {
+    pub fn text(suffix: &str, contents: String) -> Self {
+        Self {
+            suffix: suffix.to_string(),
+            contents: contents.into_bytes(),
+        }
+    }
+}
+
+pub trait TranscriptWriter: Sync {
+    /// Name accepted by `--format`
+    fn format(&self) -> &'static str;
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>>;
+}
+
+pub static WRITERS: &[&dyn TranscriptWriter] = &[
+    &captions::SrtWriter,
+    &captions::VttWriter,
+    &captions::SbvWriter,
+    &captions::LrcWriter,
+    &captions::AudacityLabelsWriter,
+    &chapters::ChaptersWriter,
+    &claims::ClaimsWriter,
+    &compliance::ComplianceWriter,
+    &script::ScriptWriter,
+    &press::TxtWriter,
+    &press::MarkdownWriter,
+    &json::JsonWriter,
+    &json::JsonLinesWriter,
+    &html::HtmlWriter,
+    &tabular::CsvWriter,
+    &tabular::TsvWriter,
+    &tabular::ParquetTableWriter,
+];
+
+/// Resolve a `--format` value such as `srt,vtt,json` or `all`
+pub fn resolve_formats(spec: &str) -> Result<Vec<&'static dyn TranscriptWriter>> {
+    let mut selected: Vec<&'static dyn TranscriptWriter> = Vec::new();
+
+    for name in spec.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        if name.eq_ignore_ascii_case("all") {
+            return Ok(WRITERS.to_vec());
+        }
+
+        let writer = WRITERS
+            .iter()
+            .find(|w| w.format().eq_ignore_ascii_case(name))
+            .ok_or_else(|| anyhow!("Unsupported format: {} (available: {}, all)", name, available_formats()))?;
+
+        if !selected.iter().any(|w| w.format() == writer.format()) {
+            selected.push(*writer);
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(anyhow!("No output format given (available: {}, all)", available_formats()));
+    }
+
+    Ok(selected)
+}
+
+fn available_formats() -> String {
+    WRITERS.iter().map(|w| w.format()).collect::<Vec<_>>().join(", ")
+}
+
+/// Segment text for reading: verbatim, or without fillers, repeats and
+/// restarts under `--clean-read`
+pub fn reading_text(segment: &TranscriptSegment, clean_read: bool) -> Cow<'_, str> {
+    if clean_read && !segment.disfluencies.is_empty() {
+        Cow::Owned(disfluency::clean(&segment.text, &segment.disfluencies))
+    } else {
+        Cow::Borrowed(&segment.text)
+    }
+}
+
+/// Segment text for captions: `reading_text`, with flagged words masked
+/// under `--broadcast-safe`
+pub fn caption_text<'a>(cli: &Cli, segment: &'a TranscriptSegment) -> Cow<'a, str> {
+    if !cli.broadcast_safe || segment.profanity.iter().all(|p| p.severity < cli.mask_severity) {
+        return reading_text(segment, cli.clean_read);
+    }
+
+    // Masking keeps every character in place, so the disfluency spans still fit
+    let masked = profanity::mask(&segment.text, &segment.profanity, cli.mask_severity);
+    if cli.clean_read && !segment.disfluencies.is_empty() {
+        Cow::Owned(disfluency::clean(&masked, &segment.disfluencies))
+    } else {
+        Cow::Owned(masked)
+    }
+}
+
+/// `HH:MM:SS` clock used by the human-facing reports
+pub fn format_clock(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("{:02}:{:02}:{:02}", total / 3600, (total % 3600) / 60, total % 60)
+}
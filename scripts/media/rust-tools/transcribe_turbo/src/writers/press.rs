@@ -0,0 +1,259 @@
+//! Press-ready transcripts (plain text and Markdown).
+//!
+//! Consecutive segments from the same speaker are merged into paragraphs that
+//! open with a `SPEAKER (00:12:31):` header, optionally with running
+//! `[00:15:00]` markers every N seconds so reporters can find their place in
+//! the recording. The Markdown version opens with the summary and talking
+//! points when political analysis was run, with the spoken text escaped so a
+//! line like "1. jobs" or "*sigh*" stays text. Both follow `--clean-read`.
+
+use anyhow::Result;
+
+use super::{format_clock, reading_text, Artifact, TranscriptWriter};
+use crate::{Cli, TranscriptResult};
+
+/// A pause this long inside one speaker's turn starts a new paragraph
+const PARAGRAPH_PAUSE_SECONDS: f64 = 4.0;
+
+#[derive(Debug, Default, Clone)]
+pub struct EventMetadata {
+    pub title: Option<String>,
+    pub location: Option<String>,
+    pub date: Option<String>,
+}
+
+pub struct TxtWriter;
+
+impl TranscriptWriter for TxtWriter {
+    fn format(&self) -> &'static str {
+        "txt"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let content = render_txt(transcript, &EventMetadata::from_cli(cli), cli.timestamp_interval, cli.clean_read);
+        Ok(vec![Artifact::text("txt", content)])
+    }
+}
+
+pub struct MarkdownWriter;
+
+impl TranscriptWriter for MarkdownWriter {
+    fn format(&self) -> &'static str {
+        "md"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let content = render_markdown(
+            transcript,
+            &EventMetadata::from_cli(cli),
+            cli.timestamp_interval,
+            cli.summary_bullets,
+            cli.clean_read,
+        );
+        Ok(vec![Artifact::text("md", content)])
+    }
+}
+
+impl EventMetadata {
+    pub fn from_cli(cli: &Cli) -> Self {
+        Self {
+            title: cli.event_title.clone(),
+            location: cli.event_location.clone(),
+            date: cli.event_date.clone(),
+        }
+    }
+}
+
+struct Paragraph {
+    speaker: Option<String>,
+    start: f64,
+    /// Same speaker as the previous paragraph, so the header is not repeated
+    continuation: bool,
+    chunks: Vec<Chunk>,
+}
+
+enum Chunk {
+    Text(String),
+    Marker(f64),
+}
+
+pub fn render_txt(transcript: &TranscriptResult, event: &EventMetadata, interval: Option<f64>, clean_read: bool) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "{}\n",
+        event.title.as_deref().unwrap_or("TRANSCRIPT").to_uppercase()
+    ));
+    for (label, value) in header_fields(transcript, event) {
+        out.push_str(&format!("{}: {}\n", label, value));
+    }
+    out.push_str(&format!("{}\n\n", "-".repeat(60)));
+
+    for paragraph in build_paragraphs(transcript, interval, clean_read) {
+        if !paragraph.continuation {
+            out.push_str(&format!("{}: ", speaker_header(&paragraph)));
+        }
+        out.push_str(&join_chunks(&paragraph.chunks, str::to_string, |t| format!("[{}]", format_clock(t))));
+        out.push_str("\n\n");
+    }
+
+    out
+}
+
+pub fn render_markdown(
+    transcript: &TranscriptResult,
+    event: &EventMetadata,
+    interval: Option<f64>,
+    summary_bullets: usize,
+    clean_read: bool,
+) -> String {
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "# {}\n\n",
+        event.title.as_deref().unwrap_or("Transcript")
+    ));
+    for (label, value) in header_fields(transcript, event) {
+        out.push_str(&format!("- **{}:** {}\n", label, value));
+    }
+    out.push_str("\n---\n\n");
+
+    if let Some(analysis) = &transcript.political_analysis {
+        if !analysis.summary.bullets.is_empty() {
+            out.push_str("## Summary\n\n");
+            out.push_str(&format!("{}\n\n", escape_markdown(&analysis.summary.abstract_text)));
+            for bullet in analysis.summary.top(summary_bullets) {
+                out.push_str(&format!("- *[{}]* {}\n", format_clock(bullet.start), escape_markdown(&bullet.text)));
+            }
+            out.push('\n');
+        }
+        if !analysis.talking_points.is_empty() {
+            out.push_str("## Talking Points\n\n");
+            for group in &analysis.talking_points {
+                out.push_str(&format!("### {}\n\n", group.label));
+                for point in &group.points {
+                    out.push_str(&format!("- *[{}]* {}\n", format_clock(point.start), escape_markdown(&point.text)));
+                }
+                out.push('\n');
+            }
+        }
+        if !analysis.summary.bullets.is_empty() || !analysis.talking_points.is_empty() {
+            out.push_str("---\n\n");
+        }
+    }
+
+    for paragraph in build_paragraphs(transcript, interval, clean_read) {
+        if !paragraph.continuation {
+            out.push_str(&format!("**{}:** ", speaker_header(&paragraph)));
+        }
+        out.push_str(&join_chunks(&paragraph.chunks, escape_markdown, |t| format!("*[{}]*", format_clock(t))));
+        out.push_str("\n\n");
+    }
+
+    out
+}
+
+fn header_fields(transcript: &TranscriptResult, event: &EventMetadata) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+
+    if let Some(date) = &event.date {
+        fields.push(("Date", date.clone()));
+    }
+    if let Some(location) = &event.location {
+        fields.push(("Location", location.clone()));
+    }
+    fields.push(("Source", transcript.filename.clone()));
+    fields.push(("Duration", format_clock(transcript.duration)));
+    fields.push(("Language", transcript.language.clone()));
+    if transcript.statistics.speakers_detected > 0 {
+        fields.push(("Speakers", transcript.statistics.speakers_detected.to_string()));
+    }
+    fields.push(("Words", transcript.statistics.total_words.to_string()));
+    fields.push(("Transcribed", transcript.timestamp.format("%Y-%m-%d %H:%M UTC").to_string()));
+
+    fields
+}
+
+fn build_paragraphs(transcript: &TranscriptResult, interval: Option<f64>, clean_read: bool) -> Vec<Paragraph> {
+    let interval = interval.filter(|i| *i > 0.0);
+    let mut next_marker = interval;
+    let mut paragraphs: Vec<Paragraph> = Vec::new();
+    let mut previous_end = 0.0;
+
+    for segment in &transcript.segments {
+        let text = reading_text(segment, clean_read);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let same_speaker = paragraphs
+            .last()
+            .map(|p| p.speaker == segment.speaker)
+            .unwrap_or(false);
+        let long_pause = segment.start - previous_end >= PARAGRAPH_PAUSE_SECONDS;
+
+        if !same_speaker || long_pause {
+            paragraphs.push(Paragraph {
+                speaker: segment.speaker.clone(),
+                start: segment.start,
+                continuation: same_speaker,
+                chunks: Vec::new(),
+            });
+        }
+        let paragraph = paragraphs.last_mut().unwrap();
+
+        // Emit a single marker for the latest interval boundary this segment crossed
+        if let (Some(step), Some(marker)) = (interval, next_marker) {
+            if segment.start >= marker {
+                let crossed = (segment.start / step).floor() * step;
+                paragraph.chunks.push(Chunk::Marker(crossed));
+                next_marker = Some(crossed + step);
+            }
+        }
+
+        paragraph.chunks.push(Chunk::Text(text.to_string()));
+        previous_end = segment.end;
+    }
+
+    paragraphs
+}
+
+fn speaker_header(paragraph: &Paragraph) -> String {
+    format!(
+        "{} ({})",
+        paragraph.speaker.as_deref().unwrap_or("SPEAKER").to_uppercase(),
+        format_clock(paragraph.start)
+    )
+}
+
+fn join_chunks(chunks: &[Chunk], text: impl Fn(&str) -> String, marker: impl Fn(f64) -> String) -> String {
+    chunks
+        .iter()
+        .map(|chunk| match chunk {
+            Chunk::Text(t) => text(t),
+            Chunk::Marker(t) => marker(*t),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Backslash-escape what Markdown would read as formatting, including a
+/// leading "-", "+" or "1." that would turn the line into a list
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    let digits = escaped.chars().take_while(char::is_ascii_digit).count();
+    if escaped.starts_with(['-', '+', '=']) {
+        escaped.insert(0, '\\');
+    } else if digits > 0 && escaped[digits..].starts_with(['.', ')']) {
+        escaped.insert(digits, '\\');
+    }
+    escaped
+}
@@ -0,0 +1,175 @@
+//! Prepared remarks against the delivery, for the speechwriting team.
+//!
+//! One `script` format writes two files, each passage in speaking order
+//! with its timestamp and a word-level diff against the prepared text:
+//! - `script.md`: ~~struck~~ words were dropped, **bold** words were added
+//! - `script.html`: the same with colored `<del>`/`<ins>`, and ad-libs listed
+//!   up top since that is where clips come from
+//!
+//! `--script` adds this format to whatever `--format` asked for.
+
+use anyhow::Result;
+
+use super::html::escape_html;
+use super::{format_clock, Artifact, TranscriptWriter};
+use crate::script::{DiffWord, Delivery, Edit, ScriptComparison, ScriptPassage};
+use crate::{Cli, TranscriptResult};
+
+const STYLE: &str = r#"
+body { margin: 0 auto; max-width: 860px; padding: 24px; font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; color: #222; background: #f6f7f9; }
+h1 { font-size: 20px; margin: 0 0 4px; }
+h2 { font-size: 16px; margin: 24px 0 8px; }
+.meta { color: #666; font-size: 13px; margin-bottom: 16px; }
+.passage { background: #fff; border-left: 4px solid #ccc; border-radius: 4px; padding: 8px 12px; margin-bottom: 10px; line-height: 1.6; }
+.passage .head { font-size: 12px; color: #666; margin-bottom: 2px; }
+.passage.verbatim { border-left-color: #2e8b57; }
+.passage.paraphrased { border-left-color: #e0a800; }
+.passage.skipped { border-left-color: #c0392b; opacity: 0.8; }
+.passage.ad_libbed { border-left-color: #1f5fbf; }
+.badge { display: inline-block; font-size: 11px; padding: 1px 6px; border-radius: 8px; margin-right: 6px; background: #e6e6e6; }
+del { background: #fbd9d9; color: #8a1c1c; }
+ins { background: #d4f4dd; color: #1b6b32; text-decoration: none; }
+ul { padding-left: 18px; }
+li { margin-bottom: 6px; }
+"#;
+
+pub struct ScriptWriter;
+
+impl TranscriptWriter for ScriptWriter {
+    fn format(&self) -> &'static str {
+        "script"
+    }
+
+    fn render(&self, _cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let Some(script) = &transcript.script else {
+            tracing::warn!("No --script given; skipping the script diff");
+            return Ok(Vec::new());
+        };
+
+        Ok(vec![
+            Artifact::text("script.md", render_markdown(&transcript.filename, script)),
+            Artifact::text("script.html", render_html(&transcript.filename, script)),
+        ])
+    }
+}
+
+pub fn render_markdown(filename: &str, script: &ScriptComparison) -> String {
+    let mut out = format!("# Prepared Remarks vs. Delivery: {}\n\n", filename);
+    out.push_str(&format!("- **Delivered:** {}\n", coverage_line(script)));
+    out.push_str(&format!("- **Passages:** {}\n\n", tally_line(script)));
+    out.push_str("~~Struck~~ words were in the script but not said; **bold** words were said but not in the script.\n\n---\n\n");
+
+    for passage in &script.passages {
+        out.push_str(&format!("**[{}] {}**", format_clock(passage.start), label(passage)));
+        if let Some(speaker) = &passage.speaker {
+            out.push_str(&format!(" ({})", speaker));
+        }
+        out.push(' ');
+        for (edit, words) in runs(&passage.diff) {
+            let words = words.join(" ");
+            match edit {
+                Edit::Same => out.push_str(&words),
+                Edit::Missing => out.push_str(&format!("~~{}~~", words)),
+                Edit::Added => out.push_str(&format!("**{}**", words)),
+            }
+            out.push(' ');
+        }
+        out.truncate(out.trim_end().len());
+        out.push_str("\n\n");
+    }
+
+    out
+}
+
+pub fn render_html(filename: &str, script: &ScriptComparison) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    html.push_str(&format!("<title>{} - Prepared Remarks vs. Delivery</title>\n", escape_html(filename)));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+
+    html.push_str(&format!("<h1>Prepared Remarks vs. Delivery: {}</h1>\n", escape_html(filename)));
+    html.push_str(&format!(
+        "<div class=\"meta\">Delivered {} &middot; {}</div>\n",
+        escape_html(&coverage_line(script)),
+        escape_html(&tally_line(script))
+    ));
+
+    let adlibs: Vec<&ScriptPassage> = script.passages.iter().filter(|p| p.delivery == Delivery::AdLibbed).collect();
+    if !adlibs.is_empty() {
+        html.push_str("<h2>Ad-libs</h2>\n<ul>\n");
+        for passage in adlibs {
+            html.push_str(&format!(
+                "<li><strong>{}</strong> {}</li>\n",
+                format_clock(passage.start),
+                escape_html(&passage.spoken)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    html.push_str("<h2>Full Text</h2>\n");
+    for passage in &script.passages {
+        html.push_str(&format!(
+            "<div class=\"passage {}\"><div class=\"head\"><span class=\"badge\">{}</span>{}{}</div>",
+            passage.delivery.as_str().replace('-', "_"),
+            label(passage),
+            format_clock(passage.start),
+            passage.speaker.as_ref().map(|s| format!(" &middot; {}", escape_html(s))).unwrap_or_default()
+        ));
+        let pieces: Vec<String> = runs(&passage.diff)
+            .into_iter()
+            .map(|(edit, words)| {
+                let words = escape_html(&words.join(" "));
+                match edit {
+                    Edit::Same => words,
+                    Edit::Missing => format!("<del>{}</del>", words),
+                    Edit::Added => format!("<ins>{}</ins>", words),
+                }
+            })
+            .collect();
+        html.push_str(&pieces.join(" "));
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn coverage_line(script: &ScriptComparison) -> String {
+    format!(
+        "{:.0}% of the prepared text ({} of {} words)",
+        script.coverage * 100.0,
+        script.delivered_words,
+        script.script_words
+    )
+}
+
+fn tally_line(script: &ScriptComparison) -> String {
+    let count = |delivery: Delivery| script.passages.iter().filter(|p| p.delivery == delivery).count();
+    format!(
+        "{} verbatim, {} paraphrased, {} skipped, {} ad-libbed ({} words)",
+        count(Delivery::Verbatim),
+        count(Delivery::Paraphrased),
+        count(Delivery::Skipped),
+        count(Delivery::AdLibbed),
+        script.adlib_words
+    )
+}
+
+fn label(passage: &ScriptPassage) -> String {
+    let mut chars = passage.delivery.as_str().chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// Neighbouring words with the same edit, grouped for one `<del>` or `~~`
+fn runs(diff: &[DiffWord]) -> Vec<(Edit, Vec<&str>)> {
+    let mut runs: Vec<(Edit, Vec<&str>)> = Vec::new();
+    for word in diff {
+        match runs.last_mut() {
+            Some((edit, words)) if *edit == word.edit => words.push(&word.text),
+            _ => runs.push((word.edit, vec![&word.text])),
+        }
+    }
+    runs
+}
@@ -0,0 +1,276 @@
+//! Tabular exports (CSV, TSV, Parquet) for notebooks and dashboards.
+//!
+//! The column layout is a stable contract; append new columns at the end and
+//! never rename or reorder existing ones.
+//!
+//! Segment table, one row per segment:
+//!
+//! | column     | type    | notes                                         |
+//! |------------|---------|-----------------------------------------------|
+//! | id         | u32     | segment id                                    |
+//! | start      | f64     | seconds from start of media                   |
+//! | end        | f64     | seconds from start of media                   |
+//! | speaker    | utf8?   | null when diarization is off                  |
+//! | text       | utf8    |                                               |
+//! | confidence | f32     | 0.0-1.0                                       |
+//! | sentiment  | utf8?   | positive / negative / neutral                 |
+//! | emphasis   | f32?    | 0.0-1.0                                       |
+//! | keywords   | utf8    | detected political keywords joined with `;`   |
+//! | categories | utf8    | distinct keyword categories joined with `;`   |
+//! | sentiment_score | f32? | compound score, -1.0 to 1.0                 |
+//!
+//! Word table (`--word-rows`), one row per word:
+//!
+//! | column     | type    | notes                                         |
+//! |------------|---------|-----------------------------------------------|
+//! | segment_id | u32     | id of the containing segment                  |
+//! | word_index | u32     | position of the word inside the segment       |
+//! | start      | f64     |                                               |
+//! | end        | f64     |                                               |
+//! | speaker    | utf8?   |                                               |
+//! | word       | utf8    |                                               |
+//! | confidence | f32     |                                               |
+//!
+//! Parquet files carry the same columns with the Arrow types listed above;
+//! `?` marks nullable columns. In CSV/TSV a null is an empty field.
+
+use anyhow::{Context, Result};
+use polars::prelude::*;
+
+use super::{Artifact, TranscriptWriter};
+use crate::{Cli, TranscriptResult, TranscriptSegment};
+
+pub const KEYWORD_SEPARATOR: &str = ";";
+
+const SEGMENT_COLUMNS: [&str; 11] = [
+    "id", "start", "end", "speaker", "text", "confidence", "sentiment", "emphasis", "keywords",
+    "categories", "sentiment_score",
+];
+
+const WORD_COLUMNS: [&str; 7] = [
+    "segment_id", "word_index", "start", "end", "speaker", "word", "confidence",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimited {
+    Csv,
+    Tsv,
+}
+
+pub struct CsvWriter;
+
+impl TranscriptWriter for CsvWriter {
+    fn format(&self) -> &'static str {
+        "csv"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        Ok(delimited_artifacts(cli, transcript, Delimited::Csv))
+    }
+}
+
+pub struct TsvWriter;
+
+impl TranscriptWriter for TsvWriter {
+    fn format(&self) -> &'static str {
+        "tsv"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        Ok(delimited_artifacts(cli, transcript, Delimited::Tsv))
+    }
+}
+
+pub struct ParquetTableWriter;
+
+impl TranscriptWriter for ParquetTableWriter {
+    fn format(&self) -> &'static str {
+        "parquet"
+    }
+
+    fn render(&self, cli: &Cli, transcript: &TranscriptResult) -> Result<Vec<Artifact>> {
+        let mut artifacts = vec![Artifact {
+            suffix: "parquet".to_string(),
+            contents: segments_parquet(transcript)?,
+        }];
+        if cli.word_rows {
+            artifacts.push(Artifact {
+                suffix: "words.parquet".to_string(),
+                contents: words_parquet(transcript)?,
+            });
+        }
+        Ok(artifacts)
+    }
+}
+
+fn delimited_artifacts(cli: &Cli, transcript: &TranscriptResult, delimited: Delimited) -> Vec<Artifact> {
+    let extension = match delimited {
+        Delimited::Csv => "csv",
+        Delimited::Tsv => "tsv",
+    };
+
+    let mut artifacts = vec![Artifact::text(extension, render_segments(transcript, delimited))];
+    if cli.word_rows {
+        artifacts.push(Artifact::text(
+            &format!("words.{}", extension),
+            render_words(transcript, delimited),
+        ));
+    }
+    artifacts
+}
+
+pub fn render_segments(transcript: &TranscriptResult, delimited: Delimited) -> String {
+    let rows = transcript.segments.iter().map(|s| {
+        vec![
+            s.id.to_string(),
+            format!("{:.3}", s.start),
+            format!("{:.3}", s.end),
+            s.speaker.clone().unwrap_or_default(),
+            s.text.clone(),
+            format!("{:.4}", s.confidence),
+            s.sentiment.as_ref().map(|x| x.label.clone()).unwrap_or_default(),
+            s.emphasis_level.map(|e| format!("{:.4}", e)).unwrap_or_default(),
+            joined_keywords(s),
+            joined_categories(s),
+            s.sentiment.as_ref().map(|x| format!("{:.4}", x.compound)).unwrap_or_default(),
+        ]
+    });
+
+    render_table(&SEGMENT_COLUMNS, rows, delimited)
+}
+
+pub fn render_words(transcript: &TranscriptResult, delimited: Delimited) -> String {
+    let rows = transcript.segments.iter().flat_map(|s| {
+        s.words.iter().enumerate().map(move |(index, word)| {
+            vec![
+                s.id.to_string(),
+                index.to_string(),
+                format!("{:.3}", word.start),
+                format!("{:.3}", word.end),
+                s.speaker.clone().unwrap_or_default(),
+                word.text.clone(),
+                format!("{:.4}", word.confidence),
+            ]
+        })
+    });
+
+    render_table(&WORD_COLUMNS, rows, delimited)
+}
+
+pub fn segments_parquet(transcript: &TranscriptResult) -> Result<Vec<u8>> {
+    let segments = &transcript.segments;
+
+    let mut frame = DataFrame::new(vec![
+        Series::new("id", segments.iter().map(|s| s.id as u32).collect::<Vec<_>>()),
+        Series::new("start", segments.iter().map(|s| s.start).collect::<Vec<_>>()),
+        Series::new("end", segments.iter().map(|s| s.end).collect::<Vec<_>>()),
+        Series::new("speaker", segments.iter().map(|s| s.speaker.clone()).collect::<Vec<_>>()),
+        Series::new("text", segments.iter().map(|s| s.text.clone()).collect::<Vec<_>>()),
+        Series::new("confidence", segments.iter().map(|s| s.confidence).collect::<Vec<_>>()),
+        Series::new("sentiment", segments.iter().map(|s| s.sentiment.as_ref().map(|x| x.label.clone())).collect::<Vec<_>>()),
+        Series::new("emphasis", segments.iter().map(|s| s.emphasis_level).collect::<Vec<_>>()),
+        Series::new("keywords", segments.iter().map(joined_keywords).collect::<Vec<_>>()),
+        Series::new("categories", segments.iter().map(joined_categories).collect::<Vec<_>>()),
+        Series::new("sentiment_score", segments.iter().map(|s| s.sentiment.as_ref().map(|x| x.compound)).collect::<Vec<_>>()),
+    ])
+    .context("Failed to build segment table")?;
+
+    write_parquet(&mut frame)
+}
+
+pub fn words_parquet(transcript: &TranscriptResult) -> Result<Vec<u8>> {
+    let mut segment_ids = Vec::new();
+    let mut word_indices = Vec::new();
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    let mut speakers = Vec::new();
+    let mut texts = Vec::new();
+    let mut confidences = Vec::new();
+
+    for segment in &transcript.segments {
+        for (index, word) in segment.words.iter().enumerate() {
+            segment_ids.push(segment.id as u32);
+            word_indices.push(index as u32);
+            starts.push(word.start);
+            ends.push(word.end);
+            speakers.push(segment.speaker.clone());
+            texts.push(word.text.clone());
+            confidences.push(word.confidence);
+        }
+    }
+
+    let mut frame = DataFrame::new(vec![
+        Series::new("segment_id", segment_ids),
+        Series::new("word_index", word_indices),
+        Series::new("start", starts),
+        Series::new("end", ends),
+        Series::new("speaker", speakers),
+        Series::new("word", texts),
+        Series::new("confidence", confidences),
+    ])
+    .context("Failed to build word table")?;
+
+    write_parquet(&mut frame)
+}
+
+fn joined_keywords(segment: &TranscriptSegment) -> String {
+    segment
+        .political_keywords
+        .iter()
+        .map(|k| k.term.as_str())
+        .collect::<Vec<_>>()
+        .join(KEYWORD_SEPARATOR)
+}
+
+fn joined_categories(segment: &TranscriptSegment) -> String {
+    let mut categories: Vec<&str> = Vec::new();
+    for keyword in &segment.political_keywords {
+        if !categories.contains(&keyword.category.as_str()) {
+            categories.push(&keyword.category);
+        }
+    }
+    categories.join(KEYWORD_SEPARATOR)
+}
+
+fn write_parquet(frame: &mut DataFrame) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    ParquetWriter::new(&mut buffer)
+        .finish(frame)
+        .context("Failed to encode Parquet")?;
+    Ok(buffer)
+}
+
+pub fn render_table(
+    columns: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+    delimited: Delimited,
+) -> String {
+    let separator = match delimited {
+        Delimited::Csv => ",",
+        Delimited::Tsv => "\t",
+    };
+
+    let mut out = columns.join(separator);
+    out.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|f| escape_field(f, delimited)).collect();
+        out.push_str(&fields.join(separator));
+        out.push('\n');
+    }
+    out
+}
+
+fn escape_field(field: &str, delimited: Delimited) -> String {
+    match delimited {
+        // RFC 4180 quoting
+        Delimited::Csv => {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        }
+        // TSV has no quoting; tabs and line breaks would split the record
+        Delimited::Tsv => field.replace(['\t', '\n', '\r'], " "),
+    }
+}